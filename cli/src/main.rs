@@ -175,14 +175,14 @@ fn encode_params(types: &[String], values: &[String], lenient: bool) -> Result<S
 fn decode_call_output(path: &str, function: &str, data: &str) -> Result<String, Error> {
 	let function = load_function(path, function)?;
 	let data : Vec<u8> = data.from_hex().chain_err(|| "Expected <data> to be hex")?;
-	let tokens = function.decode_output(&data)?;
+	let tokens = function.decode_output_named(&data)?.into_tokens();
 	let types = function.outputs;
 
 	assert_eq!(types.len(), tokens.len());
 
 	let result = types.iter()
 		.zip(tokens.iter())
-		.map(|(ty, to)| format!("{} {}", ty.kind, to))
+		.map(|(ty, to)| format!("{} {}", ty.kind, format_token(to)))
 		.collect::<Vec<String>>()
 		.join("\n");
 
@@ -202,7 +202,7 @@ fn decode_params(types: &[String], data: &str) -> Result<String, Error> {
 
 	let result = types.iter()
 		.zip(tokens.iter())
-		.map(|(ty, to)| format!("{} {}", ty, to))
+		.map(|(ty, to)| format!("{} {}", ty, format_token(to)))
 		.collect::<Vec<String>>()
 		.join("\n");
 
@@ -218,7 +218,7 @@ fn decode_log(path: &str, name_or_signature: &str, topics: &[String], data: &str
 	let decoded = event.parse_log((topics, data).into())?;
 
 	let result = decoded.params.into_iter()
-		.map(|log_param| format!("{} {}", log_param.name, log_param.value))
+		.map(|log_param| format!("{} {}", log_param.name, format_token(&log_param.value)))
 		.collect::<Vec<String>>()
 		.join("\n");
 
@@ -226,6 +226,24 @@ fn decode_log(path: &str, name_or_signature: &str, topics: &[String], data: &str
 }
 
 
+/// Formats decoded token in the raw form accepted by the tokenizers, so the output
+/// can be passed back to `encode`.
+fn format_token(token: &Token) -> String {
+	match *token {
+		Token::Bool(b) => b.to_string(),
+		Token::String(ref s) => s.clone(),
+		Token::Address(ref a) => format!("{:x}", a),
+		Token::Bytes(ref bytes) | Token::FixedBytes(ref bytes) => bytes.to_hex(),
+		Token::Uint(ref i) | Token::Int(ref i) => format!("{:x}", i),
+		Token::Array(ref tokens) | Token::FixedArray(ref tokens) => {
+			format!("[{}]", tokens.iter().map(format_token).collect::<Vec<_>>().join(","))
+		},
+		Token::Tuple(ref tokens) => {
+			format!("({})", tokens.iter().map(format_token).collect::<Vec<_>>().join(","))
+		},
+	}
+}
+
 fn hash_signature(sig: &str) -> Hash {
     let mut result = [0u8; 32];
     let data = sig.replace(" ", "").into_bytes();
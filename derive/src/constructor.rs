@@ -110,6 +110,8 @@ mod tests {
 				ethabi::Param {
 					name: "foo".into(),
 					kind: ethabi::ParamType::Uint(256),
+					internal_type: None,
+					components: vec![],
 				}
 			],
 		};
@@ -122,7 +124,9 @@ mod tests {
 				let c = ethabi::Constructor {
 					inputs: vec![ethabi::Param {
 						name: "foo".to_owned(),
-						kind: ethabi::ParamType::Uint(256usize)
+						kind: ethabi::ParamType::Uint(256usize),
+						internal_type: None,
+						components: vec![]
 					}],
 				};
 				let tokens = vec![ethabi::Token::Uint(foo.into())];
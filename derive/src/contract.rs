@@ -15,7 +15,9 @@ impl<'a> From<&'a ethabi::Contract> for Contract {
 	fn from(c: &'a ethabi::Contract) -> Self {
 		Contract {
 			constructor: c.constructor.as_ref().map(Into::into),
-			functions: c.functions().map(Into::into).collect(),
+			functions: c.functions.values()
+				.flat_map(|overloads| overloads.iter().enumerate().map(|(index, f)| Function::overload(f, index)))
+				.collect(),
 			events: c.events().map(Into::into).collect(),
 		}
 	}
@@ -67,7 +69,9 @@ mod test {
 			constructor: None,
 			functions: Default::default(),
 			events: Default::default(),
-			fallback: false,
+			errors: Default::default(),
+			fallback: None,
+			receive: None,
 		};
 
 		let c = Contract::from(&ethabi_contract);
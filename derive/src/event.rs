@@ -3,7 +3,7 @@ use heck::{SnakeCase, CamelCase};
 use proc_macro2::TokenStream;
 use syn::export::Span;
 
-use super::{rust_type, to_syntax_string, from_token, get_template_names, to_token};
+use super::{rust_type, to_syntax_string, to_internal_type, to_tuple_params, from_token, get_template_names, to_token};
 
 /// Structure used to generate contract's event interface.
 pub struct Event {
@@ -97,12 +97,16 @@ impl<'a> From<&'a ethabi::Event> for Event {
 			let name = &x.name;
 			let kind = to_syntax_string(&x.kind);
 			let indexed = x.indexed;
+			let internal_type = to_internal_type(&x.internal_type);
+			let components = to_tuple_params(&x.components);
 
 			quote! {
 				ethabi::EventParam {
 					name: #name.to_owned(),
 					kind: #kind,
-					indexed: #indexed
+					indexed: #indexed,
+					internal_type: #internal_type,
+					components: #components
 				}
 			}
 		}).collect::<Vec<_>>();
@@ -267,7 +271,9 @@ mod tests {
 			inputs: vec![ethabi::EventParam {
 				name: "foo".into(),
 				kind: ethabi::ParamType::Address,
-				indexed: true
+				indexed: true,
+				internal_type: None,
+				components: vec![]
 			}],
 			anonymous: false,
 		};
@@ -285,7 +291,9 @@ mod tests {
 						inputs: vec![ethabi::EventParam {
 							name: "foo".to_owned(),
 							kind: ethabi::ParamType::Address,
-							indexed: true
+							indexed: true,
+							internal_type: None,
+							components: vec![]
 						}],
 						anonymous: false,
 					}
@@ -326,7 +334,9 @@ mod tests {
 			inputs: vec![ethabi::EventParam {
 				name: "foo".into(),
 				kind: ethabi::ParamType::Address,
-				indexed: false
+				indexed: false,
+				internal_type: None,
+				components: vec![]
 			}],
 			anonymous: false,
 		};
@@ -350,15 +360,21 @@ mod tests {
 			inputs: vec![ethabi::EventParam {
 				name: "foo".into(),
 				kind: ethabi::ParamType::Address,
-				indexed: false
+				indexed: false,
+				internal_type: None,
+				components: vec![]
 			}, ethabi::EventParam {
 				name: "bar".into(),
 				kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::String)),
-				indexed: false
+				indexed: false,
+				internal_type: None,
+				components: vec![]
 			}, ethabi::EventParam {
 				name: "xyz".into(),
 				kind: ethabi::ParamType::Uint(256),
-				indexed: false
+				indexed: false,
+				internal_type: None,
+				components: vec![]
 			}],
 			anonymous: false,
 		};
@@ -5,7 +5,7 @@ use syn::export::Span;
 
 use super::{
 	input_names, template_param_type, rust_type, get_template_names, from_template_param, to_token,
-	to_ethabi_param_vec, get_output_kinds, from_token
+	to_ethabi_param_vec, to_ethabi_state_mutability, get_output_kinds, from_token
 };
 
 struct TemplateParam {
@@ -49,12 +49,14 @@ struct Outputs {
 pub struct Function {
 	/// Function name.
 	name: String,
+	/// Name of the generated module.
+	module_name: String,
 	/// Function input params.
 	inputs: Inputs,
 	/// Function output params.
 	outputs: Outputs,
-	/// Constant function.
-	constant: bool,
+	/// Quote used to recreate `ethabi::StateMutability`.
+	state_mutability: TokenStream,
 }
 
 impl<'a> From<&'a ethabi::Function> for Function {
@@ -99,7 +101,7 @@ impl<'a> From<&'a ethabi::Function> for Function {
 				let o = quote! { out };
 				let from_first = from_token(&f.outputs[0].kind, &o);
 				quote! {
-					let out = self.0.decode_output(output)?.into_iter().next().expect(INTERNAL_ERR);
+					let out = self.0.decode_output_named(output)?.into_tokens().into_iter().next().expect(INTERNAL_ERR);
 					Ok(#from_first)
 				}
 			},
@@ -111,7 +113,7 @@ impl<'a> From<&'a ethabi::Function> for Function {
 					.collect();
 
 				quote! {
-					let mut out = self.0.decode_output(output)?.into_iter();
+					let mut out = self.0.decode_output_named(output)?.into_tokens().into_iter();
 					Ok(( #(#outs),* ))
 				}
 			},
@@ -119,6 +121,7 @@ impl<'a> From<&'a ethabi::Function> for Function {
 
 		Function {
 			name: f.name.clone(),
+			module_name: f.name.to_snake_case(),
 			inputs: Inputs {
 				tokenize,
 				template_params,
@@ -129,22 +132,32 @@ impl<'a> From<&'a ethabi::Function> for Function {
 				result: output_result,
 				recreate_quote: to_ethabi_param_vec(&f.outputs),
 			},
-			constant: f.constant,
+			state_mutability: to_ethabi_state_mutability(f.state_mutability),
 		}
 	}
 }
 
 impl Function {
+	/// Creates the interface for an overload of contract's function. The first overload is
+	/// generated like a function which is not overloaded, the others get `_{index}` suffix.
+	pub fn overload(f: &ethabi::Function, index: usize) -> Self {
+		let mut function = Function::from(f);
+		if index > 0 {
+			function.module_name = format!("{}_{}", function.module_name, index);
+		}
+		function
+	}
+
 	/// Generates the interface for contract's function.
 	pub fn generate(&self) -> TokenStream {
 		let name = &self.name;
-		let module_name = syn::Ident::new(&self.name.to_snake_case(), Span::call_site());
+		let module_name = syn::Ident::new(&self.module_name, Span::call_site());
 		let tokenize = &self.inputs.tokenize;
 		let declarations: &Vec<_> = &self.inputs.template_params.iter().map(|i| &i.declaration).collect();
 		let definitions: &Vec<_> = &self.inputs.template_params.iter().map(|i| &i.definition).collect();
 		let recreate_inputs = &self.inputs.recreate_quote;
 		let recreate_outputs = &self.outputs.recreate_quote;
-		let constant = &self.constant;
+		let state_mutability = &self.state_mutability;
 		let outputs_result = &self.outputs.result;
 		let outputs_implementation = &self.outputs.implementation;
 
@@ -158,7 +171,7 @@ impl Function {
 						name: #name.into(),
 						inputs: #recreate_inputs,
 						outputs: #recreate_outputs,
-						constant: #constant,
+						state_mutability: #state_mutability,
 					}
 				}
 
@@ -207,7 +220,7 @@ mod tests {
 			name: "empty".into(),
 			inputs: vec![],
 			outputs: vec![],
-			constant: false,
+			state_mutability: ethabi::StateMutability::NonPayable,
 		};
 
 		let f = Function::from(&ethabi_function);
@@ -222,7 +235,7 @@ mod tests {
 						name: "empty".into(),
 						inputs: vec![],
 						outputs: vec![],
-						constant: false,
+						state_mutability: ethabi::StateMutability::NonPayable,
 					}
 				}
 
@@ -270,15 +283,19 @@ mod tests {
 				ethabi::Param {
 					name: "foo".into(),
 					kind: ethabi::ParamType::Address,
+					internal_type: None,
+					components: vec![],
 				}
 			],
 			outputs: vec![
 				ethabi::Param {
 					name: "bar".into(),
 					kind: ethabi::ParamType::Uint(256),
+					internal_type: None,
+					components: vec![],
 				}
 			],
-			constant: false,
+			state_mutability: ethabi::StateMutability::NonPayable,
 		};
 
 		let f = Function::from(&ethabi_function);
@@ -293,13 +310,17 @@ mod tests {
 						name: "hello".into(),
 						inputs: vec![ethabi::Param {
 							name: "foo".to_owned(),
-							kind: ethabi::ParamType::Address
+							kind: ethabi::ParamType::Address,
+							internal_type: None,
+							components: vec![]
 						}],
 						outputs: vec![ethabi::Param {
 							name: "bar".to_owned(),
-							kind: ethabi::ParamType::Uint(256usize)
+							kind: ethabi::ParamType::Uint(256usize),
+							internal_type: None,
+							components: vec![]
 						}],
-						constant: false,
+						state_mutability: ethabi::StateMutability::NonPayable,
 					}
 				}
 
@@ -310,7 +331,7 @@ mod tests {
 					type Output = ethabi::Uint;
 
 					fn decode(&self, output: &[u8]) -> ethabi::Result<Self::Output> {
-						let out = self.0.decode_output(output)?.into_iter().next().expect(INTERNAL_ERR);
+						let out = self.0.decode_output_named(output)?.into_tokens().into_iter().next().expect(INTERNAL_ERR);
 						Ok(out.to_uint().expect(INTERNAL_ERR))
 					}
 				}
@@ -347,23 +368,31 @@ mod tests {
 				ethabi::Param {
 					name: "foo".into(),
 					kind: ethabi::ParamType::FixedArray(Box::new(ethabi::ParamType::Address), 2),
+					internal_type: None,
+					components: vec![],
 				},
 				ethabi::Param {
 					name: "bar".into(),
 					kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256))),
+					internal_type: None,
+					components: vec![],
 				}
 			],
 			outputs: vec![
 				ethabi::Param {
 					name: "".into(),
 					kind: ethabi::ParamType::Uint(256),
+					internal_type: None,
+					components: vec![],
 				},
 				ethabi::Param {
 					name: "".into(),
 					kind: ethabi::ParamType::String,
+					internal_type: None,
+					components: vec![],
 				}
 			],
-			constant: false,
+			state_mutability: ethabi::StateMutability::NonPayable,
 		};
 
 		let f = Function::from(&ethabi_function);
@@ -378,19 +407,27 @@ mod tests {
 						name: "multi".into(),
 						inputs: vec![ethabi::Param {
 							name: "foo".to_owned(),
-							kind: ethabi::ParamType::FixedArray(Box::new(ethabi::ParamType::Address), 2usize)
+							kind: ethabi::ParamType::FixedArray(Box::new(ethabi::ParamType::Address), 2usize),
+							internal_type: None,
+							components: vec![]
 						}, ethabi::Param {
 							name: "bar".to_owned(),
-							kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256usize)))
+							kind: ethabi::ParamType::Array(Box::new(ethabi::ParamType::Uint(256usize))),
+							internal_type: None,
+							components: vec![]
 						}],
 						outputs: vec![ethabi::Param {
 							name: "".to_owned(),
-							kind: ethabi::ParamType::Uint(256usize)
+							kind: ethabi::ParamType::Uint(256usize),
+							internal_type: None,
+							components: vec![]
 						}, ethabi::Param {
 							name: "".to_owned(),
-							kind: ethabi::ParamType::String
+							kind: ethabi::ParamType::String,
+							internal_type: None,
+							components: vec![]
 						}],
-						constant: false,
+						state_mutability: ethabi::StateMutability::NonPayable,
 					}
 				}
 
@@ -401,7 +438,7 @@ mod tests {
 					type Output = (ethabi::Uint, String);
 
 					fn decode(&self, output: &[u8]) -> ethabi::Result<Self::Output> {
-						let mut out = self.0.decode_output(output)?.into_iter();
+						let mut out = self.0.decode_output_named(output)?.into_tokens().into_iter();
 						Ok((out.next().expect(INTERNAL_ERR).to_uint().expect(INTERNAL_ERR), out.next().expect(INTERNAL_ERR).to_string().expect(INTERNAL_ERR)))
 					}
 				}
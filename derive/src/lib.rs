@@ -17,7 +17,7 @@ use std::{env, fs};
 use std::path::PathBuf;
 use heck::SnakeCase;
 use syn::export::Span;
-use ethabi::{Result, ResultExt, Contract, Param, ParamType};
+use ethabi::{Result, ResultExt, Contract, Param, ParamType, StateMutability, TupleParam};
 
 const ERROR_MSG: &str = "`derive(EthabiContract)` failed";
 
@@ -96,6 +96,10 @@ fn to_syntax_string(param_type: &ethabi::ParamType) -> proc_macro2::TokenStream
 		ParamType::FixedArray(ref param_type, ref x) => {
 			let param_type_quote = to_syntax_string(param_type);
 			quote! { ethabi::ParamType::FixedArray(Box::new(#param_type_quote), #x) }
+		},
+		ParamType::Tuple(ref param_types) => {
+			let param_types_quote = param_types.iter().map(|t| to_syntax_string(t));
+			quote! { ethabi::ParamType::Tuple(ethabi::tuple_components![#(Box::new(#param_types_quote)),*]) }
 		}
 	}
 }
@@ -106,10 +110,14 @@ fn to_ethabi_param_vec<'a, P: 'a>(params: P) -> proc_macro2::TokenStream
 	let p = params.into_iter().map(|x| {
 		let name = &x.name;
 		let kind = to_syntax_string(&x.kind);
+		let internal_type = to_internal_type(&x.internal_type);
+		let components = to_tuple_params(&x.components);
 		quote! {
 			ethabi::Param {
 				name: #name.to_owned(),
-				kind: #kind
+				kind: #kind,
+				internal_type: #internal_type,
+				components: #components
 			}
 		}
 	}).collect::<Vec<_>>();
@@ -117,6 +125,44 @@ fn to_ethabi_param_vec<'a, P: 'a>(params: P) -> proc_macro2::TokenStream
 	quote! { vec![ #(#p),* ] }
 }
 
+fn to_tuple_params(components: &[TupleParam]) -> proc_macro2::TokenStream {
+	let c = components.iter().map(|x| {
+		let name = match x.name {
+			Some(ref name) => quote! { Some(#name.to_owned()) },
+			None => quote! { None },
+		};
+		let kind = to_syntax_string(&x.kind);
+		let internal_type = to_internal_type(&x.internal_type);
+		let components = to_tuple_params(&x.components);
+		quote! {
+			ethabi::TupleParam {
+				name: #name,
+				kind: #kind,
+				internal_type: #internal_type,
+				components: #components
+			}
+		}
+	}).collect::<Vec<_>>();
+
+	quote! { vec![ #(#c),* ] }
+}
+
+fn to_internal_type(internal_type: &Option<String>) -> proc_macro2::TokenStream {
+	match *internal_type {
+		Some(ref internal_type) => quote! { Some(#internal_type.to_owned()) },
+		None => quote! { None },
+	}
+}
+
+fn to_ethabi_state_mutability(state_mutability: StateMutability) -> proc_macro2::TokenStream {
+	match state_mutability {
+		StateMutability::Pure => quote! { ethabi::StateMutability::Pure },
+		StateMutability::View => quote! { ethabi::StateMutability::View },
+		StateMutability::NonPayable => quote! { ethabi::StateMutability::NonPayable },
+		StateMutability::Payable => quote! { ethabi::StateMutability::Payable },
+	}
+}
+
 fn rust_type(input: &ParamType) -> proc_macro2::TokenStream {
 	match *input {
 		ParamType::Address => quote! { ethabi::Address },
@@ -134,7 +180,11 @@ fn rust_type(input: &ParamType) -> proc_macro2::TokenStream {
 		ParamType::FixedArray(ref kind, size) => {
 			let t = rust_type(&*kind);
 			quote! { [#t, #size] }
-		}
+		},
+		ParamType::Tuple(ref kinds) => {
+			let t = kinds.iter().map(|kind| rust_type(kind));
+			quote! { (#(#t,)*) }
+		},
 	}
 }
 
@@ -161,7 +211,11 @@ fn template_param_type(input: &ParamType, index: usize) -> proc_macro2::TokenStr
 			quote! {
 				#t_ident: Into<[#u_ident; #size]>, #u_ident: Into<#t>
 			}
-		}
+		},
+		ParamType::Tuple(_) => {
+			let t = rust_type(input);
+			quote! { #t_ident: Into<#t> }
+		},
 	}
 }
 
@@ -204,6 +258,18 @@ fn to_token(name: &proc_macro2::TokenStream, kind: &ParamType) -> proc_macro2::T
 				}
 			}
 		},
+		ParamType::Tuple(ref kinds) => {
+			let names: Vec<_> = (0..kinds.len())
+				.map(|index| syn::Ident::new(&format!("component{}", index), Span::call_site()))
+				.collect();
+			let tokens: Vec<_> = names.iter().zip(kinds.iter()).map(|(name, kind)| to_token(&quote! { #name }, kind)).collect();
+			quote! {
+				{
+					let (#(#names,)*) = #name;
+					ethabi::Token::Tuple(vec![#(#tokens),*])
+				}
+			}
+		},
 	}
 }
 
@@ -255,6 +321,24 @@ fn from_token(kind: &ParamType, token: &proc_macro2::TokenStream) -> proc_macro2
 				}
 			}
 		},
+		ParamType::Tuple(ref kinds) => {
+			let inner = quote! { inner };
+			let components = kinds.iter().map(|kind| {
+				let from_inner = from_token(kind, &inner);
+				quote! {
+					{
+						let #inner = iter.next().expect(INTERNAL_ERR);
+						#from_inner
+					}
+				}
+			});
+			quote! {
+				{
+					let mut iter = #token.to_tuple().expect(INTERNAL_ERR).into_iter();
+					(#(#components,)*)
+				}
+			}
+		},
 	}
 }
 
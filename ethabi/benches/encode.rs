@@ -0,0 +1,66 @@
+//! Compares encoding to a vector with encoding directly to a writer, and building tokens
+//! before encoding them with pushing the values to an `AbiEncoder`.
+//!
+//! Run with `cargo bench -p ethabi`.
+
+#[macro_use]
+extern crate bencher;
+extern crate ethabi;
+
+use std::io;
+use bencher::Bencher;
+use ethabi::{AbiEncoder, Token, encode, encode_to_writer};
+
+/// Array of 10 000 dynamic tuples, about 2.5 MB encoded.
+fn large_array() -> Vec<Token> {
+	let element = Token::Tuple(vec![
+		Token::Address([0x11u8; 20].into()),
+		Token::Uint(1_000_000.into()),
+		Token::Bytes(vec![0x22; 100]),
+	]);
+	vec![Token::Array(vec![element; 10_000])]
+}
+
+fn encode_large_array(b: &mut Bencher) {
+	let tokens = large_array();
+	b.iter(|| {
+		let encoded = encode(&tokens);
+		io::Write::write_all(&mut io::sink(), &encoded).unwrap();
+	});
+}
+
+fn encode_large_array_to_writer(b: &mut Bencher) {
+	let tokens = large_array();
+	b.iter(|| encode_to_writer(&tokens, &mut io::sink()).unwrap());
+}
+
+fn build_and_encode_tokens(b: &mut Bencher) {
+	b.iter(|| {
+		let elements = (0..10_000u64)
+			.map(|i| Token::Tuple(vec![
+				Token::Address([0x11u8; 20].into()),
+				Token::Uint(i.into()),
+				Token::Bytes(vec![0x22; 100]),
+			]))
+			.collect();
+		encode(&[Token::Array(elements)])
+	});
+}
+
+fn build_with_abi_encoder(b: &mut Bencher) {
+	let bytes = vec![0x22; 100];
+	b.iter(|| {
+		let mut encoder = AbiEncoder::new();
+		encoder.push_array(|array| {
+			for i in 0..10_000u64 {
+				array.push_tuple(|element| {
+					element.push_address([0x11u8; 20]).push_uint(i.into()).push_bytes(&bytes);
+				});
+			}
+		});
+		encoder.finish().unwrap()
+	});
+}
+
+benchmark_group!(benches, encode_large_array, encode_large_array_to_writer, build_and_encode_tokens, build_with_abi_encoder);
+benchmark_main!(benches);
@@ -0,0 +1,44 @@
+//! Measures parsing and cloning of tuple types, which allocate their components.
+//!
+//! Run with `cargo bench -p ethabi --bench param_type`, and again with `--features smallvec`
+//! to compare with components stored inline.
+
+#[macro_use]
+extern crate bencher;
+extern crate ethabi;
+
+use bencher::Bencher;
+use ethabi::ParamType;
+use ethabi::param_type::Reader;
+
+/// Types of a typical ABI using structs, mostly small tuples.
+const TYPES: &[&str] = &[
+	"(address,uint256)",
+	"(address,uint256,bytes)[]",
+	"(uint256,(address,bool),bytes32)",
+	"((address,uint256)[],(uint8,bytes32,bytes32))",
+	"(address,address,uint256,uint256,uint256,uint256,bytes,bool)",
+	"(bytes32,(uint256,uint256),(address,uint96)[2])[]",
+];
+
+fn parse_tuple_types(b: &mut Bencher) {
+	b.iter(|| {
+		for _ in 0..100 {
+			for kind in TYPES {
+				Reader::read(kind).unwrap();
+			}
+		}
+	});
+}
+
+fn clone_tuple_types(b: &mut Bencher) {
+	let types: Vec<ParamType> = TYPES.iter().map(|kind| Reader::read(kind).unwrap()).collect();
+	b.iter(|| {
+		for _ in 0..100 {
+			bencher::black_box(types.clone());
+		}
+	});
+}
+
+benchmark_group!(benches, parse_tuple_types, clone_tuple_types);
+benchmark_main!(benches);
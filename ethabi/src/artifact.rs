@@ -0,0 +1,263 @@
+//! Compilation artifacts of development frameworks, which contain the ABI along with the bytecode.
+
+use std::collections::HashMap;
+use serde_json;
+use {errors, Contract};
+
+/// Contract compiled by Hardhat or Foundry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Artifact {
+	/// Name of the contract, Foundry artifacts don't have it.
+	pub contract_name: Option<String>,
+	/// Contract ABI.
+	pub abi: Contract,
+	/// `0x` prefixed hex of the creation bytecode.
+	///
+	/// Kept as hex, since bytecode of contracts using libraries contains placeholders
+	/// for their addresses until they are linked.
+	pub bytecode: String,
+}
+
+/// Hardhat artifact, e.g. `artifacts/contracts/Token.sol/Token.json`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HardhatArtifact {
+	contract_name: String,
+	abi: Contract,
+	bytecode: String,
+}
+
+/// Foundry artifact, e.g. `out/Token.sol/Token.json`.
+#[derive(Deserialize)]
+struct FoundryArtifact {
+	abi: Contract,
+	bytecode: FoundryBytecode,
+}
+
+#[derive(Deserialize)]
+struct FoundryBytecode {
+	object: String,
+}
+
+/// Output of `solc --combined-json abi` or `solc --standard-json`.
+#[derive(Deserialize)]
+struct SolcOutput {
+	contracts: HashMap<String, SolcContracts>,
+}
+
+/// Entry of the `contracts` map of the solc output.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SolcContracts {
+	/// `--combined-json` contract, keyed by `file.sol:Contract`.
+	Combined { abi: SolcAbi },
+	/// `--standard-json` source file, maps contract names to contracts.
+	Standard(HashMap<String, SolcStandardContract>),
+}
+
+#[derive(Deserialize)]
+struct SolcStandardContract {
+	abi: SolcAbi,
+}
+
+/// ABI of the solc output, older versions write it as a json encoded string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SolcAbi {
+	Encoded(String),
+	Decoded(Box<Contract>),
+}
+
+impl SolcAbi {
+	fn into_contract(self) -> errors::Result<Contract> {
+		match self {
+			SolcAbi::Encoded(json) => Contract::from_json(&json),
+			SolcAbi::Decoded(contract) => Ok(*contract),
+		}
+	}
+}
+
+impl Artifact {
+	/// Loads artifact from Hardhat json, `{"contractName": ..., "abi": [...], "bytecode": "0x..."}`.
+	pub fn from_hardhat(json: &str) -> errors::Result<Self> {
+		let artifact: HardhatArtifact = serde_json::from_str(json)?;
+		Ok(Artifact {
+			contract_name: Some(artifact.contract_name),
+			abi: artifact.abi,
+			bytecode: artifact.bytecode,
+		})
+	}
+
+	/// Loads artifact from Foundry json, `{"abi": [...], "bytecode": {"object": "0x..."}}`.
+	pub fn from_foundry(json: &str) -> errors::Result<Self> {
+		let artifact: FoundryArtifact = serde_json::from_str(json)?;
+		Ok(Artifact {
+			contract_name: None,
+			abi: artifact.abi,
+			bytecode: artifact.bytecode.object,
+		})
+	}
+}
+
+impl Contract {
+	/// Loads contracts from the output of the Solidity compiler, mapping contract names to contracts.
+	///
+	/// Accepts the output of `solc --combined-json abi`, `{"contracts": {"file.sol:Contract": {"abi": ...}}}`,
+	/// and the output of `solc --standard-json`, `{"contracts": {"file.sol": {"Contract": {"abi": [...]}}}}`.
+	/// Contracts with the same name in different files are an error.
+	pub fn from_solidity_json(json: &str) -> errors::Result<HashMap<String, Self>> {
+		let output: SolcOutput = serde_json::from_str(json)?;
+		let mut abis = vec![];
+		for (key, contracts) in output.contracts {
+			match contracts {
+				SolcContracts::Combined { abi } => {
+					let name = key.rsplit(':').next().unwrap_or_default().to_owned();
+					abis.push((name, abi));
+				},
+				SolcContracts::Standard(contracts) => {
+					abis.extend(contracts.into_iter().map(|(name, contract)| (name, contract.abi)));
+				},
+			}
+		}
+
+		let mut result = HashMap::new();
+		for (name, abi) in abis {
+			let contract = abi.into_contract()?;
+			if result.insert(name.clone(), contract).is_some() {
+				bail!("Multiple contracts named `{}`", name);
+			}
+		}
+
+		Ok(result)
+	}
+
+	/// Loads contract from Hardhat artifact json, see `Artifact::from_hardhat`.
+	pub fn from_hardhat_artifact(json: &str) -> errors::Result<Self> {
+		Artifact::from_hardhat(json).map(|artifact| artifact.abi)
+	}
+
+	/// Loads contract from Foundry artifact json, see `Artifact::from_foundry`.
+	pub fn from_foundry_artifact(json: &str) -> errors::Result<Self> {
+		Artifact::from_foundry(json).map(|artifact| artifact.abi)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use hex::{FromHex, ToHex};
+	use {Artifact, Contract};
+
+	// artifacts from the test data of ethers-rs, https://github.com/gakonst/ethers-rs
+	const HARDHAT: &str = include_str!("../../res/Verifier.hardhat.json");
+	const FOUNDRY: &str = include_str!("../../res/Greeter.foundry.json");
+	// hand-written in the formats of the solc 0.7 and 0.8 output for res/Counter.sol, until they
+	// are replaced with the output of tools/solc_compile.sh
+	const SOLC_COMBINED: &str = include_str!("../../res/Counter.combined.json");
+	const SOLC_COMBINED_0_8: &str = include_str!("../../res/Counter.combined-0.8.json");
+	const SOLC_STANDARD: &str = include_str!("../../res/Counter.standard.json");
+
+	/// Decodes `0x` prefixed bytecode if it ends with the CBOR encoded metadata appended by solc,
+	/// whose length is in the last 2 bytes, so that truncated bytecode is rejected.
+	fn complete_bytecode(bytecode: &str) -> Option<Vec<u8>> {
+		if !bytecode.starts_with("0x") {
+			return None;
+		}
+		let bytes: Vec<u8> = bytecode[2..].from_hex().ok()?;
+		let len = bytes.len();
+		if len < 2 {
+			return None;
+		}
+		let metadata_len = ((bytes[len - 2] as usize) << 8) | bytes[len - 1] as usize;
+		if metadata_len + 2 > len {
+			return None;
+		}
+		// a CBOR map of the `ipfs` or `bzzr` hash and the `solc` version
+		let metadata = &bytes[len - 2 - metadata_len..len - 2];
+		match metadata.first() {
+			Some(0xa1..=0xa3) if metadata.windows(4).any(|w| w == b"solc") => Some(bytes),
+			_ => None,
+		}
+	}
+
+	#[test]
+	fn hardhat_artifact() {
+		let artifact = Artifact::from_hardhat(HARDHAT).unwrap();
+		assert_eq!(artifact.contract_name, Some("Verifier".to_owned()));
+		// this artifact comes without bytecode, which Hardhat writes as `0x`
+		assert_eq!(artifact.bytecode, "0x");
+		assert!(artifact.abi.constructor().is_some());
+		assert_eq!(
+			artifact.abi.function("verify").unwrap().canonical_signature(),
+			concat!(
+				"verify(uint256[],((uint256,uint256),(uint256[2],uint256[2]),(uint256,uint256)),",
+				"((uint256,uint256),(uint256[2],uint256[2]),(uint256[2],uint256[2]),(uint256[2],uint256[2]),(uint256,uint256)[]))"
+			)
+		);
+
+		assert_eq!(Contract::from_hardhat_artifact(HARDHAT).unwrap(), artifact.abi);
+	}
+
+	#[test]
+	fn foundry_artifact() {
+		let artifact = Artifact::from_foundry(FOUNDRY).unwrap();
+		assert_eq!(artifact.contract_name, None);
+		let greet = artifact.abi.function("greet").unwrap();
+		assert_eq!(greet.canonical_signature(), "greet((bool))");
+		assert_eq!(greet.selector(), [0x55, 0x81, 0x70, 0x1b]);
+
+		let bytecode = complete_bytecode(&artifact.bytecode).unwrap();
+		// the dispatcher of the deployed code compares the calldata with `PUSH4 <selector>`
+		assert!(bytecode.to_hex::<String>().contains("635581701b"));
+
+		assert_eq!(Contract::from_foundry_artifact(FOUNDRY).unwrap(), artifact.abi);
+	}
+
+	#[test]
+	fn truncated_bytecode_is_incomplete() {
+		let bytecode = Artifact::from_foundry(FOUNDRY).unwrap().bytecode;
+		assert!(complete_bytecode(&bytecode[..bytecode.len() - 8]).is_none());
+		assert!(complete_bytecode(&bytecode[..bytecode.len() - 1]).is_none());
+		assert!(complete_bytecode(&bytecode[2..]).is_none());
+		assert!(complete_bytecode("0x").is_none());
+	}
+
+	#[test]
+	fn bare_abi_is_not_an_artifact() {
+		let abi = include_str!("../../res/eip20.abi");
+		assert!(Contract::from_hardhat_artifact(abi).is_err());
+		assert!(Contract::from_foundry_artifact(abi).is_err());
+		assert!(Contract::from_foundry_artifact(HARDHAT).is_err());
+	}
+
+	#[test]
+	fn solidity_json() {
+		let contracts = Contract::from_solidity_json(SOLC_COMBINED).unwrap();
+		assert_eq!(contracts.len(), 2);
+		let counter = &contracts["Counter"];
+		assert_eq!(counter.function("count").unwrap().selector(), [0x06, 0x66, 0x1a, 0xbd]);
+		assert_eq!(counter.function("increment").unwrap().selector(), [0xd0, 0x9d, 0xe0, 0x8a]);
+		assert!(counter.event("Incremented").is_ok());
+		let ownable = &contracts["Ownable"];
+		assert_eq!(ownable.function("transferOwnership").unwrap().canonical_signature(), "transferOwnership(address)");
+		assert!(ownable.event("OwnershipTransferred").is_ok());
+		assert!(ownable.constructor().is_some());
+
+		// solc 0.8 encodes the abi in combined json as an array, before 0.8 as a string
+		assert_eq!(Contract::from_solidity_json(SOLC_COMBINED_0_8).unwrap(), contracts);
+		assert_eq!(Contract::from_solidity_json(SOLC_STANDARD).unwrap(), contracts);
+	}
+
+	#[test]
+	fn invalid_solidity_json() {
+		let duplicate = r#"{"contracts": {
+			"a/Token.sol:Token": {"abi": "[]"},
+			"b/Token.sol:Token": {"abi": []}
+		}}"#;
+		let err = Contract::from_solidity_json(duplicate).unwrap_err();
+		assert_eq!(format!("{}", err), "Multiple contracts named `Token`");
+
+		assert!(Contract::from_solidity_json(r#"{"contracts": {"Token.sol:Token": {"abi": "[{"}}}"#).is_err());
+		assert!(Contract::from_solidity_json(include_str!("../../res/eip20.abi")).is_err());
+		assert!(Contract::from_solidity_json(HARDHAT).is_err());
+	}
+}
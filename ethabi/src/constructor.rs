@@ -1,8 +1,10 @@
 //! Contract constructor call builder.
-use {Param, Result, ErrorKind, Token, ParamType, encode, Bytes};
+use {Param, Result, Token, ParamType, encode_with_types, decode, Bytes};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Contract constructor specification.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Constructor {
 	/// Constructor input.
 	pub inputs: Vec<Param>,
@@ -20,10 +22,54 @@ impl Constructor {
 	pub fn encode_input(&self, code: Bytes, tokens: &[Token]) -> Result<Bytes> {
 		let params = self.param_types();
 
-		if Token::types_check(tokens, &params) {
-			Ok(code.into_iter().chain(encode(tokens)).collect())
-		} else {
-			Err(ErrorKind::InvalidData.into())
+		Ok(code.into_iter().chain(encode_with_types(&params, tokens)?).collect())
+	}
+
+	/// Parses the ABI encoded constructor arguments, found after the code in deploy data.
+	pub fn decode_input(&self, data: &[u8]) -> Result<Vec<Token>> {
+		decode(&self.param_types(), data)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {Constructor, Param, ParamType, Token};
+
+	fn constructor() -> Constructor {
+		Constructor {
+			inputs: vec![Param {
+				name: "owner".to_owned(),
+				kind: ParamType::Address,
+				internal_type: None,
+				components: vec![],
+			}, Param {
+				name: "name".to_owned(),
+				kind: ParamType::String,
+				internal_type: None,
+				components: vec![],
+			}],
 		}
 	}
+
+	#[test]
+	fn test_constructor_encode_decode_input() {
+		let code = hex!("6060604052").to_vec();
+		let tokens = vec![Token::Address([0x11u8; 20].into()), Token::String("gavofyork".into())];
+		let encoded = constructor().encode_input(code.clone(), &tokens).unwrap();
+		let expected = hex!("
+			6060604052
+			0000000000000000000000001111111111111111111111111111111111111111
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000009
+			6761766f66796f726b0000000000000000000000000000000000000000000000
+		").to_vec();
+		assert_eq!(encoded, expected);
+		assert_eq!(constructor().decode_input(&encoded[code.len()..]).unwrap(), tokens);
+	}
+
+	#[test]
+	fn test_constructor_encode_invalid_input() {
+		assert!(constructor().encode_input(vec![], &[Token::Address([0x11u8; 20].into())]).is_err());
+		assert!(constructor().encode_input(vec![], &[Token::Bool(true), Token::String("".into())]).is_err());
+	}
 }
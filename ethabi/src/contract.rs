@@ -2,23 +2,74 @@ use std::{io, fmt};
 use std::collections::HashMap;
 use std::collections::hash_map::Values;
 use std::iter::Flatten;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::{Visitor, SeqAccess};
+use serde::ser::SerializeSeq;
 use serde_json;
 use operation::Operation;
-use {errors, ErrorKind, Event, Constructor, Function};
+use param_type::Writer;
+use {errors, Bytes, ErrorKind, Event, Constructor, CustomError, Fallback, Function, Hash, ParamType, Receive, Token};
+
+/// Differences between the functions of two versions of a contract ABI, see `Contract::diff`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContractDiff {
+	/// Names of functions only present in the new version.
+	pub added: Vec<String>,
+	/// Names of functions only present in the old version.
+	pub removed: Vec<String>,
+	/// Functions present in both versions with different signatures,
+	/// as `(name, old signature, new signature)`.
+	pub changed: Vec<(String, String, String)>,
+}
+
+impl ContractDiff {
+	/// Returns true if the new version is backward compatible, i.e. no function was removed
+	/// or changed.
+	pub fn is_compatible(&self) -> bool {
+		self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
+/// Kind of issue found by `Contract::validate_abi`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AbiWarningKind {
+	/// A tuple param without components.
+	EmptyTuple,
+	/// Two functions with the same selector, calls can only reach one of them.
+	SelectorClash,
+	/// An indexed param of a dynamic type in an anonymous event, only its hash is logged
+	/// and the log can't be matched by its event topic.
+	UndecodableIndexedParam,
+	/// An event with more than the 4 topics a log can have.
+	TooManyTopics,
+}
+
+/// Issue found in a contract ABI, see `Contract::validate_abi`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AbiWarning {
+	/// Kind of issue.
+	pub kind: AbiWarningKind,
+	/// Name of the function, event or error with the issue, or `constructor`.
+	pub location: String,
+	/// Description of the issue.
+	pub description: String,
+}
 
 /// API building calls to contracts ABI.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Contract {
 	/// Contract constructor.
 	pub constructor: Option<Constructor>,
-	/// Contract functions.
-	pub functions: HashMap<String, Function>,
+	/// Contract functions, maps name to overloaded functions.
+	pub functions: HashMap<String, Vec<Function>>,
 	/// Contract events, maps signature to event.
 	pub events: HashMap<String, Vec<Event>>,
-	/// Contract has fallback function.
-	pub fallback: bool,
+	/// Contract custom errors, maps name to error.
+	pub errors: HashMap<String, CustomError>,
+	/// Contract fallback function.
+	pub fallback: Option<Fallback>,
+	/// Contract receive function.
+	pub receive: Option<Receive>,
 }
 
 impl<'a> Deserialize<'a> for Contract {
@@ -41,7 +92,9 @@ impl<'a> Visitor<'a> for ContractVisitor {
 			constructor: None,
 			functions: HashMap::default(),
 			events: HashMap::default(),
-			fallback: false,
+			errors: HashMap::default(),
+			fallback: None,
+			receive: None,
 		};
 
 		while let Some(operation) = seq.next_element()? {
@@ -50,13 +103,19 @@ impl<'a> Visitor<'a> for ContractVisitor {
 					result.constructor = Some(constructor);
 				},
 				Operation::Function(func) => {
-					result.functions.insert(func.name.clone(), func);
+					result.functions.entry(func.name.clone()).or_default().push(func);
 				},
 				Operation::Event(event) => {
 					result.events.entry(event.name.clone()).or_default().push(event);
 				},
-				Operation::Fallback => {
-					result.fallback = true;
+				Operation::Error(error) => {
+					result.errors.insert(error.name.clone(), error);
+				},
+				Operation::Fallback(fallback) => {
+					result.fallback = Some(fallback);
+				},
+				Operation::Receive(receive) => {
+					result.receive = Some(receive);
 				},
 			}
 		}
@@ -65,20 +124,361 @@ impl<'a> Visitor<'a> for ContractVisitor {
 	}
 }
 
+impl Serialize for Contract {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		/// ABI json entry, `value` tagged with its `type`.
+		#[derive(Serialize)]
+		struct Entry<'a, T: 'a> {
+			#[serde(rename = "type")]
+			kind: &'static str,
+			#[serde(flatten)]
+			value: &'a T,
+		}
+
+		// sort by name, so that the output doesn't depend on the hash map order
+		let mut functions: Vec<_> = self.functions.iter().collect();
+		functions.sort_by(|a, b| a.0.cmp(b.0));
+		let mut events: Vec<_> = self.events.iter().collect();
+		events.sort_by(|a, b| a.0.cmp(b.0));
+		let mut errors: Vec<_> = self.errors.iter().collect();
+		errors.sort_by(|a, b| a.0.cmp(b.0));
+
+		let mut seq = serializer.serialize_seq(None)?;
+		if let Some(ref constructor) = self.constructor {
+			seq.serialize_element(&Entry { kind: "constructor", value: constructor })?;
+		}
+		for function in functions.into_iter().flat_map(|(_, functions)| functions) {
+			seq.serialize_element(&Entry { kind: "function", value: function })?;
+		}
+		for event in events.into_iter().flat_map(|(_, events)| events) {
+			seq.serialize_element(&Entry { kind: "event", value: event })?;
+		}
+		for (_, error) in errors {
+			seq.serialize_element(&Entry { kind: "error", value: error })?;
+		}
+		if let Some(ref fallback) = self.fallback {
+			seq.serialize_element(&Entry { kind: "fallback", value: fallback })?;
+		}
+		if let Some(ref receive) = self.receive {
+			seq.serialize_element(&Entry { kind: "receive", value: receive })?;
+		}
+		seq.end()
+	}
+}
+
 impl Contract {
 	/// Loads contract from json.
 	pub fn load<T: io::Read>(reader: T) -> errors::Result<Self> {
 		serde_json::from_reader(reader).map_err(From::from)
 	}
 
+	/// Loads contract from json string.
+	pub fn from_json(json: &str) -> errors::Result<Self> {
+		serde_json::from_str(json).map_err(From::from)
+	}
+
+	/// Loads contract from json read from `reader`, without reading it into a string first.
+	///
+	/// The reader is read byte by byte, wrap files and sockets in `io::BufReader`.
+	pub fn from_json_reader<T: io::Read>(reader: T) -> errors::Result<Self> {
+		serde_json::from_reader(reader).map_err(From::from)
+	}
+
+	/// Loads contract from an already parsed json value, e.g. a field of a JSON-RPC response.
+	pub fn from_json_value(value: serde_json::Value) -> errors::Result<Self> {
+		serde_json::from_value(value).map_err(From::from)
+	}
+
+	/// Serializes contract to json in the ABI format.
+	pub fn to_json(&self) -> errors::Result<String> {
+		serde_json::to_string(self).map_err(From::from)
+	}
+
+	/// Serializes contract to json in the ABI format, writing it to `writer` as it goes.
+	pub fn to_json_writer<T: io::Write>(&self, writer: T) -> errors::Result<()> {
+		serde_json::to_writer(writer, self).map_err(From::from)
+	}
+
+	/// Serializes contract to a json value in the ABI format.
+	pub fn to_json_value(&self) -> errors::Result<serde_json::Value> {
+		serde_json::to_value(self).map_err(From::from)
+	}
+
+	/// Combines the ABIs of two contracts, e.g. of a proxy and its implementation.
+	///
+	/// The result has the functions, events and errors of both contracts, including all overloads.
+	/// Entries present in both are included once, functions are the same if their canonical
+	/// signatures are. Functions with different signatures but the same selector are a
+	/// conflict, and result in an error. The constructor, fallback and receive
+	/// functions of `self` take precedence.
+	pub fn merge(&self, other: &Contract) -> errors::Result<Contract> {
+		let mut result = self.clone();
+
+		for function in other.functions() {
+			match self.function_by_selector(&function.selector()) {
+				Some(existing) if existing.canonical_signature() == function.canonical_signature() => continue,
+				Some(existing) => bail!(
+					"Function `{}` conflicts with `{}`, both have selector {}",
+					function.canonical_signature(), existing.canonical_signature(), function.selector_hex()
+				),
+				None => result.functions.entry(function.name.clone()).or_default().push(function.clone()),
+			}
+		}
+
+		for event in other.events() {
+			let events = result.events.entry(event.name.clone()).or_default();
+			if !events.contains(event) {
+				events.push(event.clone());
+			}
+		}
+
+		for (name, error) in &other.errors {
+			result.errors.entry(name.clone()).or_insert_with(|| error.clone());
+		}
+
+		result.constructor = result.constructor.or_else(|| other.constructor.clone());
+		result.fallback = result.fallback.or(other.fallback);
+		result.receive = result.receive.or(other.receive);
+
+		Ok(result)
+	}
+
+	/// Returns contract with only the functions named in `names`, including all their overloads.
+	///
+	/// Unknown names are ignored. Events, errors, constructor, fallback and receive functions
+	/// are not included.
+	pub fn subset_by_name(&self, names: &[&str]) -> Contract {
+		let functions = self.functions.iter()
+			.filter(|&(name, _)| names.contains(&name.as_str()))
+			.map(|(name, functions)| (name.clone(), functions.clone()))
+			.collect();
+
+		Contract::with_functions(functions)
+	}
+
+	/// Returns contract with only the functions whose selectors are in `selectors`, e.g. to
+	/// decode just the calls a monitor is interested in.
+	///
+	/// Unknown selectors are ignored. Events, errors, constructor, fallback and receive functions
+	/// are not included.
+	pub fn subset_by_selector(&self, selectors: &[[u8; 4]]) -> Contract {
+		let functions = self.functions.iter()
+			.map(|(name, functions)| {
+				let functions: Vec<Function> = functions.iter()
+					.filter(|f| selectors.contains(&f.selector()))
+					.cloned()
+					.collect();
+				(name.clone(), functions)
+			})
+			.filter(|(_, functions)| !functions.is_empty())
+			.collect();
+
+		Contract::with_functions(functions)
+	}
+
+	fn with_functions(functions: HashMap<String, Vec<Function>>) -> Contract {
+		Contract {
+			constructor: None,
+			functions,
+			events: HashMap::default(),
+			errors: HashMap::default(),
+			fallback: None,
+			receive: None,
+		}
+	}
+
+	/// Returns the EIP-165 interface identifier, the xor of all function selectors.
+	///
+	/// `supportsInterface(bytes4)` is excluded, as required by EIP-165.
+	pub fn interface_id(&self) -> [u8; 4] {
+		self.functions()
+			.filter(|f| f.canonical_signature() != "supportsInterface(bytes4)")
+			.fold([0u8; 4], |mut id, f| {
+				for (a, b) in id.iter_mut().zip(&f.selector()) {
+					*a ^= b;
+				}
+				id
+			})
+	}
+
+	/// Compares the functions of `self`, the old version of the contract, with `other`, the new one.
+	///
+	/// A function is changed if any of its overloads was added, removed, or had its outputs changed.
+	/// Signatures are written as `name(types) returns (types)`, overloads are separated by `; `.
+	/// All lists are sorted by function name.
+	pub fn diff(&self, other: &Contract) -> ContractDiff {
+		fn signatures(functions: &[Function]) -> String {
+			let mut signatures: Vec<_> = functions.iter()
+				.map(|f| match f.outputs.len() {
+					0 => f.canonical_signature(),
+					_ => {
+						let outputs: Vec<_> = f.outputs.iter().map(|p| Writer::write(&p.kind)).collect();
+						format!("{} returns ({})", f.canonical_signature(), outputs.join(","))
+					},
+				})
+				.collect();
+			signatures.sort();
+			signatures.join("; ")
+		}
+
+		let mut diff = ContractDiff::default();
+
+		for (name, old) in &self.functions {
+			match other.functions.get(name) {
+				None => diff.removed.push(name.clone()),
+				Some(new) => {
+					let (old, new) = (signatures(old), signatures(new));
+					if old != new {
+						diff.changed.push((name.clone(), old, new));
+					}
+				},
+			}
+		}
+
+		diff.added = other.functions.keys()
+			.filter(|name| !self.functions.contains_key(*name))
+			.cloned()
+			.collect();
+
+		diff.added.sort();
+		diff.removed.sort();
+		diff.changed.sort();
+		diff
+	}
+
+	/// Checks the ABI for violations of the ABI spec, as generated by buggy compilers and tools.
+	///
+	/// Reports tuple params without components, functions with clashing selectors, anonymous
+	/// events with indexed params of dynamic types, and events with more than 4 topics.
+	/// Warnings are sorted by location.
+	pub fn validate_abi(&self) -> errors::Result<Vec<AbiWarning>> {
+		fn has_empty_tuple(kind: &ParamType) -> bool {
+			match *kind {
+				ParamType::Array(ref inner) | ParamType::FixedArray(ref inner, _) => has_empty_tuple(inner),
+				ParamType::Tuple(ref components) => components.is_empty() || components.iter().any(|c| has_empty_tuple(c)),
+				_ => false,
+			}
+		}
+
+		fn check_params<'a, I>(location: &str, params: I, warnings: &mut Vec<AbiWarning>)
+			where I: IntoIterator<Item = (&'a String, &'a ParamType)>
+		{
+			for (name, kind) in params.into_iter().filter(|&(_, kind)| has_empty_tuple(kind)) {
+				warnings.push(AbiWarning {
+					kind: AbiWarningKind::EmptyTuple,
+					location: location.to_owned(),
+					description: format!("Param `{}` of type `{}` has a tuple without components", name, Writer::write(kind)),
+				});
+			}
+		}
+
+		let mut warnings = Vec::new();
+		if let Some(ref constructor) = self.constructor {
+			check_params("constructor", constructor.inputs.iter().map(|p| (&p.name, &p.kind)), &mut warnings);
+		}
+		for function in self.functions() {
+			let params = function.inputs.iter().chain(&function.outputs);
+			check_params(&function.name, params.map(|p| (&p.name, &p.kind)), &mut warnings);
+		}
+		for error in self.errors.values() {
+			check_params(&error.name, error.inputs.iter().map(|p| (&p.name, &p.kind)), &mut warnings);
+		}
+		for event in self.events() {
+			check_params(&event.name, event.inputs.iter().map(|p| (&p.name, &p.kind)), &mut warnings);
+		}
+
+		let mut functions: Vec<_> = self.functions().collect();
+		functions.sort_by_key(|f| f.canonical_signature());
+		let mut selectors: HashMap<[u8; 4], &Function> = HashMap::new();
+		for function in functions {
+			if let Some(existing) = selectors.insert(function.selector(), function) {
+				warnings.push(AbiWarning {
+					kind: AbiWarningKind::SelectorClash,
+					location: function.name.clone(),
+					description: format!(
+						"Function `{}` clashes with `{}`, both have selector {}",
+						function.canonical_signature(), existing.canonical_signature(), function.selector_hex()
+					),
+				});
+			}
+		}
+
+		for event in self.events() {
+			if event.anonymous {
+				for param in event.inputs.iter().filter(|p| p.indexed && p.kind.is_dynamic()) {
+					warnings.push(AbiWarning {
+						kind: AbiWarningKind::UndecodableIndexedParam,
+						location: event.name.clone(),
+						description: format!(
+							"Anonymous event has indexed param `{}` of dynamic type `{}`",
+							param.name, Writer::write(&param.kind)
+						),
+					});
+				}
+			}
+
+			if event.topic_count() > 4 {
+				warnings.push(AbiWarning {
+					kind: AbiWarningKind::TooManyTopics,
+					location: event.name.clone(),
+					description: format!("Event has {} topics, logs have at most 4", event.topic_count()),
+				});
+			}
+		}
+
+		warnings.sort_by(|a, b| (&a.location, &a.description).cmp(&(&b.location, &b.description)));
+		Ok(warnings)
+	}
+
+	/// Returns the data of the transaction deploying the contract, the creation bytecode followed
+	/// by the ABI encoded constructor arguments, validated against the constructor inputs.
+	///
+	/// Contracts without constructor take no arguments.
+	pub fn encode_deploy(&self, bytecode: &[u8], args: &[Token]) -> errors::Result<Bytes> {
+		match self.constructor {
+			Some(ref constructor) => constructor.encode_input(bytecode.to_vec(), args),
+			None if args.is_empty() => Ok(bytecode.to_vec()),
+			None => bail!("Contract has no constructor, expected no arguments, got {}", args.len()),
+		}
+	}
+
+	/// Decodes the constructor arguments of the deploy transaction `data`, the inverse of
+	/// `encode_deploy`. Fails unless `data` starts with the creation `bytecode`.
+	pub fn decode_deploy_args(&self, bytecode: &[u8], data: &[u8]) -> errors::Result<Vec<Token>> {
+		if !data.starts_with(bytecode) {
+			bail!("Deploy data doesn't start with the contract bytecode");
+		}
+
+		let args = &data[bytecode.len()..];
+		match self.constructor {
+			Some(ref constructor) => constructor.decode_input(args),
+			None if args.is_empty() => Ok(vec![]),
+			None => bail!("Contract has no constructor, expected no arguments, got {} bytes", args.len()),
+		}
+	}
+
 	/// Creates constructor call builder.
 	pub fn constructor(&self) -> Option<&Constructor> {
 		self.constructor.as_ref()
 	}
 
-	/// Creates function call builder.
+	/// Creates function call builder for the function named `name`, the first if it is overloaded.
 	pub fn function(&self, name: &str) -> errors::Result<&Function> {
-		self.functions.get(name).ok_or_else(|| ErrorKind::InvalidName(name.to_owned()).into())
+		self.functions.get(name).into_iter()
+							.flatten()
+							.next()
+							.ok_or_else(|| ErrorKind::InvalidName(name.to_owned()).into())
+	}
+
+	/// Get all overloads of the contract function named `name`.
+	pub fn functions_by_name(&self, name: &str) -> errors::Result<&Vec<Function>> {
+		self.functions.get(name)
+					.ok_or_else(|| ErrorKind::InvalidName(name.to_owned()).into())
+	}
+
+	/// Get the contract function with the given 4 byte selector.
+	pub fn function_by_selector(&self, selector: &[u8; 4]) -> Option<&Function> {
+		self.functions().find(|f| f.selector() == *selector)
 	}
 
 	/// Get the contract event named `name`, the first if there are multiple.
@@ -89,6 +489,32 @@ impl Contract {
 							.ok_or_else(|| ErrorKind::InvalidName(name.to_owned()).into())
 	}
 
+	/// Get the non-anonymous contract event with the given topic, see `Event::topic`.
+	pub fn event_by_topic(&self, topic: &[u8; 32]) -> Option<&Event> {
+		let topic = Hash::from(*topic);
+		self.events().find(|e| !e.anonymous && e.topic() == topic)
+	}
+
+	/// Get all non-anonymous contract events with the given topic.
+	///
+	/// Events only differing in which params are indexed share the topic, e.g. the `Transfer`
+	/// events of ERC-20 and ERC-721, they can be told apart by the number of topics of a log.
+	pub fn events_by_topic(&self, topic: &[u8; 32]) -> Vec<&Event> {
+		let topic = Hash::from(*topic);
+		self.events().filter(|e| !e.anonymous && e.topic() == topic).collect()
+	}
+
+	/// Get the contract custom error named `name`.
+	pub fn error(&self, name: &str) -> errors::Result<&CustomError> {
+		self.errors.get(name)
+					.ok_or_else(|| ErrorKind::InvalidName(name.to_owned()).into())
+	}
+
+	/// Get the contract custom error with the given 4 byte selector, e.g. to decode revert data.
+	pub fn error_by_selector(&self, selector: &[u8; 4]) -> Option<&CustomError> {
+		self.errors.values().find(|e| e.selector() == *selector)
+	}
+
 	/// Get all contract events named `name`.
 	pub fn events_by_name(&self, name: &str) -> errors::Result<&Vec<Event>> {
 		self.events.get(name)
@@ -96,23 +522,77 @@ impl Contract {
 	}
 
 	/// Iterate over all functions of the contract in arbitrary order.
-	pub fn functions(&self) -> Functions {
-		Functions(self.functions.values())
+	pub fn functions(&self) -> Functions<'_> {
+		Functions(self.functions.values().flatten())
 	}
 
 	/// Iterate over all events of the contract in arbitrary order.
-	pub fn events(&self) -> Events {
+	pub fn events(&self) -> Events<'_> {
 		Events(self.events.values().flatten())
 	}
 
-	/// Returns true if contract has fallback
-	pub fn fallback(&self) -> bool {
-		self.fallback
+	/// Returns the fallback function, if the contract has one.
+	pub fn fallback(&self) -> Option<&Fallback> {
+		self.fallback.as_ref()
+	}
+
+	/// Returns the receive function, if the contract has one.
+	pub fn receive(&self) -> Option<&Receive> {
+		self.receive.as_ref()
 	}
 }
 
+/// Encodes the call data of the contract function named `function_name` with the given inputs.
+///
+/// Overloaded functions are called with the first overload the inputs match. The inputs are
+/// validated against the function inputs before encoding, see `Function::encode_input`.
+pub fn encode_function_call(contract: &Contract, function_name: &str, tokens: &[Token]) -> errors::Result<Bytes> {
+	let overloads = contract.functions_by_name(function_name)?;
+	match overloads.iter().find_map(|function| function.encode_input(tokens).ok()) {
+		Some(data) => Ok(data),
+		// reports why the inputs don't match
+		None => contract.function(function_name)?.encode_input(tokens),
+	}
+}
+
+/// Decodes the output of the contract function named `function_name` to tokens named after the
+/// outputs, see `Function::decode_output_named`. Overloaded functions use the first overload.
+pub fn decode_function_output(contract: &Contract, function_name: &str, data: &[u8]) -> errors::Result<Vec<(String, Token)>> {
+	let outputs = contract.function(function_name)?.decode_output_named(data)?;
+	Ok(outputs.into_iter().collect())
+}
+
+/// Encodes the topics and data of a log of the contract event named `event_name` with the
+/// given named params, see `Event::encode_log`.
+///
+/// Events with the same name are tried in order, the first one the params match is used.
+pub fn encode_log(contract: &Contract, event_name: &str, params: &[(String, Token)]) -> errors::Result<(Vec<[u8; 32]>, Bytes)> {
+	let events = contract.events_by_name(event_name)?;
+	let (topics, data) = match events.iter().find_map(|event| event.encode_log(params).ok()) {
+		Some(log) => log,
+		// reports why the params don't match
+		None => contract.event(event_name)?.encode_log(params)?,
+	};
+	Ok((topics.into_iter().map(|topic| topic.0).collect(), data))
+}
+
+/// Decodes the topics and data of a log of the contract event named `event_name` to named
+/// params, in the order of the event inputs, see `Event::decode_log`.
+///
+/// Events with the same name are tried in order, the first one the log matches is used.
+pub fn decode_log(contract: &Contract, event_name: &str, topics: &[[u8; 32]], data: &[u8]) -> errors::Result<Vec<(String, Token)>> {
+	let topics: Vec<Hash> = topics.iter().map(|topic| Hash::from(*topic)).collect();
+	let events = contract.events_by_name(event_name)?;
+	let params = match events.iter().find_map(|event| event.decode_log(&topics, data).ok()) {
+		Some(params) => params,
+		// reports why the log doesn't match
+		None => contract.event(event_name)?.decode_log(&topics, data)?,
+	};
+	Ok(params.into_iter().collect())
+}
+
 /// Contract functions interator.
-pub struct Functions<'a>(Values<'a, String, Function>);
+pub struct Functions<'a>(Flatten<Values<'a, String, Vec<Function>>>);
 
 impl<'a> Iterator for Functions<'a> {
 	type Item = &'a Function;
@@ -132,3 +612,590 @@ impl<'a> Iterator for Events<'a> {
 		self.0.next()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+	use std::io::Cursor;
+	use serde_json;
+	use {AbiWarningKind, Contract, ContractDiff, Fallback, Function, StateMutability, Token, encode, encode_function_call, decode_function_output, encode_log, decode_log};
+
+	const ERC20: &str = include_str!("../../res/eip20.abi");
+
+	const OVERLOADED: &str = r#"[
+		{
+			"type": "function",
+			"name": "balanceOf",
+			"inputs": [{ "name": "owner", "type": "address" }],
+			"outputs": [{ "name": "", "type": "uint256" }]
+		},
+		{
+			"type": "function",
+			"name": "balanceOf",
+			"inputs": [{ "name": "owner", "type": "address" }, { "name": "id", "type": "uint256" }],
+			"outputs": [{ "name": "", "type": "uint256" }]
+		},
+		{
+			"type": "function",
+			"name": "totalSupply",
+			"inputs": [],
+			"outputs": [{ "name": "", "type": "uint256" }]
+		}
+	]"#;
+
+	#[test]
+	fn overloaded_functions() {
+		let contract = Contract::load(OVERLOADED.as_bytes()).unwrap();
+
+		let overloads = contract.functions_by_name("balanceOf").unwrap();
+		assert_eq!(overloads.len(), 2);
+		assert_eq!(overloads[0].canonical_signature(), "balanceOf(address)");
+		assert_eq!(overloads[1].canonical_signature(), "balanceOf(address,uint256)");
+		assert_eq!(contract.function("balanceOf").unwrap(), &overloads[0]);
+		assert_eq!(contract.functions().count(), 3);
+		assert!(contract.functions_by_name("transfer").is_err());
+		assert!(contract.function("transfer").is_err());
+	}
+
+	#[test]
+	fn function_by_selector() {
+		let contract = Contract::load(OVERLOADED.as_bytes()).unwrap();
+
+		let balance_of = contract.function_by_selector(&hex!("70a08231")).unwrap();
+		assert_eq!(balance_of.canonical_signature(), "balanceOf(address)");
+		let balance_of = contract.function_by_selector(&hex!("00fdd58e")).unwrap();
+		assert_eq!(balance_of.canonical_signature(), "balanceOf(address,uint256)");
+		assert!(contract.function_by_selector(&hex!("a9059cbb")).is_none());
+	}
+
+	#[test]
+	fn erc20() {
+		let contract = Contract::from_json(ERC20).unwrap();
+
+		assert!(contract.constructor().is_none());
+		assert!(contract.fallback().is_none());
+		assert!(contract.receive().is_none());
+		assert_eq!(contract.functions().count(), 6);
+		assert_eq!(contract.events().count(), 2);
+
+		let transfer = contract.function("transfer").unwrap();
+		assert_eq!(transfer.canonical_signature(), "transfer(address,uint256)");
+		assert_eq!(contract.function_by_selector(&hex!("a9059cbb")), Some(transfer));
+		assert_eq!(contract.function("balanceOf").unwrap().state_mutability, StateMutability::View);
+
+		let transfer = contract.event("Transfer").unwrap();
+		assert_eq!(transfer.inputs.iter().filter(|p| p.indexed).count(), 2);
+		let topic = hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+		assert_eq!(contract.event_by_topic(&topic), Some(transfer));
+		assert!(contract.event_by_topic(&[0u8; 32]).is_none());
+		assert!(contract.event("Mint").is_err());
+	}
+
+	#[test]
+	fn fallback_and_receive() {
+		let contract = Contract::from_json(r#"[
+			{ "type": "fallback", "stateMutability": "nonpayable" },
+			{ "type": "receive", "stateMutability": "payable" }
+		]"#).unwrap();
+
+		assert_eq!(contract.fallback(), Some(&Fallback { state_mutability: StateMutability::NonPayable }));
+		assert!(contract.receive().unwrap().state_mutability.is_payable());
+		assert_eq!(contract.functions().count(), 0);
+	}
+
+	#[test]
+	fn all_entry_types() {
+		let contract = Contract::from_json(r#"[
+			{ "type": "constructor", "inputs": [{ "name": "owner", "type": "address" }], "stateMutability": "nonpayable" },
+			{ "type": "function", "name": "deposit", "inputs": [], "outputs": [], "stateMutability": "payable" },
+			{ "type": "event", "name": "Deposit", "anonymous": false,
+				"inputs": [{ "name": "from", "type": "address", "indexed": true }, { "name": "value", "type": "uint256", "indexed": false }] },
+			{ "type": "error", "name": "Unauthorized", "inputs": [{ "name": "caller", "type": "address" }] },
+			{ "type": "fallback", "stateMutability": "payable" },
+			{ "type": "receive", "stateMutability": "payable" }
+		]"#).unwrap();
+
+		assert_eq!(contract.constructor().unwrap().inputs.len(), 1);
+		assert!(contract.function("deposit").unwrap().state_mutability.is_payable());
+		assert_eq!(contract.event("Deposit").unwrap().inputs.len(), 2);
+		assert!(contract.error("Unauthorized").is_ok());
+		assert_eq!(contract.fallback().unwrap().state_mutability, StateMutability::Payable);
+		assert_eq!(contract.receive().unwrap().state_mutability, StateMutability::Payable);
+		assert_eq!(Contract::from_json(&contract.to_json().unwrap()).unwrap(), contract);
+	}
+
+	#[test]
+	fn legacy_fallback() {
+		// solidity 0.5 marks fallback functions with `payable` instead of `stateMutability`
+		let payable = Contract::from_json(r#"[{ "type": "fallback", "payable": true }]"#).unwrap();
+		assert_eq!(payable.fallback().unwrap().state_mutability, StateMutability::Payable);
+		assert!(payable.receive().is_none());
+
+		let nonpayable = Contract::from_json(r#"[{ "type": "fallback", "payable": false, "constant": false }]"#).unwrap();
+		assert_eq!(nonpayable.fallback().unwrap().state_mutability, StateMutability::NonPayable);
+
+		let json = payable.to_json().unwrap();
+		assert_eq!(json, r#"[{"type":"fallback","stateMutability":"payable"}]"#);
+	}
+
+	#[test]
+	fn serialization_round_trip() {
+		let abi = r#"[
+			{ "type": "constructor", "inputs": [{ "name": "owner", "type": "address" }] },
+			{ "type": "function", "name": "swap", "stateMutability": "payable",
+				"inputs": [{ "name": "path", "type": "(address,uint24)[]" }, { "name": "deadline", "type": "uint256" }],
+				"outputs": [{ "name": "", "type": "uint256[2]" }] },
+			{ "type": "event", "name": "Swap", "anonymous": true,
+				"inputs": [{ "name": "sender", "type": "address", "indexed": true }, { "name": "data", "type": "bytes", "indexed": false }] },
+			{ "type": "fallback", "stateMutability": "payable" },
+			{ "type": "receive", "stateMutability": "payable" }
+		]"#;
+
+		for abi in &[abi, OVERLOADED, ERC20] {
+			let contract = Contract::from_json(abi).unwrap();
+			let json = contract.to_json().unwrap();
+			assert_eq!(Contract::from_json(&json).unwrap(), contract);
+		}
+	}
+
+	#[test]
+	fn json_value() {
+		let value: serde_json::Value = serde_json::from_str(ERC20).unwrap();
+		let contract = Contract::from_json_value(value).unwrap();
+		assert_eq!(contract, Contract::from_json(ERC20).unwrap());
+
+		let value = contract.to_json_value().unwrap();
+		assert_eq!(value, serde_json::from_str::<serde_json::Value>(&contract.to_json().unwrap()).unwrap());
+		assert_eq!(Contract::from_json_value(value).unwrap(), contract);
+
+		assert!(Contract::from_json_value(serde_json::json!({ "abi": [] })).is_err());
+	}
+
+	#[test]
+	fn streaming_large_abi() {
+		let functions: Vec<String> = (0..500).map(|i| format!(
+			r#"{{ "type": "function", "name": "f{}", "inputs": [{{ "name": "a", "type": "uint256" }}], "outputs": [] }}"#,
+			i
+		)).collect();
+		let abi = format!("[{}]", functions.join(","));
+
+		let contract = Contract::from_json_reader(Cursor::new(abi.as_bytes())).unwrap();
+		assert_eq!(contract.functions().count(), 500);
+		assert!((0..500).all(|i| contract.function(&format!("f{}", i)).is_ok()));
+
+		let mut json = vec![];
+		contract.to_json_writer(&mut json).unwrap();
+		assert_eq!(Contract::from_json_reader(Cursor::new(&json[..])).unwrap(), contract);
+	}
+
+	#[test]
+	fn serialization_format() {
+		let contract = Contract::from_json(r#"[
+			{ "type": "function", "name": "balanceOf", "constant": true,
+				"inputs": [{ "name": "owner", "type": "address" }], "outputs": [{ "name": "", "type": "uint256" }] },
+			{ "type": "event", "name": "Transfer",
+				"inputs": [{ "name": "from", "type": "address", "indexed": true }] },
+			{ "type": "fallback" }
+		]"#).unwrap();
+
+		let json: serde_json::Value = serde_json::to_value(&contract).unwrap();
+		let expected: serde_json::Value = serde_json::from_str(r#"[
+			{ "type": "function", "name": "balanceOf", "stateMutability": "view",
+				"inputs": [{ "name": "owner", "type": "address" }], "outputs": [{ "name": "", "type": "uint256" }] },
+			{ "type": "event", "name": "Transfer", "anonymous": false,
+				"inputs": [{ "name": "from", "type": "address", "indexed": true }] },
+			{ "type": "fallback", "stateMutability": "nonpayable" }
+		]"#).unwrap();
+		assert_eq!(json, expected);
+	}
+
+	#[test]
+	fn merge_proxy_and_implementation() {
+		let proxy = Contract::from_json(r#"[
+			{ "type": "function", "name": "upgradeTo",
+				"inputs": [{ "name": "implementation", "type": "address" }], "outputs": [] },
+			{ "type": "event", "name": "Upgraded",
+				"inputs": [{ "name": "implementation", "type": "address", "indexed": true }] },
+			{ "type": "fallback", "stateMutability": "payable" }
+		]"#).unwrap();
+		let erc20 = Contract::from_json(ERC20).unwrap();
+		let overloaded = Contract::from_json(OVERLOADED).unwrap();
+
+		let merged = proxy.merge(&erc20).unwrap();
+		assert_eq!(merged.functions().count(), 7);
+		assert_eq!(merged.events().count(), 3);
+		assert!(merged.function("upgradeTo").is_ok());
+		assert!(merged.function("transfer").is_ok());
+		assert!(merged.fallback().unwrap().state_mutability.is_payable());
+		assert_eq!(merged.merge(&erc20).unwrap(), merged);
+
+		// `balanceOf(address)` is declared differently, with a named output and `constant`,
+		// but it's the same function, and the declaration of `self` is kept
+		let merged = erc20.merge(&overloaded).unwrap();
+		assert_eq!(merged.functions_by_name("balanceOf").unwrap().len(), 2);
+		assert_eq!(merged.function_by_selector(&hex!("70a08231")), erc20.function("balanceOf").ok());
+		let merged = proxy.merge(&overloaded).unwrap();
+		assert_eq!(merged.functions_by_name("balanceOf").unwrap().len(), 2);
+	}
+
+	#[test]
+	fn merge_selector_conflict() {
+		// both functions have selector 0x42966c68
+		let a = Contract::from_json(r#"[{ "type": "function", "name": "burn",
+			"inputs": [{ "name": "amount", "type": "uint256" }], "outputs": [] }]"#).unwrap();
+		let b = Contract::from_json(r#"[{ "type": "function", "name": "collate_propagate_storage",
+			"inputs": [{ "name": "", "type": "bytes16" }], "outputs": [] }]"#).unwrap();
+
+		let err = a.merge(&b).unwrap_err();
+		assert_eq!(format!("{}", err), "Function `collate_propagate_storage(bytes16)` conflicts with `burn(uint256)`, both have selector 0x42966c68");
+
+		// the same function with different param names and outputs is not a conflict
+		let c = Contract::from_json(r#"[{ "type": "function", "name": "burn",
+			"inputs": [{ "name": "", "type": "uint256", "internalType": "uint256" }],
+			"outputs": [{ "name": "", "type": "bool" }] }]"#).unwrap();
+		assert_eq!(a.merge(&c).unwrap(), a);
+	}
+
+	#[test]
+	fn subset() {
+		let contract = Contract::from_json(OVERLOADED).unwrap();
+
+		let subset = contract.subset_by_name(&["balanceOf", "mint"]);
+		assert_eq!(subset.functions().count(), 2);
+		assert!(subset.function("totalSupply").is_err());
+		assert_eq!(subset.functions_by_name("balanceOf").unwrap(), contract.functions_by_name("balanceOf").unwrap());
+		assert_eq!(contract.subset_by_name(&[]).functions().count(), 0);
+
+		// only the `balanceOf(address,uint256)` overload
+		let subset = contract.subset_by_selector(&[hex!("00fdd58e")]);
+		assert_eq!(subset.functions().map(Function::canonical_signature).collect::<Vec<_>>(), vec!["balanceOf(address,uint256)"]);
+	}
+
+	#[test]
+	fn erc20_subset_by_selector() {
+		let contract = Contract::from_json(ERC20).unwrap();
+		let transfer = contract.function("transfer").unwrap().selector();
+		let approve = contract.function("approve").unwrap().selector();
+
+		let subset = contract.subset_by_selector(&[transfer, approve, [0u8; 4]]);
+		let mut signatures: Vec<String> = subset.functions().map(Function::canonical_signature).collect();
+		signatures.sort();
+		assert_eq!(signatures, vec!["approve(address,uint256)", "transfer(address,uint256)"]);
+		assert_eq!(subset.function_by_selector(&hex!("a9059cbb")), contract.function_by_selector(&hex!("a9059cbb")));
+		assert_eq!(subset.events().count(), 0);
+		assert_eq!(subset, contract.subset_by_name(&["transfer", "approve"]));
+		assert_eq!(contract.subset_by_selector(&[]).functions().count(), 0);
+	}
+
+	fn from_signatures(signatures: &[&str]) -> Contract {
+		let mut functions: HashMap<String, Vec<Function>> = HashMap::new();
+		for signature in signatures {
+			let function = Function::from_str_signature(signature).unwrap();
+			functions.entry(function.name.clone()).or_default().push(function);
+		}
+
+		Contract {
+			constructor: None,
+			functions,
+			events: HashMap::new(),
+			errors: HashMap::new(),
+			fallback: None,
+			receive: None,
+		}
+	}
+
+	#[test]
+	fn interface_id() {
+		let erc721 = from_signatures(&[
+			"balanceOf(address) view returns (uint256)",
+			"ownerOf(uint256) view returns (address)",
+			"safeTransferFrom(address,address,uint256,bytes) payable",
+			"safeTransferFrom(address,address,uint256) payable",
+			"transferFrom(address,address,uint256) payable",
+			"approve(address,uint256) payable",
+			"setApprovalForAll(address,bool)",
+			"getApproved(uint256) view returns (address)",
+			"isApprovedForAll(address,address) view returns (bool)",
+			"supportsInterface(bytes4) view returns (bool)",
+		]);
+		assert_eq!(erc721.interface_id(), hex!("80ac58cd"));
+
+		let erc1155 = from_signatures(&[
+			"safeTransferFrom(address,address,uint256,uint256,bytes)",
+			"safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+			"balanceOf(address,uint256) view returns (uint256)",
+			"balanceOfBatch(address[],uint256[]) view returns (uint256[])",
+			"setApprovalForAll(address,bool)",
+			"isApprovedForAll(address,address) view returns (bool)",
+		]);
+		assert_eq!(erc1155.interface_id(), hex!("d9b67a26"));
+
+		assert_eq!(from_signatures(&["supportsInterface(bytes4)"]).interface_id(), [0u8; 4]);
+		assert_eq!(from_signatures(&[]).interface_id(), [0u8; 4]);
+	}
+
+	#[test]
+	fn diff() {
+		let v1 = from_signatures(&[
+			"totalSupply() view returns (uint256)",
+			"balanceOf(address) view returns (uint256)",
+			"transfer(address,uint256) returns (bool)",
+			"approve(address,uint256) returns (bool)",
+			"burn(uint256)",
+		]);
+		let v2 = from_signatures(&[
+			"totalSupply() view returns (uint256)",
+			"balanceOf(address) view returns (uint256)",
+			"transfer(address,uint256) returns (bool)",
+			"approve(address,uint256)",
+			"permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+		]);
+
+		let diff = v1.diff(&v2);
+		assert_eq!(diff, ContractDiff {
+			added: vec!["permit".to_owned()],
+			removed: vec!["burn".to_owned()],
+			changed: vec![(
+				"approve".to_owned(),
+				"approve(address,uint256) returns (bool)".to_owned(),
+				"approve(address,uint256)".to_owned(),
+			)],
+		});
+		assert!(!diff.is_compatible());
+
+		let diff = v1.diff(&v1);
+		assert_eq!(diff, ContractDiff::default());
+		assert!(diff.is_compatible());
+
+		let v3 = from_signatures(&[
+			"totalSupply() view returns (uint256)",
+			"balanceOf(address) view returns (uint256)",
+			"balanceOf(address,uint256) view returns (uint256)",
+			"transfer(address,uint256) returns (bool)",
+			"approve(address,uint256) returns (bool)",
+			"burn(uint256)",
+			"mint(address,uint256)",
+		]);
+		let diff = v1.diff(&v3);
+		assert_eq!(diff.added, vec!["mint".to_owned()]);
+		assert_eq!(diff.changed, vec![(
+			"balanceOf".to_owned(),
+			"balanceOf(address) returns (uint256)".to_owned(),
+			"balanceOf(address) returns (uint256); balanceOf(address,uint256) returns (uint256)".to_owned(),
+		)]);
+	}
+
+	#[test]
+	fn hardhat_internal_types() {
+		let contract = Contract::from_json(r#"[
+			{
+				"inputs": [{ "internalType": "enum Vault.State", "name": "state", "type": "uint8" }],
+				"name": "setState",
+				"outputs": [],
+				"stateMutability": "nonpayable",
+				"type": "function"
+			},
+			{
+				"anonymous": false,
+				"inputs": [{ "indexed": true, "internalType": "contract IERC20", "name": "token", "type": "address" }],
+				"name": "Deposit",
+				"type": "event"
+			}
+		]"#).unwrap();
+
+		let state = &contract.function("setState").unwrap().inputs[0];
+		assert_eq!(state.internal_type, Some("enum Vault.State".to_owned()));
+		let token = &contract.event("Deposit").unwrap().inputs[0];
+		assert_eq!(token.internal_type, Some("contract IERC20".to_owned()));
+
+		let json = contract.to_json().unwrap();
+		assert!(json.contains(r#""internalType":"enum Vault.State""#));
+		assert_eq!(Contract::from_json(&json).unwrap(), contract);
+	}
+
+	#[test]
+	fn custom_errors() {
+		let contract = Contract::from_json(r#"[
+			{ "type": "error", "name": "InsufficientBalance",
+				"inputs": [{ "name": "available", "type": "uint256" }, { "name": "required", "type": "uint256" }] },
+			{ "type": "error", "name": "Unauthorized", "inputs": [] }
+		]"#).unwrap();
+
+		assert_eq!(contract.errors.len(), 2);
+		let error = contract.error_by_selector(&hex!("cf479181")).unwrap();
+		assert_eq!(error, contract.error("InsufficientBalance").unwrap());
+		assert_eq!(contract.error_by_selector(&hex!("82b42900")).unwrap().name, "Unauthorized");
+		assert!(contract.error_by_selector(&hex!("08c379a0")).is_none());
+		assert!(contract.error("Error").is_err());
+
+		let revert = hex!("
+			cf479181
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000002
+		");
+		let error = contract.error_by_selector(&[revert[0], revert[1], revert[2], revert[3]]).unwrap();
+		assert_eq!(error.decode_revert_data(&revert).unwrap(), vec![Token::Uint(1.into()), Token::Uint(2.into())]);
+
+		assert_eq!(Contract::from_json(&contract.to_json().unwrap()).unwrap(), contract);
+	}
+
+	#[test]
+	fn validate_abi() {
+		let contract = Contract::from_json(r#"[
+			{ "type": "function", "name": "transfer",
+				"inputs": [{ "name": "to", "type": "address" }, { "name": "amount", "type": "uint256" }], "outputs": [] },
+			{ "type": "function", "name": "many_msg_babbage", "inputs": [{ "name": "", "type": "bytes1" }], "outputs": [] },
+			{ "type": "function", "name": "configure",
+				"inputs": [{ "name": "config", "type": "tuple[]", "components": [] }],
+				"outputs": [{ "name": "", "type": "tuple", "components": [{ "name": "inner", "type": "tuple" }] }] },
+			{ "type": "event", "name": "Message", "anonymous": true,
+				"inputs": [{ "name": "from", "type": "address", "indexed": true }, { "name": "text", "type": "string", "indexed": true }] },
+			{ "type": "event", "name": "Wide", "anonymous": false, "inputs": [
+				{ "name": "a", "type": "uint256", "indexed": true },
+				{ "name": "b", "type": "uint256", "indexed": true },
+				{ "name": "c", "type": "uint256", "indexed": true },
+				{ "name": "d", "type": "uint256", "indexed": true }
+			] }
+		]"#).unwrap();
+
+		let warnings = contract.validate_abi().unwrap();
+		let kinds: Vec<_> = warnings.iter().map(|w| (w.kind, w.location.as_str())).collect();
+		assert_eq!(kinds, vec![
+			(AbiWarningKind::UndecodableIndexedParam, "Message"),
+			(AbiWarningKind::TooManyTopics, "Wide"),
+			(AbiWarningKind::EmptyTuple, "configure"),
+			(AbiWarningKind::EmptyTuple, "configure"),
+			(AbiWarningKind::SelectorClash, "transfer"),
+		]);
+		assert_eq!(warnings[0].description, "Anonymous event has indexed param `text` of dynamic type `string`");
+		assert_eq!(warnings[1].description, "Event has 5 topics, logs have at most 4");
+		assert_eq!(warnings[2].description, "Param `` of type `(())` has a tuple without components");
+		assert_eq!(warnings[3].description, "Param `config` of type `()[]` has a tuple without components");
+		assert_eq!(
+			warnings[4].description,
+			"Function `transfer(address,uint256)` clashes with `many_msg_babbage(bytes1)`, both have selector 0xa9059cbb"
+		);
+
+		assert_eq!(Contract::from_json(ERC20).unwrap().validate_abi().unwrap(), vec![]);
+	}
+
+	#[test]
+	fn erc20_transfer_end_to_end() {
+		let contract = Contract::from_json(ERC20).unwrap();
+		let to = Token::Address(hex!("4bbeeb066ed09b7aed07bf39eee0460dfa261520").into());
+		let amount = Token::Uint(1_000_000.into());
+
+		let data = encode_function_call(&contract, "transfer", &[to.clone(), amount.clone()]).unwrap();
+		assert_eq!(data, hex!("
+			a9059cbb
+			0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520
+			00000000000000000000000000000000000000000000000000000000000f4240
+		").to_vec());
+		assert_eq!(contract.function("transfer").unwrap().decode_input(&data).unwrap(), vec![to.clone(), amount.clone()]);
+
+		let output = decode_function_output(&contract, "transfer", &encode(&[Token::Bool(true)])).unwrap();
+		assert_eq!(output, vec![("success".to_owned(), Token::Bool(true))]);
+
+		assert!(encode_function_call(&contract, "transfer", &[amount.clone(), to.clone()]).is_err());
+		assert!(encode_function_call(&contract, "transfer", &[to.clone()]).is_err());
+		assert!(encode_function_call(&contract, "mint", &[to, amount]).is_err());
+		assert!(decode_function_output(&contract, "transfer", &[]).is_err());
+	}
+
+	#[test]
+	fn encode_overloaded_function_call() {
+		let contract = Contract::load(OVERLOADED.as_bytes()).unwrap();
+		let owner = Token::Address([0x11u8; 20].into());
+
+		let data = encode_function_call(&contract, "balanceOf", &[owner.clone()]).unwrap();
+		assert_eq!(data[..4], hex!("70a08231"));
+		let data = encode_function_call(&contract, "balanceOf", &[owner.clone(), Token::Uint(7.into())]).unwrap();
+		assert_eq!(data[..4], hex!("00fdd58e"));
+
+		let err = encode_function_call(&contract, "balanceOf", &[Token::Bool(true)]).unwrap_err();
+		assert_eq!(format!("{}", err), "token at param 0: expected address, got bool");
+	}
+
+	#[test]
+	fn erc20_transfer_log() {
+		let contract = Contract::from_json(ERC20).unwrap();
+		let from = Token::Address([0x11u8; 20].into());
+		let to = Token::Address([0x22u8; 20].into());
+		let value = Token::Uint(1_000_000.into());
+		let params = vec![
+			("value".to_owned(), value.clone()),
+			("from".to_owned(), from.clone()),
+			("to".to_owned(), to.clone()),
+		];
+
+		let (topics, data) = encode_log(&contract, "Transfer", &params).unwrap();
+		assert_eq!(topics, vec![
+			hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+			hex!("0000000000000000000000001111111111111111111111111111111111111111"),
+			hex!("0000000000000000000000002222222222222222222222222222222222222222"),
+		]);
+		assert_eq!(data, encode(&[value.clone()]));
+
+		let decoded = decode_log(&contract, "Transfer", &topics, &data).unwrap();
+		assert_eq!(decoded, vec![
+			("from".to_owned(), from.clone()),
+			("to".to_owned(), to.clone()),
+			("value".to_owned(), value.clone()),
+		]);
+
+		let missing = encode_log(&contract, "Transfer", &params[..2]).unwrap_err();
+		assert_eq!(format!("{}", missing), "Missing param `to` of event `Transfer`");
+		let wrong_type = vec![("value".to_owned(), Token::Bool(true)), ("from".to_owned(), from), ("to".to_owned(), to)];
+		assert!(encode_log(&contract, "Transfer", &wrong_type).is_err());
+		assert!(encode_log(&contract, "Transferred", &params).is_err());
+		assert!(decode_log(&contract, "Transfer", &topics[..2], &data).is_err());
+		assert!(decode_log(&contract, "Approval", &topics, &data).is_err());
+	}
+
+	#[test]
+	fn encode_and_decode_deploy() {
+		let contract = Contract::from_json(r#"[{
+			"type": "constructor",
+			"inputs": [
+				{ "name": "owner", "type": "address" },
+				{ "name": "pools", "type": "tuple[]", "components": [
+					{ "name": "token", "type": "address" },
+					{ "name": "fee", "type": "uint24" },
+					{ "name": "path", "type": "bytes" }
+				] },
+				{ "name": "limits", "type": "tuple", "components": [
+					{ "name": "min", "type": "uint256" },
+					{ "name": "max", "type": "uint256" }
+				] }
+			]
+		}]"#).unwrap();
+		let bytecode = hex!("6080604052348015600f57600080fd5b50").to_vec();
+		let args = vec![
+			Token::Address([0x11u8; 20].into()),
+			Token::Array(vec![
+				Token::Tuple(vec![Token::Address([0x22u8; 20].into()), Token::Uint(3000.into()), Token::Bytes(vec![1, 2, 3])]),
+				Token::Tuple(vec![Token::Address([0x33u8; 20].into()), Token::Uint(500.into()), Token::Bytes(vec![])]),
+			]),
+			Token::Tuple(vec![Token::Uint(1.into()), Token::Uint(100.into())]),
+		];
+
+		let data = contract.encode_deploy(&bytecode, &args).unwrap();
+		assert_eq!(data[..bytecode.len()], bytecode[..]);
+		assert_eq!(data[bytecode.len()..], encode(&args)[..]);
+		assert_eq!(contract.decode_deploy_args(&bytecode, &data).unwrap(), args);
+
+		assert!(contract.encode_deploy(&bytecode, &args[..2]).is_err());
+		let mut wrong_fee = args.clone();
+		wrong_fee[1] = Token::Array(vec![Token::Tuple(vec![Token::Address([0x22u8; 20].into()), Token::Uint((1u64 << 24).into()), Token::Bytes(vec![])])]);
+		assert!(contract.encode_deploy(&bytecode, &wrong_fee).is_err());
+		assert!(contract.decode_deploy_args(&bytecode[1..], &data).is_err());
+		assert!(contract.decode_deploy_args(&bytecode, &bytecode).is_err());
+
+		let without_constructor = Contract::from_json(ERC20).unwrap();
+		assert_eq!(without_constructor.encode_deploy(&bytecode, &[]).unwrap(), bytecode);
+		assert_eq!(without_constructor.decode_deploy_args(&bytecode, &bytecode).unwrap(), vec![]);
+		let err = without_constructor.encode_deploy(&bytecode, &args).unwrap_err();
+		assert_eq!(format!("{}", err), "Contract has no constructor, expected no arguments, got 3");
+		assert!(without_constructor.decode_deploy_args(&bytecode, &data).is_err());
+	}
+}
@@ -0,0 +1,148 @@
+//! Registry of contract ABIs by address, to decode logs emitted by many contracts.
+
+use std::collections::HashMap;
+use hex::ToHex;
+use {Address, Contract, DecodedParams, Event, Hash, Result};
+
+/// Maps contract addresses to their ABIs, e.g. to decode the logs of a block.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContractRegistry {
+	contracts: HashMap<Address, Contract>,
+}
+
+impl ContractRegistry {
+	/// Creates an empty registry.
+	pub fn new() -> Self {
+		ContractRegistry::default()
+	}
+
+	/// Registers the ABI of the contract at `address`, returning the ABI previously registered
+	/// at the address, if any.
+	pub fn register(&mut self, address: [u8; 20], contract: Contract) -> Option<Contract> {
+		self.contracts.insert(address.into(), contract)
+	}
+
+	/// Returns the ABI registered at `address`.
+	pub fn contract(&self, address: &[u8; 20]) -> Option<&Contract> {
+		self.contracts.get(&Address::from(*address))
+	}
+
+	/// Returns the events of the contract at `address` with the given topic,
+	/// see `Contract::events_by_topic`.
+	pub fn events(&self, address: &[u8; 20], topic: &[u8; 32]) -> Vec<&Event> {
+		self.contract(address).map(|contract| contract.events_by_topic(topic)).unwrap_or_default()
+	}
+
+	/// Decodes a log emitted by the contract at `address`, returning the event it belongs to
+	/// along with its params.
+	///
+	/// The event is looked up by the first topic. Of events sharing the topic, the first one
+	/// decoding the log is returned. Fails if no contract is registered at `address`, or none
+	/// of its events decodes the log.
+	pub fn decode_log(&self, address: &[u8; 20], topics: &[[u8; 32]], data: &[u8]) -> Result<(&Event, DecodedParams)> {
+		let contract = match self.contract(address) {
+			Some(contract) => contract,
+			None => bail!("No contract registered at 0x{}", address.to_hex::<String>()),
+		};
+		let topic = match topics.first() {
+			Some(topic) => topic,
+			None => bail!("Log of 0x{} has no topics", address.to_hex::<String>()),
+		};
+
+		let hashes: Vec<Hash> = topics.iter().map(|&topic| topic.into()).collect();
+		let mut error = None;
+		for event in contract.events_by_topic(topic) {
+			match event.decode_log(&hashes, data) {
+				Ok(params) => return Ok((event, params)),
+				Err(e) => error = error.or(Some(e)),
+			}
+		}
+
+		match error {
+			Some(error) => Err(error),
+			None => bail!("Contract at 0x{} has no event with topic 0x{}", address.to_hex::<String>(), topic.to_hex::<String>()),
+		}
+	}
+
+	/// Returns the number of registered contracts.
+	pub fn len(&self) -> usize {
+		self.contracts.len()
+	}
+
+	/// Returns true if no contract is registered.
+	pub fn is_empty(&self) -> bool {
+		self.contracts.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ContractRegistry;
+	use {Contract, Token};
+
+	const ERC20: &str = include_str!("../../res/eip20.abi");
+	const ERC721_TRANSFER: &str = r#"[{
+		"type": "event",
+		"name": "Transfer",
+		"anonymous": false,
+		"inputs": [
+			{ "name": "from", "type": "address", "indexed": true },
+			{ "name": "to", "type": "address", "indexed": true },
+			{ "name": "tokenId", "type": "uint256", "indexed": true }
+		]
+	}]"#;
+
+	const USDC: [u8; 20] = hex!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+	const BAYC: [u8; 20] = hex!("bc4ca0eda7647a8ab7c2061c2e118a18a936f13d");
+	const TRANSFER: [u8; 32] = hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+	fn word(byte: u8) -> [u8; 32] {
+		let mut word = [0u8; 32];
+		word[31] = byte;
+		word
+	}
+
+	#[test]
+	fn decode_logs_of_many_contracts() {
+		let mut registry = ContractRegistry::new();
+		assert!(registry.register(USDC, Contract::from_json(ERC20).unwrap()).is_none());
+		assert!(registry.register(BAYC, Contract::from_json(ERC721_TRANSFER).unwrap()).is_none());
+		assert_eq!(registry.len(), 2);
+
+		let (event, params) = registry.decode_log(&USDC, &[TRANSFER, word(1), word(2)], &word(100)).unwrap();
+		assert_eq!(event.name, "Transfer");
+		assert_eq!(params.get("value"), Some(&Token::from(100u64)));
+
+		let (event, params) = registry.decode_log(&BAYC, &[TRANSFER, word(1), word(2), word(7)], &[]).unwrap();
+		assert_eq!(event.inputs[2].name, "tokenId");
+		assert_eq!(params.get("tokenId"), Some(&Token::from(7u64)));
+
+		assert_eq!(registry.events(&USDC, &TRANSFER).len(), 1);
+		assert!(registry.events(&[0u8; 20], &TRANSFER).is_empty());
+		assert!(registry.decode_log(&USDC, &[TRANSFER, word(1), word(2), word(7)], &[]).is_err());
+		assert!(registry.decode_log(&USDC, &[[0u8; 32]], &[]).is_err());
+		assert!(registry.decode_log(&USDC, &[], &[]).is_err());
+		assert_eq!(
+			format!("{}", registry.decode_log(&[0u8; 20], &[TRANSFER], &[]).unwrap_err()),
+			"No contract registered at 0x0000000000000000000000000000000000000000"
+		);
+	}
+
+	#[test]
+	fn events_sharing_a_topic() {
+		let mut contract = Contract::from_json(ERC20).unwrap();
+		let erc721 = Contract::from_json(ERC721_TRANSFER).unwrap();
+		contract.events.get_mut("Transfer").unwrap().extend(erc721.events["Transfer"].clone());
+		assert_eq!(contract.events_by_topic(&TRANSFER).len(), 2);
+
+		let mut registry = ContractRegistry::new();
+		registry.register(USDC, contract.clone());
+		let (event, _) = registry.decode_log(&USDC, &[TRANSFER, word(1), word(2)], &word(100)).unwrap();
+		assert_eq!(event.inputs[2].name, "value");
+		let (event, _) = registry.decode_log(&USDC, &[TRANSFER, word(1), word(2), word(7)], &[]).unwrap();
+		assert_eq!(event.inputs[2].name, "tokenId");
+
+		assert_eq!(registry.register(USDC, Contract::from_json(ERC20).unwrap()), Some(contract));
+		assert_eq!(registry.len(), 1);
+	}
+}
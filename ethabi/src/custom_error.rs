@@ -0,0 +1,99 @@
+//! Contract custom error.
+
+use signature::short_signature;
+use param_type::Writer;
+use human_readable;
+use {Param, ParamType, Token, Result, Bytes, encode_with_types, decode_function_input};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Contract custom error specification, e.g. `error InsufficientBalance(uint256 available)`.
+///
+/// Reverting with the error returns its selector followed by the ABI encoded arguments,
+/// just like a function call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomError {
+	/// Error name.
+	pub name: String,
+	/// Error input.
+	pub inputs: Vec<Param>,
+}
+
+impl CustomError {
+	/// Returns all input params of given error.
+	fn param_types(&self) -> Vec<ParamType> {
+		self.inputs.iter()
+			.map(|p| p.kind.clone())
+			.collect()
+	}
+
+	/// Parses error from human readable signature, e.g.
+	/// `InsufficientBalance(uint256 available, uint256 required)`.
+	pub fn from_str_signature(signature: &str) -> Result<CustomError> {
+		human_readable::parse_error(signature)
+	}
+
+	/// Returns the canonical signature of the error, e.g. `InsufficientBalance(uint256,uint256)`.
+	pub fn canonical_signature(&self) -> String {
+		let types = self.inputs.iter()
+			.map(|p| Writer::write(&p.kind))
+			.collect::<Vec<String>>()
+			.join(",");
+
+		format!("{}({})", self.name, types)
+	}
+
+	/// Returns the 4 byte selector of the error, the first bytes of the signature hash.
+	pub fn selector(&self) -> [u8; 4] {
+		short_signature(&self.name, &self.param_types())
+	}
+
+	/// Prepares the revert data of the error with given input params.
+	pub fn encode_revert_data(&self, tokens: &[Token]) -> Result<Bytes> {
+		let encoded = encode_with_types(&self.param_types(), tokens)?;
+		Ok(self.selector().iter().cloned().chain(encoded).collect())
+	}
+
+	/// Parses the revert data, including the selector, to list of tokens.
+	pub fn decode_revert_data(&self, data: &[u8]) -> Result<Vec<Token>> {
+		decode_function_input(self.selector(), data, &self.param_types())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {CustomError, Token};
+
+	#[test]
+	fn test_error_string() {
+		let error = CustomError::from_str_signature("Error(string)").unwrap();
+		assert_eq!(error.selector(), hex!("08c379a0"));
+
+		let data = hex!("
+			08c379a0
+			0000000000000000000000000000000000000000000000000000000000000020
+			000000000000000000000000000000000000000000000000000000000000001a
+			4e6f7420656e6f7567682045746865722070726f76696465642e000000000000
+		");
+		let decoded = error.decode_revert_data(&data).unwrap();
+		assert_eq!(decoded, vec![Token::String("Not enough Ether provided.".to_owned())]);
+		assert_eq!(error.encode_revert_data(&decoded).unwrap(), data.to_vec());
+	}
+
+	#[test]
+	fn test_custom_error() {
+		let error = CustomError::from_str_signature("error InsufficientBalance(uint256 available, uint256 required)").unwrap();
+		assert_eq!(error.canonical_signature(), "InsufficientBalance(uint256,uint256)");
+		assert_eq!(error.selector(), hex!("cf479181"));
+
+		let tokens = vec![Token::Uint(1.into()), Token::Uint(2.into())];
+		let data = error.encode_revert_data(&tokens).unwrap();
+		assert_eq!(&data[..4], &hex!("cf479181"));
+		assert_eq!(error.decode_revert_data(&data).unwrap(), tokens);
+
+		assert!(error.decode_revert_data(&data[..35]).is_err());
+		assert!(error.encode_revert_data(&[Token::Bool(true)]).is_err());
+		let other = CustomError::from_str_signature("Unauthorized()").unwrap();
+		assert!(other.decode_revert_data(&data).is_err());
+	}
+}
@@ -0,0 +1,89 @@
+//! Decoded params along with their names.
+
+use core::iter::FromIterator;
+use core::slice;
+use {Log, Token};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Decoded function outputs or event params, in the order of their declaration.
+///
+/// Params can be accessed by name, or by position when they are unnamed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecodedParams(Vec<(String, Token)>);
+
+impl DecodedParams {
+	/// Returns the value of the first param named `name`.
+	pub fn get(&self, name: &str) -> Option<&Token> {
+		self.0.iter().find(|(n, _)| n == name).map(|(_, token)| token)
+	}
+
+	/// Returns the value of the param at `index`.
+	pub fn get_index(&self, index: usize) -> Option<&Token> {
+		self.0.get(index).map(|(_, token)| token)
+	}
+
+	/// Returns the nested token at `path`, the name of a param followed by dot separated
+	/// indices into its tuples and arrays, e.g. `order.0.1`. See `Token::path_get`.
+	pub fn path_get(&self, path: &str) -> Option<&Token> {
+		let mut segments = path.splitn(2, '.');
+		let token = self.get(segments.next()?)?;
+		token.path_get(segments.next().unwrap_or_default())
+	}
+
+	/// Returns the number of params.
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Returns true if there are no params.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Returns an iterator over names and values of the params.
+	pub fn iter(&self) -> slice::Iter<'_, (String, Token)> {
+		self.0.iter()
+	}
+
+	/// Returns the values of the params, dropping their names.
+	pub fn into_tokens(self) -> Vec<Token> {
+		self.0.into_iter().map(|(_, token)| token).collect()
+	}
+}
+
+impl From<Vec<(String, Token)>> for DecodedParams {
+	fn from(params: Vec<(String, Token)>) -> Self {
+		DecodedParams(params)
+	}
+}
+
+impl FromIterator<(String, Token)> for DecodedParams {
+	fn from_iter<I: IntoIterator<Item = (String, Token)>>(iter: I) -> Self {
+		DecodedParams(iter.into_iter().collect())
+	}
+}
+
+impl From<Log> for DecodedParams {
+	fn from(log: Log) -> Self {
+		DecodedParams(log.params.into_iter().map(|param| (param.name, param.value)).collect())
+	}
+}
+
+impl IntoIterator for DecodedParams {
+	type Item = (String, Token);
+	type IntoIter = ::alloc::vec::IntoIter<(String, Token)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl<'a> IntoIterator for &'a DecodedParams {
+	type Item = &'a (String, Token);
+	type IntoIter = slice::Iter<'a, (String, Token)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.iter()
+	}
+}
@@ -1,21 +1,32 @@
 //! ABI decoder.
 
-use util::slice_data;
-use {Token, ErrorKind, Error, ResultExt, ParamType};
-
-struct DecodeResult {
-	token: Token,
+use core::{cmp, str};
+use core::convert::TryFrom;
+use hex::{ToHex, FromHex};
+use token::BorrowedToken;
+use encoder::encoded_size_with_types;
+use {Token, ErrorKind, Error, Param, ResultExt, ParamType, Uint};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+struct DecodeResult<'a> {
+	token: BorrowedToken<'a>,
 	new_offset: usize,
 }
 
-struct BytesTaken {
-	bytes: Vec<u8>,
+struct BytesTaken<'a> {
+	bytes: &'a [u8],
 	new_offset: usize,
 }
 
-fn as_u32(slice: &[u8; 32]) -> Result<u32, Error> {
+/// Error for invalid data found at the given word of the data.
+fn invalid_data(position: usize, reason: &'static str) -> Error {
+	ErrorKind::InvalidDataAt(position * 32, reason).into()
+}
+
+fn as_u32(slice: &[u8; 32], position: usize) -> Result<u32, Error> {
 	if !slice[..28].iter().all(|x| *x == 0) {
-		return Err(ErrorKind::InvalidData.into());
+		return Err(invalid_data(position, "value does not fit in 32 bits"));
 	}
 
 	let result = ((slice[28] as u32) << 24) +
@@ -26,190 +37,377 @@ fn as_u32(slice: &[u8; 32]) -> Result<u32, Error> {
 	Ok(result)
 }
 
-fn as_bool(slice: &[u8; 32]) -> Result<bool, Error> {
+fn as_bool(slice: &[u8; 32], position: usize) -> Result<bool, Error> {
 	if !slice[..31].iter().all(|x| *x == 0) {
-		return Err(ErrorKind::InvalidData.into());
+		return Err(invalid_data(position, "invalid boolean"));
 	}
 
 	Ok(slice[31] == 1)
 }
 
+/// Reads an integer of `size` bits, which has to be zero extended when unsigned and sign
+/// extended when signed.
+fn as_int(slice: &[u8; 32], size: usize, signed: bool, position: usize) -> Result<Uint, Error> {
+	let value = Uint::from(slice);
+	let fits = if signed {
+		let magnitude = if value.bit(255) { !value } else { value };
+		magnitude.bits() < size
+	} else {
+		value.bits() <= size
+	};
+
+	if !fits {
+		return Err(invalid_data(position, "integer does not fit in its type"));
+	}
+
+	Ok(value)
+}
+
+/// Limits applied while decoding untrusted data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+	/// Maximum nesting depth of arrays and tuples, deeper params fail with `MaxDepthExceeded`.
+	pub max_depth: usize,
+	/// Maximum number of elements of dynamic arrays, longer arrays fail with `ArrayLengthLimitExceeded`.
+	pub max_array_len: usize,
+}
+
+impl Default for DecodeOptions {
+	fn default() -> Self {
+		DecodeOptions {
+			max_depth: 64,
+			max_array_len: 100_000,
+		}
+	}
+}
+
 /// Decodes ABI compliant vector of bytes into vector of tokens described by types param.
 pub fn decode(types: &[ParamType], data: &[u8]) -> Result<Vec<Token>, Error> {
+	decode_with_options(types, data, &DecodeOptions::default())
+}
+
+/// Decodes ABI compliant vector of bytes like `decode`, enforcing the given limits.
+pub fn decode_with_options(types: &[ParamType], data: &[u8], options: &DecodeOptions) -> Result<Vec<Token>, Error> {
+	let tokens = try!(decode_borrowed_with_options(types, data, options));
+	Ok(tokens.into_iter().map(BorrowedToken::into_owned).collect())
+}
+
+/// Decodes ABI compliant vector of bytes like `decode`, without copying bytes and strings.
+///
+/// Bytes, fixed bytes and strings of the returned tokens borrow from `data`.
+pub fn decode_borrowed<'a>(types: &[ParamType], data: &'a [u8]) -> Result<Vec<BorrowedToken<'a>>, Error> {
+	decode_borrowed_with_options(types, data, &DecodeOptions::default())
+}
+
+/// Decodes ABI compliant vector of bytes like `decode_borrowed`, enforcing the given limits.
+pub fn decode_borrowed_with_options<'a>(
+	types: &[ParamType],
+	data: &'a [u8],
+	options: &DecodeOptions,
+) -> Result<Vec<BorrowedToken<'a>>, Error> {
     let is_empty_bytes_valid_encoding = types.iter().all(|t| t.is_empty_bytes_valid_encoding());
     if !is_empty_bytes_valid_encoding && data.is_empty() {
         bail!("please ensure the contract and method you're calling exist! failed to decode empty bytes. if you're using jsonrpc this is likely due to jsonrpc returning `0x` in case contract or method don't exist");
     }
-	let slices = slice_data(data)?;
+	if !data.len().is_multiple_of(32) {
+		return Err(ErrorKind::InvalidDataAt(data.len() / 32 * 32, "data length is not a multiple of 32").into());
+	}
 	let mut tokens = vec![];
 	let mut offset = 0;
 	for param in types {
-		let res = decode_param(param, &slices, offset).chain_err(|| format!("Cannot decode {}", param))?;
+		let res = decode_param(param, data, 0, offset, options, 0).chain_err(|| format!("Cannot decode {}", param))?;
 		offset = res.new_offset;
 		tokens.push(res.token);
 	}
 	Ok(tokens)
 }
 
-fn peek(slices: &[[u8; 32]], position: usize) -> Result<&[u8; 32], Error> {
-	slices.get(position).ok_or_else(|| ErrorKind::InvalidData.into())
+/// Decodes ABI compliant vector of bytes like `decode`, pairing the tokens with the names of
+/// the params. Unnamed params are named after their index, e.g. `"0"`.
+pub fn decode_with_names(params: &[Param], data: &[u8]) -> Result<Vec<(String, Token)>, Error> {
+	let types: Vec<ParamType> = params.iter().map(|p| p.kind.clone()).collect();
+	let tokens = decode(&types, data)?;
+	let names = params.iter().enumerate().map(|(index, p)| match p.name.as_str() {
+		"" => index.to_string(),
+		name => name.to_owned(),
+	});
+	Ok(names.zip(tokens).collect())
 }
 
-fn take_bytes(slices: &[[u8; 32]], position: usize, len: usize) -> Result<BytesTaken, Error> {
-	let slices_len = (len + 31) / 32;
+/// Decodes the data of each call with its types independently, like `decode`, e.g. the results
+/// of a batch of `eth_call` requests. Results are in the order of the calls.
+///
+/// With the `parallel` feature, calls are decoded in parallel on the rayon thread pool.
+pub fn decode_many(calls: &[(Vec<ParamType>, Vec<u8>)]) -> Vec<Result<Vec<Token>, Error>> {
+	#[cfg(feature = "parallel")]
+	{
+		use rayon::prelude::*;
+		calls.par_iter().map(|(types, data)| decode(types, data)).collect()
+	}
 
-	let mut bytes_slices = vec![];
-	for i in 0..slices_len {
-		let slice = try!(peek(slices, position + i));
-		bytes_slices.push(slice);
+	#[cfg(not(feature = "parallel"))]
+	{
+		calls.iter().map(|(types, data)| decode(types, data)).collect()
 	}
+}
 
-	let bytes = bytes_slices.into_iter()
-		.flat_map(|slice| slice.to_vec())
-		.take(len)
-		.collect();
+/// Decodes hex encoded ABI data, as returned by JSON-RPC. The `0x` prefix is optional.
+pub fn decode_from_hex(types: &[ParamType], data: &str) -> Result<Vec<Token>, Error> {
+	let data = data.strip_prefix("0x").unwrap_or(data);
+	let bytes: Vec<u8> = data.from_hex()?;
+	decode(types, &bytes)
+}
+
+/// Decodes ABI compliant vector of bytes like `decode`, but fails if the data contains
+/// anything beyond the encoding of the decoded tokens.
+///
+/// `decode` ignores trailing words, which hides decoding with the wrong types or corrupted
+/// data, e.g. when validating raw transaction input.
+pub fn decode_strict(types: &[ParamType], data: &[u8]) -> Result<Vec<Token>, Error> {
+	let tokens = decode(types, data)?;
+	let decoded_len = encoded_size_with_types(types, &tokens);
+	if decoded_len != data.len() {
+		bail!("Unexpected trailing data, decoded {} of {} bytes", decoded_len, data.len());
+	}
+	Ok(tokens)
+}
+
+/// Decodes function call data, checking that it starts with the given 4 byte selector.
+pub fn decode_function_input(selector: [u8; 4], data: &[u8], types: &[ParamType]) -> Result<Vec<Token>, Error> {
+	if data.len() < 4 || data[..4] != selector {
+		bail!("Expected data to start with function selector 0x{}", selector.to_hex::<String>());
+	}
+	decode(types, &data[4..])
+}
+
+/// Returns the word at `position` of `data`, which starts at word `base` of the decoded data.
+fn peek(data: &[u8], base: usize, position: usize) -> Result<&[u8; 32], Error> {
+	data.get(position * 32..(position + 1) * 32)
+		.map(|word| <&[u8; 32]>::try_from(word).expect("slice has 32 bytes; qed"))
+		.ok_or_else(|| invalid_data(base + position, "unexpected end of data"))
+}
+
+/// Takes `len` bytes starting at word `position` of `data`, which have to be padded to a
+/// multiple of 32 bytes.
+fn take_bytes<'a>(data: &'a [u8], base: usize, position: usize, len: usize) -> Result<BytesTaken<'a>, Error> {
+	let slices_len = (len + 31) / 32;
+	let start = position * 32;
+	let available = data.len() / 32;
+	if position + slices_len > available {
+		return Err(invalid_data(base + cmp::max(position, available), "unexpected end of data"));
+	}
 
 	let taken = BytesTaken {
-		bytes,
+		bytes: &data[start..start + len],
 		new_offset: position + slices_len,
 	};
 
 	Ok(taken)
 }
 
-fn decode_param(param: &ParamType, slices: &[[u8; 32]], offset: usize) -> Result<DecodeResult, Error> {
+/// Follows the offset at word `offset` of `data` to the tail encoding of a dynamic tuple or
+/// fixed array. Returns the data starting there along with the position of its first word.
+fn tail(data: &[u8], base: usize, offset: usize) -> Result<(&[u8], usize), Error> {
+	let offset_slice = try!(peek(data, base, offset));
+	let tail_offset = (try!(as_u32(offset_slice, base + offset)) / 32) as usize;
+	let tail_data = try!(data.get(tail_offset * 32..)
+		.ok_or_else(|| invalid_data(base + offset, "tuple offset out of bounds")));
+	Ok((tail_data, base + tail_offset))
+}
+
+/// Decodes param at word `offset` of `data`. Offsets found in the data are relative to the
+/// beginning of `data`, which starts at word `base` of the decoded data. `depth` is the number
+/// of arrays and tuples the param is nested in.
+fn decode_param<'a>(
+	param: &ParamType,
+	data: &'a [u8],
+	base: usize,
+	offset: usize,
+	options: &DecodeOptions,
+	depth: usize,
+) -> Result<DecodeResult<'a>, Error> {
+	if depth > options.max_depth {
+		return Err(ErrorKind::MaxDepthExceeded(depth).into());
+	}
+
 	match *param {
 		ParamType::Address => {
-			let slice = try!(peek(slices, offset));
+			let slice = try!(peek(data, base, offset));
 			let mut address = [0u8; 20];
 			address.copy_from_slice(&slice[12..]);
 
 			let result = DecodeResult {
-				token: Token::Address(address.into()),
+				token: BorrowedToken::Address(address.into()),
 				new_offset: offset + 1,
 			};
 
 			Ok(result)
 		},
-		ParamType::Int(_) => {
-			let slice = try!(peek(slices, offset));
+		ParamType::Int(size) => {
+			let slice = try!(peek(data, base, offset));
 
 			let result = DecodeResult {
-				token: Token::Int(slice.clone().into()),
+				token: BorrowedToken::Int(as_int(slice, size, true, base + offset)?),
 				new_offset: offset + 1,
 			};
 
 			Ok(result)
 		},
-		ParamType::Uint(_) => {
-			let slice = try!(peek(slices, offset));
+		ParamType::Uint(size) => {
+			let slice = try!(peek(data, base, offset));
 
 			let result = DecodeResult {
-				token: Token::Uint(slice.clone().into()),
+				token: BorrowedToken::Uint(as_int(slice, size, false, base + offset)?),
 				new_offset: offset + 1,
 			};
 
 			Ok(result)
 		},
 		ParamType::Bool => {
-			let slice = try!(peek(slices, offset));
+			let slice = try!(peek(data, base, offset));
 
-			let b = try!(as_bool(slice));
+			let b = try!(as_bool(slice, base + offset));
 
 			let result = DecodeResult {
-				token: Token::Bool(b),
+				token: BorrowedToken::Bool(b),
 				new_offset: offset + 1,
 			};
 
 			Ok(result)
 		},
 		ParamType::FixedBytes(len) => {
-			let taken = try!(take_bytes(slices, offset, len));
+			let taken = try!(take_bytes(data, base, offset, len));
 
 			let result = DecodeResult {
-				token: Token::FixedBytes(taken.bytes),
+				token: BorrowedToken::FixedBytes(taken.bytes),
 				new_offset: taken.new_offset,
 			};
 
 			Ok(result)
 		},
 		ParamType::Bytes => {
-			let offset_slice = try!(peek(slices, offset));
-			let len_offset = (try!(as_u32(offset_slice)) / 32) as usize;
+			let offset_slice = try!(peek(data, base, offset));
+			let len_offset = (try!(as_u32(offset_slice, base + offset)) / 32) as usize;
 
-			let len_slice = try!(peek(slices, len_offset));
-			let len = try!(as_u32(len_slice)) as usize;
+			let len_slice = try!(peek(data, base, len_offset));
+			let len = try!(as_u32(len_slice, base + len_offset)) as usize;
 
-			let taken = try!(take_bytes(slices, len_offset + 1, len));
+			let taken = try!(take_bytes(data, base, len_offset + 1, len));
 
 			let result = DecodeResult {
-				token: Token::Bytes(taken.bytes),
+				token: BorrowedToken::Bytes(taken.bytes),
 				new_offset: offset + 1,
 			};
 
 			Ok(result)
 		},
 		ParamType::String => {
-			let offset_slice = try!(peek(slices, offset));
-			let len_offset = (try!(as_u32(offset_slice)) / 32) as usize;
+			let offset_slice = try!(peek(data, base, offset));
+			let len_offset = (try!(as_u32(offset_slice, base + offset)) / 32) as usize;
 
-			let len_slice = try!(peek(slices, len_offset));
-			let len = try!(as_u32(len_slice)) as usize;
+			let len_slice = try!(peek(data, base, len_offset));
+			let len = try!(as_u32(len_slice, base + len_offset)) as usize;
 
-			let taken = try!(take_bytes(slices, len_offset + 1, len));
+			let taken = try!(take_bytes(data, base, len_offset + 1, len));
+			let string = match str::from_utf8(taken.bytes) {
+				Ok(string) => string,
+				// reported as `FromUtf8Error`, like when decoding to an owned string
+				Err(_) => return Err(String::from_utf8(taken.bytes.to_vec()).unwrap_err().into()),
+			};
 
 			let result = DecodeResult {
-				token: Token::String(try!(String::from_utf8(taken.bytes))),
+				token: BorrowedToken::String(string),
 				new_offset: offset + 1,
 			};
 
 			Ok(result)
 		},
 		ParamType::Array(ref t) => {
-			let offset_slice = try!(peek(slices, offset));
-			let len_offset = (try!(as_u32(offset_slice)) / 32) as usize;
+			let offset_slice = try!(peek(data, base, offset));
+			let len_offset = (try!(as_u32(offset_slice, base + offset)) / 32) as usize;
 
-			let len_slice = try!(peek(slices, len_offset));
-			let len = try!(as_u32(len_slice)) as usize;
+			let len_slice = try!(peek(data, base, len_offset));
+			let len = try!(as_u32(len_slice, base + len_offset)) as usize;
+			if len > options.max_array_len {
+				return Err(ErrorKind::ArrayLengthLimitExceeded(len, options.max_array_len).into());
+			}
 
+			// elements follow the length, offsets of their data are relative to the first of them
+			let elements_data = &data[(len_offset + 1) * 32..];
+			let elements_base = base + len_offset + 1;
 			let mut tokens = vec![];
-			let mut new_offset = len_offset + 1;
+			let mut new_offset = 0;
 
 			for _ in 0..len {
-				let res = try!(decode_param(t, &slices, new_offset));
+				let res = try!(decode_param(t, elements_data, elements_base, new_offset, options, depth + 1));
 				new_offset = res.new_offset;
 				tokens.push(res.token);
 			}
 
 			let result = DecodeResult {
-				token: Token::Array(tokens),
+				token: BorrowedToken::Array(tokens),
 				new_offset: offset + 1,
 			};
 
 			Ok(result)
 		},
 		ParamType::FixedArray(ref t, len) => {
+			let is_dynamic = param.is_dynamic();
+
+			// fixed array of dynamic elements is encoded in the tail, like a dynamic tuple
+			let (array_data, array_base, mut new_offset) = if is_dynamic {
+				let (array_data, array_base) = try!(tail(data, base, offset));
+				(array_data, array_base, 0)
+			} else {
+				(data, base, offset)
+			};
+
 			let mut tokens = vec![];
-			let mut new_offset = offset;
 			for _ in 0..len {
-				let res = try!(decode_param(t, &slices, new_offset));
+				let res = try!(decode_param(t, array_data, array_base, new_offset, options, depth + 1));
 				new_offset = res.new_offset;
 				tokens.push(res.token);
 			}
 
 			let result = DecodeResult {
-				token: Token::FixedArray(tokens),
-				new_offset,
+				token: BorrowedToken::FixedArray(tokens),
+				new_offset: if is_dynamic { offset + 1 } else { new_offset },
 			};
 
 			Ok(result)
-		}
+		},
+		ParamType::Tuple(ref t) => {
+			let is_dynamic = param.is_dynamic();
+
+			// dynamic tuple is encoded in the tail, its params offsets are relative to the tuple beginning
+			let (tuple_data, tuple_base, mut new_offset) = if is_dynamic {
+				let (tuple_data, tuple_base) = try!(tail(data, base, offset));
+				(tuple_data, tuple_base, 0)
+			} else {
+				(data, base, offset)
+			};
+
+			let mut tokens = vec![];
+			for param in t {
+				let res = try!(decode_param(param, tuple_data, tuple_base, new_offset, options, depth + 1));
+				new_offset = res.new_offset;
+				tokens.push(res.token);
+			}
+
+			let result = DecodeResult {
+				token: BorrowedToken::Tuple(tokens),
+				new_offset: if is_dynamic { offset + 1 } else { new_offset },
+			};
+
+			Ok(result)
+		},
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use {decode, Token, ParamType};
+	use super::{decode_param, DecodeOptions};
+	use {decode, encode, decode_with_options, decode_with_names, decode_many, decode_borrowed, decode_strict, decode_from_hex, decode_function_input, Token, BorrowedToken, Param, ParamType, Error, ErrorKind, Uint};
 
 	#[test]
 	fn decode_address() {
@@ -251,7 +449,7 @@ mod tests {
 		let encoded = hex!("1111111111111111111111111111111111111111111111111111111111111111");
 		let uint = Token::Uint([0x11u8; 32].into());
 		let expected = vec![uint];
-		let decoded = decode(&[ParamType::Uint(32)], &encoded).unwrap();
+		let decoded = decode(&[ParamType::Uint(256)], &encoded).unwrap();
 		assert_eq!(decoded, expected);
 	}
 
@@ -260,10 +458,47 @@ mod tests {
 		let encoded = hex!("1111111111111111111111111111111111111111111111111111111111111111");
 		let int = Token::Int([0x11u8; 32].into());
 		let expected = vec![int];
-		let decoded = decode(&[ParamType::Int(32)], &encoded).unwrap();
+		let decoded = decode(&[ParamType::Int(256)], &encoded).unwrap();
 		assert_eq!(decoded, expected);
 	}
 
+	#[test]
+	fn decode_int_boundaries() {
+		let max = (Uint::one() << 255) - 1;
+		let min = Uint::one() << 255;
+		let cases = vec![
+			(Token::Uint(0u64.into()), hex!("0000000000000000000000000000000000000000000000000000000000000000")),
+			(Token::Uint(1u64.into()), hex!("0000000000000000000000000000000000000000000000000000000000000001")),
+			(Token::Uint(max), hex!("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")),
+			(Token::from(0i64), hex!("0000000000000000000000000000000000000000000000000000000000000000")),
+			(Token::from(1i64), hex!("0000000000000000000000000000000000000000000000000000000000000001")),
+			(Token::Int(max), hex!("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")),
+			(Token::from(-1i64), hex!("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")),
+			(Token::Int(min), hex!("8000000000000000000000000000000000000000000000000000000000000000")),
+		];
+		for (token, encoded) in cases {
+			assert_eq!(encode(&[token.clone()]), encoded.to_vec());
+			assert_eq!(decode(&[token.type_of()], &encoded).unwrap(), vec![token]);
+		}
+	}
+
+	#[test]
+	fn decode_int_checks_bit_width() {
+		let word = |value: Token| encode(&[value]);
+		assert_eq!(decode(&[ParamType::Uint(8)], &word(Token::from(255u64))).unwrap(), vec![Token::from(255u64)]);
+		assert!(decode(&[ParamType::Uint(8)], &word(Token::from(256u64))).is_err());
+		assert!(decode(&[ParamType::Uint(8)], &word(Token::from(-1i64))).is_err());
+		assert_eq!(decode(&[ParamType::Int(8)], &word(Token::from(127i64))).unwrap(), vec![Token::from(127i64)]);
+		assert_eq!(decode(&[ParamType::Int(8)], &word(Token::from(-128i64))).unwrap(), vec![Token::from(-128i64)]);
+		assert!(decode(&[ParamType::Int(8)], &word(Token::from(128i64))).is_err());
+		assert!(decode(&[ParamType::Int(8)], &word(Token::from(-129i64))).is_err());
+		assert!(decode(&[ParamType::Uint(255)], &word(Token::Uint(Uint::one() << 255))).is_err());
+
+		let tuple = ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Int(16))]);
+		let data = encode(&[Token::Bool(true), Token::from(1i128 << 15)]);
+		assert_eq!(error_offset(tuple, &data), (32, "integer does not fit in its type"));
+	}
+
 	#[test]
 	fn decode_dynamic_array_of_addresses() {
 		let encoded = hex!("
@@ -311,8 +546,8 @@ mod tests {
 		let encoded  = hex!("
 			0000000000000000000000000000000000000000000000000000000000000020
 			0000000000000000000000000000000000000000000000000000000000000002
+			0000000000000000000000000000000000000000000000000000000000000040
 			0000000000000000000000000000000000000000000000000000000000000080
-			00000000000000000000000000000000000000000000000000000000000000c0
 			0000000000000000000000000000000000000000000000000000000000000001
 			0000000000000000000000001111111111111111111111111111111111111111
 			0000000000000000000000000000000000000000000000000000000000000001
@@ -338,8 +573,8 @@ mod tests {
 		let encoded = hex!("
 			0000000000000000000000000000000000000000000000000000000000000020
 			0000000000000000000000000000000000000000000000000000000000000002
-			0000000000000000000000000000000000000000000000000000000000000080
-			00000000000000000000000000000000000000000000000000000000000000e0
+			0000000000000000000000000000000000000000000000000000000000000040
+			00000000000000000000000000000000000000000000000000000000000000a0
 			0000000000000000000000000000000000000000000000000000000000000002
 			0000000000000000000000001111111111111111111111111111111111111111
 			0000000000000000000000002222222222222222222222222222222222222222
@@ -394,6 +629,7 @@ mod tests {
 	#[test]
 	fn decode_fixed_array_of_dynamic_array_of_addresses() {
 		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
 			0000000000000000000000000000000000000000000000000000000000000040
 			00000000000000000000000000000000000000000000000000000000000000a0
 			0000000000000000000000000000000000000000000000000000000000000002
@@ -491,6 +727,49 @@ mod tests {
 		assert_eq!(decoded, expected);
 	}
 
+	#[test]
+	fn decode_borrowed_string_and_bytes() {
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000060
+			1234000000000000000000000000000000000000000000000000000000000000
+			00000000000000000000000000000000000000000000000000000000000000a0
+			0000000000000000000000000000000000000000000000000000000000000009
+			6761766f66796f726b0000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000003
+			0102030000000000000000000000000000000000000000000000000000000000
+		");
+		let types = [ParamType::String, ParamType::FixedBytes(2), ParamType::Bytes];
+		let decoded = decode_borrowed(&types, &encoded).unwrap();
+		assert_eq!(decoded, vec![
+			BorrowedToken::String("gavofyork"),
+			BorrowedToken::FixedBytes(&[0x12, 0x34]),
+			BorrowedToken::Bytes(&[1, 2, 3]),
+		]);
+
+		// borrowed from the input, not copied
+		match decoded[0] {
+			BorrowedToken::String(s) => assert_eq!(s.as_ptr(), encoded[128..].as_ptr()),
+			_ => unreachable!(),
+		}
+
+		let owned: Vec<Token> = decoded.into_iter().map(Into::into).collect();
+		assert_eq!(owned, decode(&types, &encoded).unwrap());
+	}
+
+	#[test]
+	fn decode_borrowed_invalid_utf8() {
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
+			0000000000000000000000000000000000000000000000000000000000000001
+			ff00000000000000000000000000000000000000000000000000000000000000
+		");
+		match decode_param(&ParamType::String, &encoded, 0, 0, &DecodeOptions::default(), 0) {
+			Err(Error(ErrorKind::Utf8(_), _)) => {},
+			Err(err) => panic!("unexpected error: {}", err),
+			Ok(_) => panic!("decoding should fail"),
+		}
+	}
+
 	#[test]
 	fn decode_from_empty_byte_slice() {
         // these can NOT be decoded from empty byte slice
@@ -506,9 +785,308 @@ mod tests {
         assert!(decode(&[ParamType::FixedBytes(1)], &[]).is_err());
         assert!(decode(&[ParamType::FixedArray(Box::new(ParamType::Bool), 1)], &[]).is_err());
 
-        // these are the only ones that can be decoded from empty byte slice
+        // these are the only ones that can be decoded from empty byte slice, their encoding is empty
         assert!(decode(&[ParamType::FixedBytes(0)], &[]).is_ok());
         assert!(decode(&[ParamType::FixedArray(Box::new(ParamType::Bool), 0)], &[]).is_ok());
+        assert!(decode(&[ParamType::FixedArray(Box::new(ParamType::Tuple(tuple_components![])), 2)], &[]).is_ok());
 	}
-}
 
+	#[test]
+	fn decode_static_tuple() {
+		let encoded = hex!("
+			0000000000000000000000001111111111111111111111111111111111111111
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000002222222222222222222222222222222222222222
+		");
+		let address1 = Token::Address([0x11u8; 20].into());
+		let address2 = Token::Address([0x22u8; 20].into());
+		let expected = vec![Token::Tuple(vec![address1, Token::Bool(true)]), address2];
+		let decoded = decode(&[
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]),
+			ParamType::Address,
+		], &encoded).unwrap();
+		assert_eq!(decoded, expected);
+	}
+
+	#[test]
+	fn decode_dynamic_tuple() {
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000009
+			6761766f66796f726b0000000000000000000000000000000000000000000000
+		");
+		let expected = vec![
+			Token::Tuple(vec![
+				Token::Uint(1.into()),
+				Token::String("gavofyork".to_owned()),
+			]),
+			Token::Bool(true),
+		];
+		let decoded = decode(&[
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Uint(256)), Box::new(ParamType::String)]),
+			ParamType::Bool,
+		], &encoded).unwrap();
+		assert_eq!(decoded, expected);
+	}
+
+	#[test]
+	fn decode_nested_tuples() {
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000080
+			0000000000000000000000000000000000000000000000000000000000000002
+			1234000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000003
+			666f6f0000000000000000000000000000000000000000000000000000000000
+		");
+		let expected = vec![
+			Token::Tuple(vec![
+				Token::Bytes(vec![0x12, 0x34]),
+				Token::Tuple(vec![
+					Token::Bool(false),
+					Token::String("foo".to_owned()),
+				]),
+			]),
+		];
+		let decoded = decode(&[
+			ParamType::Tuple(tuple_components![
+				Box::new(ParamType::Bytes),
+				Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::String)])),
+			]),
+		], &encoded).unwrap();
+		assert_eq!(decoded, expected);
+	}
+
+	#[test]
+	fn decode_function_input_with_selector() {
+		let data = hex!("
+			cdcd77c0
+			0000000000000000000000000000000000000000000000000000000000000045
+			0000000000000000000000000000000000000000000000000000000000000001
+		");
+		let decoded = decode_function_input(hex!("cdcd77c0"), &data, &[ParamType::Uint(32), ParamType::Bool]).unwrap();
+		assert_eq!(decoded, vec![Token::Uint(0x45.into()), Token::Bool(true)]);
+	}
+
+	#[test]
+	fn decode_function_input_with_wrong_selector() {
+		let data = hex!("cdcd77c00000000000000000000000000000000000000000000000000000000000000001");
+		assert_eq!(
+			format!("{}", decode_function_input(hex!("cdcd77c1"), &data, &[ParamType::Bool]).unwrap_err()),
+			"Expected data to start with function selector 0xcdcd77c1"
+		);
+		assert!(decode_function_input(hex!("cdcd77c0"), &data[..3], &[]).is_err());
+	}
+
+	#[test]
+	fn decode_strict_rejects_trailing_data() {
+		let types = [ParamType::Bool, ParamType::String];
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000002
+			6869000000000000000000000000000000000000000000000000000000000000
+		").to_vec();
+		let expected = vec![Token::Bool(true), Token::String("hi".into())];
+		assert_eq!(decode_strict(&types, &encoded).unwrap(), expected);
+
+		let mut trailing = encoded.clone();
+		trailing.extend_from_slice(&[0u8; 32]);
+		assert_eq!(decode(&types, &trailing).unwrap(), expected);
+		assert_eq!(
+			format!("{}", decode_strict(&types, &trailing).unwrap_err()),
+			"Unexpected trailing data, decoded 128 of 160 bytes"
+		);
+	}
+
+	fn error_offset(param: ParamType, data: &[u8]) -> (usize, &'static str) {
+		match decode_param(&param, data, 0, 0, &DecodeOptions::default(), 0) {
+			Err(Error(ErrorKind::InvalidDataAt(offset, reason), _)) => (offset, reason),
+			Err(err) => panic!("unexpected error: {}", err),
+			Ok(_) => panic!("decoding should fail"),
+		}
+	}
+
+	#[test]
+	fn decode_errors_contain_offset() {
+		let invalid_bool = hex!("
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000101
+		");
+		let param = ParamType::FixedArray(Box::new(ParamType::Bool), 2);
+		assert_eq!(error_offset(param, &invalid_bool), (32, "invalid boolean"));
+
+		let truncated_bytes = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
+			0000000000000000000000000000000000000000000000000000000000000040
+			1111111111111111111111111111111111111111111111111111111111111111
+		");
+		assert_eq!(error_offset(ParamType::Bytes, &truncated_bytes), (96, "unexpected end of data"));
+
+		let huge_offset = hex!("0000000000000000000000000000000000000001000000000000000000000020");
+		assert_eq!(error_offset(ParamType::String, &huge_offset), (0, "value does not fit in 32 bits"));
+	}
+
+	#[test]
+	fn decode_errors_in_dynamic_tuple_contain_absolute_offset() {
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000020
+			0000000000000000000000000000000000000000000000000000000000000005
+		");
+		let param = ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::String)]))]);
+		assert_eq!(error_offset(param.clone(), &encoded), (128, "unexpected end of data"));
+		assert_eq!(
+			format!("{}", decode(&[param], &encoded).unwrap_err().iter().nth(1).unwrap()),
+			"Invalid data at byte offset 128: unexpected end of data"
+		);
+	}
+
+	#[test]
+	fn decode_error_for_unaligned_data() {
+		match decode(&[ParamType::Bool], &[0u8; 33]) {
+			Err(Error(ErrorKind::InvalidDataAt(32, _), _)) => {},
+			other => panic!("unexpected result: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn decode_transfer_input_from_hex() {
+		// input of transfer(address,uint256) without the selector
+		let input = "0x\
+			0000000000000000000000004bbeeb066ed09b7aed07bf39eee0460dfa261520\
+			00000000000000000000000000000000000000000000000000000000002dc6c0";
+		let expected = vec![
+			Token::Address("4bbeeb066ed09b7aed07bf39eee0460dfa261520".parse().unwrap()),
+			Token::Uint(3_000_000.into()),
+		];
+		let types = [ParamType::Address, ParamType::Uint(256)];
+		assert_eq!(decode_from_hex(&types, input).unwrap(), expected);
+		assert_eq!(decode_from_hex(&types, &input[2..]).unwrap(), expected);
+	}
+
+	#[test]
+	fn decode_from_malformed_hex() {
+		match decode_from_hex(&[ParamType::Bool], "0x00zz") {
+			Err(Error(ErrorKind::Hex(_), _)) => {},
+			other => panic!("unexpected result: {:?}", other),
+		}
+		assert!(decode_from_hex(&[ParamType::Bool], "0x0").is_err());
+	}
+
+	fn nested_arrays(depth: usize) -> ParamType {
+		(0..depth).fold(ParamType::Uint(256), |param, _| ParamType::Array(Box::new(param)))
+	}
+
+	fn nested_arrays_encoding(depth: usize) -> Vec<u8> {
+		// every array has a single element
+		encode(&[(0..depth).fold(Token::Uint(1.into()), |token, _| Token::Array(vec![token]))])
+	}
+
+	#[test]
+	fn decode_too_deeply_nested_arrays() {
+		assert!(decode(&[nested_arrays(64)], &nested_arrays_encoding(64)).is_ok());
+		assert_eq!(
+			format!("{}", decode(&[nested_arrays(65)], &nested_arrays_encoding(65)).unwrap_err().iter().nth(1).unwrap()),
+			"Maximum nesting depth exceeded at depth 65"
+		);
+
+		let options = DecodeOptions { max_depth: 2, ..Default::default() };
+		assert!(decode_with_options(&[nested_arrays(2)], &nested_arrays_encoding(2), &options).is_ok());
+		assert!(decode_with_options(&[nested_arrays(3)], &nested_arrays_encoding(3), &options).is_err());
+	}
+
+	#[test]
+	fn decode_array_with_forged_length() {
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
+			00000000000000000000000000000000000000000000000000000000ffffffff
+		");
+		let param = ParamType::Array(Box::new(ParamType::Bool));
+		assert_eq!(
+			format!("{}", decode(&[param.clone()], &encoded).unwrap_err().iter().nth(1).unwrap()),
+			"Array length 4294967295 exceeds the limit of 100000"
+		);
+
+		let encoded = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
+			0000000000000000000000000000000000000000000000000000000000000002
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000000
+		");
+		let options = DecodeOptions { max_array_len: 1, ..Default::default() };
+		assert!(decode_with_options(&[param.clone()], &encoded, &options).is_err());
+		let options = DecodeOptions { max_array_len: 2, ..Default::default() };
+		assert_eq!(
+			decode_with_options(&[param], &encoded, &options).unwrap(),
+			vec![Token::Array(vec![Token::Bool(true), Token::Bool(false)])]
+		);
+	}
+
+	#[test]
+	fn decode_named_struct() {
+		// Uniswap v3 `slot0()` of a pool, with the unlocked flag left unnamed
+		let params: Vec<Param> = [
+			("sqrtPriceX96", "uint160"),
+			("tick", "int24"),
+			("observationIndex", "uint16"),
+			("observationCardinality", "uint16"),
+			("observationCardinalityNext", "uint16"),
+			("feeProtocol", "uint8"),
+			("", "bool"),
+		].iter().map(|&(name, kind)| Param { name: name.to_owned(), kind: kind.parse().unwrap(), internal_type: None, components: vec![] }).collect();
+		let encoded = hex!("
+			000000000000000000000000000000000000000000014f3d2fc2b3e0b1f9a5f1
+			fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffcf2c4
+			00000000000000000000000000000000000000000000000000000000000000a2
+			00000000000000000000000000000000000000000000000000000000000000b4
+			00000000000000000000000000000000000000000000000000000000000000b4
+			0000000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000001
+		");
+
+		let decoded = decode_with_names(&params, &encoded).unwrap();
+		assert_eq!(decoded, vec![
+			("sqrtPriceX96".to_owned(), Token::Uint("14f3d2fc2b3e0b1f9a5f1".parse().unwrap())),
+			("tick".to_owned(), Token::Int(!::Uint::from(0x30d3b))),
+			("observationIndex".to_owned(), Token::Uint(162.into())),
+			("observationCardinality".to_owned(), Token::Uint(180.into())),
+			("observationCardinalityNext".to_owned(), Token::Uint(180.into())),
+			("feeProtocol".to_owned(), Token::Uint(0.into())),
+			("6".to_owned(), Token::Bool(true)),
+		]);
+
+		assert!(decode_with_names(&params, &encoded[..192]).is_err());
+	}
+
+	#[test]
+	fn decode_many_keeps_order_and_failures() {
+		let calls: Vec<(Vec<ParamType>, Vec<u8>)> = (0..100u64)
+			.map(|i| match i % 3 {
+				0 => (vec![ParamType::Uint(256)], encode(&[Token::Uint(i.into())])),
+				1 => (vec![ParamType::String], encode(&[Token::String(i.to_string())])),
+				// truncated data
+				_ => (vec![ParamType::Uint(256)], vec![0u8; 31]),
+			})
+			.collect();
+
+		let decoded = decode_many(&calls);
+		assert_eq!(decoded.len(), calls.len());
+		for (i, result) in (0..100u64).zip(decoded) {
+			match i % 3 {
+				0 => assert_eq!(result.unwrap(), vec![Token::Uint(i.into())]),
+				1 => assert_eq!(result.unwrap(), vec![Token::String(i.to_string())]),
+				_ => assert!(result.is_err()),
+			}
+		}
+
+		assert!(decode_many(&[]).is_empty());
+	}
+}
@@ -0,0 +1,686 @@
+//! EIP-712 typed structured data hashing.
+//!
+//! Typed data is given in the json format of `eth_signTypedData_v4`, see
+//! [EIP-712](https://eips.ethereum.org/EIPS/eip-712).
+
+use std::collections::HashMap;
+use hex::FromHex;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use tiny_keccak::keccak256;
+use param_type::Reader;
+use {Address, Hash, ParamType, Result, Token, Uint, encode};
+
+/// Name of the type of the domain.
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// Field of a struct type.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Eip712Field {
+	/// Field name.
+	pub name: String,
+	/// Field type, either an atomic type like `uint256`, or the name of a struct type.
+	#[serde(rename = "type")]
+	pub kind: String,
+}
+
+/// Signing domain, distinguishes the signatures of different dapps.
+///
+/// All fields are optional, those that are present make up the `EIP712Domain` type.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip712Domain {
+	/// Name of the dapp or protocol.
+	pub name: Option<String>,
+	/// Current major version of the signing domain.
+	pub version: Option<String>,
+	/// Chain id of the network.
+	#[serde(default, deserialize_with = "deserialize_uint")]
+	pub chain_id: Option<Uint>,
+	/// Address of the contract verifying the signature.
+	pub verifying_contract: Option<Address>,
+	/// Disambiguating salt of the protocol.
+	pub salt: Option<Hash>,
+}
+
+impl Eip712Domain {
+	/// Returns the fields of the domain type, in the order defined by EIP-712.
+	fn fields(&self) -> Vec<Eip712Field> {
+		let field = |name: &str, kind: &str| Eip712Field { name: name.to_owned(), kind: kind.to_owned() };
+		let mut fields = vec![];
+		if self.name.is_some() {
+			fields.push(field("name", "string"));
+		}
+		if self.version.is_some() {
+			fields.push(field("version", "string"));
+		}
+		if self.chain_id.is_some() {
+			fields.push(field("chainId", "uint256"));
+		}
+		if self.verifying_contract.is_some() {
+			fields.push(field("verifyingContract", "address"));
+		}
+		if self.salt.is_some() {
+			fields.push(field("salt", "bytes32"));
+		}
+		fields
+	}
+
+	/// Returns the domain as json value, to be hashed like any other struct.
+	fn to_value(&self) -> Value {
+		let mut value = serde_json::Map::new();
+		if let Some(ref name) = self.name {
+			value.insert("name".to_owned(), Value::String(name.clone()));
+		}
+		if let Some(ref version) = self.version {
+			value.insert("version".to_owned(), Value::String(version.clone()));
+		}
+		if let Some(chain_id) = self.chain_id {
+			value.insert("chainId".to_owned(), Value::String(format!("{}", chain_id)));
+		}
+		if let Some(verifying_contract) = self.verifying_contract {
+			value.insert("verifyingContract".to_owned(), Value::String(format!("0x{:x}", verifying_contract)));
+		}
+		if let Some(salt) = self.salt {
+			value.insert("salt".to_owned(), Value::String(format!("0x{:x}", salt)));
+		}
+		Value::Object(value)
+	}
+}
+
+/// Typed structured data, as signed with `eth_signTypedData_v4`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+	/// Signing domain.
+	pub domain: Eip712Domain,
+	/// Struct types, maps name to fields. May include the `EIP712Domain` type.
+	pub types: HashMap<String, Vec<Eip712Field>>,
+	/// Type of the message.
+	pub primary_type: String,
+	/// Message to sign.
+	pub message: Value,
+}
+
+impl TypedData {
+	/// Parses typed data in the json format of `eth_signTypedData_v4`, see `validate`.
+	pub fn from_json_value(value: Value) -> Result<TypedData> {
+		let typed_data: TypedData = serde_json::from_value(value)?;
+		typed_data.validate()?;
+		Ok(typed_data)
+	}
+
+	/// Checks that the primary type and the types of all fields are defined, and that
+	/// the `EIP712Domain` type, if present, declares exactly the fields of the domain.
+	pub fn validate(&self) -> Result<()> {
+		if !self.types.contains_key(&self.primary_type) && self.primary_type != DOMAIN_TYPE {
+			bail!("Unknown primary type `{}`", self.primary_type);
+		}
+
+		for (type_name, fields) in &self.types {
+			for (index, field) in fields.iter().enumerate() {
+				if fields[..index].iter().any(|f| f.name == field.name) {
+					bail!("Duplicate field `{}` of `{}`", field.name, type_name);
+				}
+				if !self.is_valid_type(&field.kind) {
+					bail!("Unknown type `{}` of field `{}` of `{}`", field.kind, field.name, type_name);
+				}
+			}
+		}
+
+		if let Some(fields) = self.types.get(DOMAIN_TYPE) {
+			let expected = self.domain.fields();
+			for field in fields {
+				match expected.iter().find(|f| f.name == field.name) {
+					Some(f) if f.kind == field.kind => {},
+					Some(_) => bail!("Invalid type `{}` of domain field `{}`", field.kind, field.name),
+					None => bail!("Missing value of domain field `{}`", field.name),
+				}
+			}
+			if let Some(field) = expected.iter().find(|f| !fields.iter().any(|field| field.name == f.name)) {
+				bail!("Undeclared domain field `{}`", field.name);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Returns true if `kind` is a struct type, an atomic or dynamic type, or an array of these.
+	fn is_valid_type(&self, kind: &str) -> bool {
+		let base = base_type(kind);
+		if self.types.contains_key(base) {
+			// array suffixes are validated on a known atomic type
+			return Reader::read(&format!("bool{}", &kind[base.len()..])).is_ok();
+		}
+
+		match Reader::read(kind) {
+			Ok(ParamType::Tuple(_)) | Err(_) => false,
+			Ok(_) => !kind.contains('('),
+		}
+	}
+
+	/// Returns the fields of the struct type named `type_name`.
+	fn fields(&self, type_name: &str) -> Result<Vec<Eip712Field>> {
+		match self.types.get(type_name) {
+			Some(fields) => Ok(fields.clone()),
+			None if type_name == DOMAIN_TYPE => Ok(self.domain.fields()),
+			None => bail!("Unknown type `{}`", type_name),
+		}
+	}
+
+	/// Returns the encoded struct type, e.g. `Mail(Person from,Person to,string contents)`,
+	/// followed by the struct types it references, directly or transitively, in alphabetical
+	/// order. Arrays of structs, like `Person[]`, reference the struct type.
+	pub fn type_string(&self, type_name: &str) -> Result<String> {
+		let mut referenced = vec![];
+		self.collect_references(type_name, &mut referenced)?;
+		referenced.retain(|name| name != type_name);
+		referenced.sort();
+
+		let mut encoded = String::new();
+		for name in Some(type_name.to_owned()).into_iter().chain(referenced) {
+			let fields = self.fields(&name)?.iter()
+				.map(|field| format!("{} {}", field.kind, field.name))
+				.collect::<Vec<_>>()
+				.join(",");
+			encoded.push_str(&format!("{}({})", name, fields));
+		}
+
+		Ok(encoded)
+	}
+
+	/// Collects the struct types referenced by `type_name`, including itself.
+	fn collect_references(&self, type_name: &str, referenced: &mut Vec<String>) -> Result<()> {
+		if referenced.iter().any(|name| name == type_name) {
+			return Ok(());
+		}
+
+		referenced.push(type_name.to_owned());
+		for field in self.fields(type_name)? {
+			let base = base_type(&field.kind);
+			if self.types.contains_key(base) {
+				self.collect_references(base, referenced)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Returns the hash of the encoded struct type named `type_name`.
+	pub fn type_hash(&self, type_name: &str) -> Result<Hash> {
+		Ok(keccak256(self.type_string(type_name)?.as_bytes()).into())
+	}
+
+	/// Returns the hash of `value`, a struct of type `type_name`.
+	pub fn hash_struct(&self, type_name: &str, value: &Value) -> Result<Hash> {
+		let object = match value.as_object() {
+			Some(object) => object,
+			None => bail!("Expected `{}` struct, got `{}`", type_name, value),
+		};
+
+		let mut encoded = self.type_hash(type_name)?.as_ref().to_vec();
+		for field in self.fields(type_name)? {
+			let value = match object.get(&field.name) {
+				Some(value) => value,
+				None => bail!("Missing field `{}` of `{}`", field.name, type_name),
+			};
+			encoded.extend_from_slice(&self.encode_data(&field.kind, value)?);
+		}
+
+		Ok(keccak256(&encoded).into())
+	}
+
+	/// Encodes `value` of type `kind` to a single word.
+	fn encode_data(&self, kind: &str, value: &Value) -> Result<[u8; 32]> {
+		if kind.ends_with(']') {
+			return self.encode_array(kind, value);
+		}
+
+		if self.types.contains_key(kind) {
+			return Ok(self.hash_struct(kind, value)?.into());
+		}
+
+		let param_type = Reader::read(kind)?;
+		let token = match param_type {
+			ParamType::Address => {
+				let bytes = parse_bytes(value, kind)?;
+				if bytes.len() != 20 {
+					bail!("Expected `address`, got {} bytes", bytes.len());
+				}
+				Token::Address(Address::from_slice(&bytes))
+			},
+			ParamType::Bool => match value.as_bool() {
+				Some(value) => Token::Bool(value),
+				None => bail!("Expected `bool`, got `{}`", value),
+			},
+			ParamType::Uint(_) => Token::Uint(parse_uint(value)?),
+			ParamType::Int(_) => Token::Int(parse_int(value)?),
+			ParamType::FixedBytes(len) => {
+				let bytes = parse_bytes(value, kind)?;
+				if bytes.len() > len {
+					bail!("Expected `{}`, got {} bytes", kind, bytes.len());
+				}
+				Token::FixedBytes(bytes)
+			},
+			ParamType::String => return Ok(keccak256(parse_str(value, kind)?.as_bytes())),
+			ParamType::Bytes => return Ok(keccak256(&parse_bytes(value, kind)?)),
+			ParamType::Array(_) | ParamType::FixedArray(..) | ParamType::Tuple(_) => {
+				bail!("Unknown type `{}`", kind)
+			},
+		};
+
+		let mut word = [0u8; 32];
+		word.copy_from_slice(&encode(&[token]));
+		Ok(word)
+	}
+
+	/// Encodes array as the hash of the concatenated encodings of its elements.
+	fn encode_array(&self, kind: &str, value: &Value) -> Result<[u8; 32]> {
+		let elements = match value.as_array() {
+			Some(elements) => elements,
+			None => bail!("Expected `{}` array, got `{}`", kind, value),
+		};
+
+		let open = kind.rfind('[').expect("array type ends with `]`; qed");
+		let len = &kind[open + 1..kind.len() - 1];
+		if !len.is_empty() && len.parse() != Ok(elements.len()) {
+			bail!("Expected `{}`, got {} elements", kind, elements.len());
+		}
+
+		let mut encoded = Vec::with_capacity(elements.len() * 32);
+		for element in elements {
+			encoded.extend_from_slice(&self.encode_data(&kind[..open], element)?);
+		}
+
+		Ok(keccak256(&encoded))
+	}
+
+	/// Returns the hash of the domain.
+	pub fn domain_separator(&self) -> Result<Hash> {
+		self.hash_struct(DOMAIN_TYPE, &self.domain.to_value())
+	}
+
+	/// Returns the hash to sign, `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+	pub fn sign_hash(&self) -> Result<Hash> {
+		let mut encoded = vec![0x19, 0x01];
+		encoded.extend_from_slice(self.domain_separator()?.as_ref());
+		encoded.extend_from_slice(self.hash_struct(&self.primary_type, &self.message)?.as_ref());
+		Ok(keccak256(&encoded).into())
+	}
+}
+
+/// Returns the type without array suffixes, e.g. `Person` for `Person[][2]`.
+fn base_type(kind: &str) -> &str {
+	match kind.find('[') {
+		Some(index) => &kind[..index],
+		None => kind,
+	}
+}
+
+fn parse_str<'a>(value: &'a Value, kind: &str) -> Result<&'a str> {
+	match value.as_str() {
+		Some(value) => Ok(value),
+		None => bail!("Expected `{}` string, got `{}`", kind, value),
+	}
+}
+
+fn parse_bytes(value: &Value, kind: &str) -> Result<Vec<u8>> {
+	let value = parse_str(value, kind)?;
+	let value = value.strip_prefix("0x").unwrap_or(value);
+	Ok(value.from_hex()?)
+}
+
+/// Parses unsigned integer, given as json number, decimal string or `0x` prefixed hex string.
+fn parse_uint(value: &Value) -> Result<Uint> {
+	if let Some(value) = value.as_u64() {
+		return Ok(value.into());
+	}
+
+	let parsed = match value.as_str() {
+		Some(value) if value.starts_with("0x") => value[2..].parse().ok(),
+		Some(value) => Uint::from_dec_str(value).ok(),
+		None => None,
+	};
+
+	match parsed {
+		Some(parsed) => Ok(parsed),
+		None => bail!("Expected unsigned integer, got `{}`", value),
+	}
+}
+
+/// Parses signed integer to its two's complement, see `parse_uint`.
+fn parse_int(value: &Value) -> Result<Uint> {
+	let negative = match *value {
+		Value::Number(ref number) => number.as_i64().map(|n| n < 0).unwrap_or(false),
+		Value::String(ref s) => s.starts_with('-'),
+		_ => false,
+	};
+
+	if !negative {
+		return parse_uint(value);
+	}
+
+	let magnitude = match *value {
+		Value::Number(ref number) => Value::from(number.as_i64().map(|n| n.wrapping_neg() as u64)),
+		Value::String(ref s) => Value::String(s[1..].to_owned()),
+		_ => unreachable!("only numbers and strings are negative; qed"),
+	};
+
+	let magnitude = parse_uint(&magnitude)?;
+	Ok((!magnitude).overflowing_add(1.into()).0)
+}
+
+fn deserialize_uint<'a, D>(deserializer: D) -> ::std::result::Result<Option<Uint>, D::Error> where D: Deserializer<'a> {
+	use serde::de::Error;
+
+	let value = Value::deserialize(deserializer)?;
+	parse_uint(&value).map(Some).map_err(|e| D::Error::custom(format!("{}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::TypedData;
+
+	const MAIL: &str = r#"{
+		"types": {
+			"EIP712Domain": [
+				{ "name": "name", "type": "string" },
+				{ "name": "version", "type": "string" },
+				{ "name": "chainId", "type": "uint256" },
+				{ "name": "verifyingContract", "type": "address" }
+			],
+			"Person": [
+				{ "name": "name", "type": "string" },
+				{ "name": "wallet", "type": "address" }
+			],
+			"Mail": [
+				{ "name": "from", "type": "Person" },
+				{ "name": "to", "type": "Person" },
+				{ "name": "contents", "type": "string" }
+			]
+		},
+		"primaryType": "Mail",
+		"domain": {
+			"name": "Ether Mail",
+			"version": "1",
+			"chainId": 1,
+			"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+		},
+		"message": {
+			"from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+			"to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+			"contents": "Hello, Bob!"
+		}
+	}"#;
+
+	#[test]
+	fn test_mail() {
+		let typed_data: TypedData = serde_json::from_str(MAIL).unwrap();
+
+		assert_eq!(typed_data.type_string("Mail").unwrap(), "Mail(Person from,Person to,string contents)Person(string name,address wallet)");
+		assert_eq!(typed_data.type_hash("Mail").unwrap(), "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2".parse().unwrap());
+		assert_eq!(
+			typed_data.hash_struct("Mail", &typed_data.message).unwrap(),
+			"c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e".parse().unwrap()
+		);
+		assert_eq!(typed_data.domain_separator().unwrap(), "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f".parse().unwrap());
+		assert_eq!(typed_data.sign_hash().unwrap(), "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2".parse().unwrap());
+	}
+
+	#[test]
+	fn test_implicit_domain_type() {
+		let mut typed_data: TypedData = serde_json::from_str(MAIL).unwrap();
+		let separator = typed_data.domain_separator().unwrap();
+
+		typed_data.types.remove("EIP712Domain");
+		assert_eq!(typed_data.domain_separator().unwrap(), separator);
+	}
+
+	#[test]
+	fn test_uniswap_permit() {
+		let typed_data = TypedData::from_json_value(json!({
+			"types": {
+				"EIP712Domain": [
+					{ "name": "name", "type": "string" },
+					{ "name": "chainId", "type": "uint256" },
+					{ "name": "verifyingContract", "type": "address" }
+				],
+				"Permit": [
+					{ "name": "owner", "type": "address" },
+					{ "name": "spender", "type": "address" },
+					{ "name": "value", "type": "uint256" },
+					{ "name": "nonce", "type": "uint256" },
+					{ "name": "deadline", "type": "uint256" }
+				]
+			},
+			"primaryType": "Permit",
+			"domain": {
+				"name": "Uniswap",
+				"chainId": 1,
+				"verifyingContract": "0x1f9840a85d5aF5bf1D1762F925BDADdC4201F984"
+			},
+			"message": {
+				"owner": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+				"spender": "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D",
+				"value": "1000000000000000000",
+				"nonce": 0,
+				"deadline": "1700000000"
+			}
+		})).unwrap();
+
+		assert_eq!(typed_data.domain_separator().unwrap(), "28e9a6a663fbec82798f959fbf7b0805000a2aa21154d62a24be5f2a8716bf81".parse().unwrap());
+		assert_eq!(typed_data.sign_hash().unwrap(), "dea6a56e812c3e8118c0b48f5c6a1a1436bd7ffa394d779623397a2378e695da".parse().unwrap());
+	}
+
+	#[test]
+	fn test_uniswap_permit2() {
+		let typed_data = TypedData::from_json_value(json!({
+			"types": {
+				"EIP712Domain": [
+					{ "name": "name", "type": "string" },
+					{ "name": "chainId", "type": "uint256" },
+					{ "name": "verifyingContract", "type": "address" }
+				],
+				"PermitSingle": [
+					{ "name": "details", "type": "PermitDetails" },
+					{ "name": "spender", "type": "address" },
+					{ "name": "sigDeadline", "type": "uint256" }
+				],
+				"PermitDetails": [
+					{ "name": "token", "type": "address" },
+					{ "name": "amount", "type": "uint160" },
+					{ "name": "expiration", "type": "uint48" },
+					{ "name": "nonce", "type": "uint48" }
+				]
+			},
+			"primaryType": "PermitSingle",
+			"domain": {
+				"name": "Permit2",
+				"chainId": "1",
+				"verifyingContract": "0x000000000022d473030f116ddee9f6b43ac78ba3"
+			},
+			"message": {
+				"details": {
+					"token": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+					"amount": "1461501637330902918203684832716283019655932542975",
+					"expiration": "1702592000",
+					"nonce": "0"
+				},
+				"spender": "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD",
+				"sigDeadline": "1700002000"
+			}
+		})).unwrap();
+
+		// the domain separator of the mainnet Permit2 contract
+		assert_eq!(typed_data.domain_separator().unwrap(), "866a5aba21966af95d6c7ab78eb2b2fc913915c28be3b9aa07cc04ff903e3f28".parse().unwrap());
+		assert_eq!(typed_data.sign_hash().unwrap(), "d589b98f02c57a2e186f5cc5194e7d1a400d2413f274a41612502ee1c62f00c0".parse().unwrap());
+	}
+
+	#[test]
+	fn test_seaport_order() {
+		let typed_data = TypedData::from_json_value(json!({
+			"types": {
+				"EIP712Domain": [
+					{ "name": "name", "type": "string" },
+					{ "name": "version", "type": "string" },
+					{ "name": "chainId", "type": "uint256" },
+					{ "name": "verifyingContract", "type": "address" }
+				],
+				"OrderComponents": [
+					{ "name": "offerer", "type": "address" },
+					{ "name": "zone", "type": "address" },
+					{ "name": "offer", "type": "OfferItem[]" },
+					{ "name": "consideration", "type": "ConsiderationItem[]" },
+					{ "name": "orderType", "type": "uint8" },
+					{ "name": "startTime", "type": "uint256" },
+					{ "name": "endTime", "type": "uint256" },
+					{ "name": "zoneHash", "type": "bytes32" },
+					{ "name": "salt", "type": "uint256" },
+					{ "name": "conduitKey", "type": "bytes32" },
+					{ "name": "counter", "type": "uint256" }
+				],
+				"OfferItem": [
+					{ "name": "itemType", "type": "uint8" },
+					{ "name": "token", "type": "address" },
+					{ "name": "identifierOrCriteria", "type": "uint256" },
+					{ "name": "startAmount", "type": "uint256" },
+					{ "name": "endAmount", "type": "uint256" }
+				],
+				"ConsiderationItem": [
+					{ "name": "itemType", "type": "uint8" },
+					{ "name": "token", "type": "address" },
+					{ "name": "identifierOrCriteria", "type": "uint256" },
+					{ "name": "startAmount", "type": "uint256" },
+					{ "name": "endAmount", "type": "uint256" },
+					{ "name": "recipient", "type": "address" }
+				]
+			},
+			"primaryType": "OrderComponents",
+			"domain": {
+				"name": "Seaport",
+				"version": "1.5",
+				"chainId": 1,
+				"verifyingContract": "0x00000000000000ADc04C56Bf30aC9d3c0aAF14dC"
+			},
+			"message": {
+				"offerer": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+				"zone": "0x0000000000000000000000000000000000000000",
+				"offer": [{
+					"itemType": 2,
+					"token": "0xBC4CA0EdA7647A8aB7C2061c2E118A18a936f13D",
+					"identifierOrCriteria": "1234",
+					"startAmount": "1",
+					"endAmount": "1"
+				}],
+				"consideration": [{
+					"itemType": 0,
+					"token": "0x0000000000000000000000000000000000000000",
+					"identifierOrCriteria": "0",
+					"startAmount": "9750000000000000000",
+					"endAmount": "9750000000000000000",
+					"recipient": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+				}, {
+					"itemType": 0,
+					"token": "0x0000000000000000000000000000000000000000",
+					"identifierOrCriteria": "0",
+					"startAmount": "250000000000000000",
+					"endAmount": "250000000000000000",
+					"recipient": "0x0000a26b00c1F0DF003000390027140000fAa719"
+				}],
+				"orderType": 0,
+				"startTime": "1700000000",
+				"endTime": "1702592000",
+				"zoneHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+				"salt": "24446860302761739304752683030156737591518664810215442929818227897836383814680",
+				"conduitKey": "0x0000007b02230091a7ed01230072f7006a004d60a8d4e71d599b8104250f0000",
+				"counter": "0"
+			}
+		})).unwrap();
+
+		assert_eq!(
+			typed_data.type_string("OrderComponents").unwrap(),
+			"OrderComponents(address offerer,address zone,OfferItem[] offer,ConsiderationItem[] consideration,\
+			uint8 orderType,uint256 startTime,uint256 endTime,bytes32 zoneHash,uint256 salt,bytes32 conduitKey,uint256 counter)\
+			ConsiderationItem(uint8 itemType,address token,uint256 identifierOrCriteria,uint256 startAmount,uint256 endAmount,address recipient)\
+			OfferItem(uint8 itemType,address token,uint256 identifierOrCriteria,uint256 startAmount,uint256 endAmount)"
+		);
+		// the order type hash of the Seaport contracts
+		assert_eq!(typed_data.type_hash("OrderComponents").unwrap(), "fa445660b7e21515a59617fcd68910b487aa5808b8abda3d78bc85df364b2c2f".parse().unwrap());
+		assert_eq!(
+			typed_data.hash_struct("OrderComponents", &typed_data.message).unwrap(),
+			"e120f16aba6e4572c3f13e82d92d266b81bc022350e63c32b4d5509347d40713".parse().unwrap()
+		);
+		assert_eq!(typed_data.sign_hash().unwrap(), "f0518e0e033d063168257e4d41cce5c42af07b2c0270887c0945d56bbd372e49".parse().unwrap());
+	}
+
+	#[test]
+	fn test_arrays() {
+		let mut typed_data = TypedData::from_json_value(json!({
+			"types": {
+				"EIP712Domain": [{ "name": "name", "type": "string" }],
+				"Person": [
+					{ "name": "name", "type": "string" },
+					{ "name": "wallets", "type": "address[]" }
+				],
+				"Group": [
+					{ "name": "name", "type": "string" },
+					{ "name": "members", "type": "Person[2]" },
+					{ "name": "scores", "type": "int8[][]" }
+				]
+			},
+			"primaryType": "Group",
+			"domain": { "name": "Groups" },
+			"message": {
+				"name": "Pals",
+				"members": [
+					{
+						"name": "Cow",
+						"wallets": ["0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826", "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"]
+					},
+					{ "name": "Bob", "wallets": [] }
+				],
+				"scores": [[1, -2], [], [-128]]
+			}
+		})).unwrap();
+
+		assert_eq!(typed_data.type_string("Group").unwrap(), "Group(string name,Person[2] members,int8[][] scores)Person(string name,address[] wallets)");
+		assert_eq!(typed_data.sign_hash().unwrap(), "c4552ba9938a8823c084719986eeefcf104abd6b147b245056f60777c31f3897".parse().unwrap());
+
+		typed_data.message["members"].as_array_mut().unwrap().pop();
+		assert_eq!(format!("{}", typed_data.sign_hash().unwrap_err()), "Expected `Person[2]`, got 1 elements");
+	}
+
+	#[test]
+	fn test_validation() {
+		fn error(mutate: fn(&mut serde_json::Value)) -> String {
+			let mut value: serde_json::Value = serde_json::from_str(MAIL).unwrap();
+			mutate(&mut value);
+			format!("{}", TypedData::from_json_value(value).unwrap_err())
+		}
+
+		assert!(TypedData::from_json_value(serde_json::from_str(MAIL).unwrap()).is_ok());
+		assert_eq!(error(|v| v["primaryType"] = json!("Letter")), "Unknown primary type `Letter`");
+		assert_eq!(error(|v| v["types"]["Mail"][1]["type"] = json!("Persona")), "Unknown type `Persona` of field `to` of `Mail`");
+		assert_eq!(error(|v| v["types"]["Mail"][1]["type"] = json!("uint7")), "Unknown type `uint7` of field `to` of `Mail`");
+		assert_eq!(error(|v| v["types"]["Mail"][1]["type"] = json!("Person[x]")), "Unknown type `Person[x]` of field `to` of `Mail`");
+		assert_eq!(error(|v| v["types"]["Mail"][1]["name"] = json!("from")), "Duplicate field `from` of `Mail`");
+		assert_eq!(error(|v| v["types"]["EIP712Domain"][2]["type"] = json!("uint64")), "Invalid type `uint64` of domain field `chainId`");
+		assert_eq!(error(|v| { v["domain"].as_object_mut().unwrap().remove("version"); }), "Missing value of domain field `version`");
+		assert_eq!(error(|v| v["domain"]["salt"] = json!(format!("0x{}", "11".repeat(32)))), "Undeclared domain field `salt`");
+		assert!(error(|v| v["domain"]["chainId"] = json!("one")).contains("Expected unsigned integer"));
+	}
+
+	#[test]
+	fn test_invalid_message() {
+		let mut typed_data: TypedData = serde_json::from_str(MAIL).unwrap();
+		typed_data.message["to"]["wallet"] = serde_json::Value::Bool(true);
+		assert!(typed_data.sign_hash().is_err());
+
+		typed_data.message["to"].as_object_mut().unwrap().remove("wallet");
+		assert_eq!(format!("{}", typed_data.sign_hash().unwrap_err()), "Missing field `wallet` of `Person`");
+
+		typed_data.primary_type = "Letter".to_owned();
+		assert_eq!(format!("{}", typed_data.sign_hash().unwrap_err()), "Unknown type `Letter`");
+	}
+}
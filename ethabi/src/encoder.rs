@@ -1,174 +1,548 @@
 //! ABI encoder.
 
+use core::slice;
+#[cfg(feature = "std")]
+use std::io;
+use hex::ToHex;
 use util::pad_u32;
-use {Token, Bytes};
+use {Token, ParamType, Address, Bytes, Error, Int, Uint, Result, ResultExt};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
-fn pad_bytes(bytes: &[u8]) -> Vec<[u8; 32]> {
-	let mut result = vec![pad_u32(bytes.len() as u32)];
-	result.extend(pad_fixed_bytes(bytes));
-	result
+/// Destination of the encoding, written word by word.
+///
+/// Implemented for every `io::Write`, and for `Vec<u8>` in `no_std` builds.
+trait Sink {
+	fn put(&mut self, bytes: &[u8]) -> Result<()>;
 }
 
-fn pad_fixed_bytes(bytes: &[u8]) -> Vec<[u8; 32]> {
-	let mut result = vec![];
-	let len = (bytes.len() + 31) / 32;
-	for i in 0..len {
-		let mut padded = [0u8; 32];
-
-		let to_copy = match i == len - 1 {
-			false => 32,
-			true => match bytes.len() % 32 {
-				0 => 32,
-				x => x,
-			},
-		};
+#[cfg(feature = "std")]
+impl<W: io::Write> Sink for W {
+	fn put(&mut self, bytes: &[u8]) -> Result<()> {
+		self.write_all(bytes)?;
+		Ok(())
+	}
+}
 
-		let offset = 32 * i;
-		padded[..to_copy].copy_from_slice(&bytes[offset..offset + to_copy]);
-		result.push(padded);
+#[cfg(not(feature = "std"))]
+impl Sink for Vec<u8> {
+	fn put(&mut self, bytes: &[u8]) -> Result<()> {
+		self.extend_from_slice(bytes);
+		Ok(())
 	}
+}
 
-	result
+/// Writes bytes right padded with zeros to a multiple of 32 bytes.
+fn put_padded<S: Sink>(bytes: &[u8], sink: &mut S) -> Result<()> {
+	sink.put(bytes)?;
+	sink.put(&[0u8; 32][..padded_size(bytes.len()) - bytes.len()])
 }
 
-#[derive(Debug)]
-enum Mediate {
-	Raw(Vec<[u8; 32]>),
-	Prefixed(Vec<[u8; 32]>),
-	FixedArray(Vec<Mediate>),
-	Array(Vec<Mediate>),
+/// Types of consecutive tokens, when known.
+///
+/// Tokens alone don't tell whether an empty fixed array like `string[0]` is dynamic, so
+/// without the types it's encoded as static. With the types, it's decided from them.
+#[derive(Clone, Copy)]
+enum Kinds<'a> {
+	Unknown,
+	Each(&'a [ParamType]),
+	Tuple(&'a [Box<ParamType>]),
+	Same(&'a ParamType),
 }
 
-impl Mediate {
-	fn init_len(&self) -> u32 {
-		match *self {
-			Mediate::Raw(ref raw) => 32 * raw.len() as u32,
-			Mediate::Prefixed(_) => 32,
-			Mediate::FixedArray(ref nes) => nes.iter().fold(0, |acc, m| acc + m.init_len()),
-			Mediate::Array(_) => 32,
+impl<'a> Kinds<'a> {
+	/// Types of the elements of a token of type `kind`.
+	fn of_elements(kind: Option<&'a ParamType>) -> Self {
+		match kind {
+			Some(ParamType::Array(kind)) | Some(ParamType::FixedArray(kind, _)) => Kinds::Same(kind),
+			Some(ParamType::Tuple(kinds)) => Kinds::Tuple(kinds),
+			_ => Kinds::Unknown,
 		}
 	}
 
-	fn closing_len(&self) -> u32 {
-		match *self {
-			Mediate::Raw(_) => 0,
-			Mediate::Prefixed(ref pre) => pre.len() as u32 * 32,
-			Mediate::FixedArray(ref nes) => nes.iter().fold(0, |acc, m| acc + m.closing_len()),
-			Mediate::Array(ref nes) => nes.iter().fold(32, |acc, m| acc + m.init_len() + m.closing_len()),
+	fn get(self, index: usize) -> Option<&'a ParamType> {
+		match self {
+			Kinds::Unknown => None,
+			Kinds::Each(kinds) => kinds.get(index),
+			Kinds::Tuple(kinds) => kinds.get(index).map(|kind| &**kind),
+			Kinds::Same(kind) => Some(kind),
 		}
 	}
+}
 
-	fn offset_for(mediates: &[Mediate], position: usize) -> u32 {
-		assert!(position < mediates.len());
-
-		let init_len = mediates.iter().fold(0, |acc, m| acc + m.init_len());
-		mediates[0..position].iter().fold(init_len, |acc, m| acc + m.closing_len())
+/// Writes the head and then the tails of consecutive tokens, like a tuple of them.
+///
+/// Offsets of the tails are relative to the beginning of the written data.
+fn put_tokens<S: Sink>(tokens: &[Token], kinds: Kinds, sink: &mut S) -> Result<()> {
+	let mut tail_offset = tokens.iter().enumerate().map(|(i, t)| init_size(t, kinds.get(i))).sum::<usize>();
+	for (i, token) in tokens.iter().enumerate() {
+		put_head(token, kinds.get(i), tail_offset, sink)?;
+		tail_offset += closing_size(token, kinds.get(i));
 	}
 
-	fn init(&self, suffix_offset: u32) -> Vec<[u8; 32]> {
-		match *self {
-			Mediate::Raw(ref raw) => raw.clone(),
-			Mediate::FixedArray(ref nes) => {
-				nes.iter()
-					.enumerate()
-					.flat_map(|(i, m)| m.init(Mediate::offset_for(nes, i)))
-					.collect()
-			},
-			Mediate::Prefixed(_) | Mediate::Array(_) => {
-				vec![pad_u32(suffix_offset)]
-			}
-		}
+	for (i, token) in tokens.iter().enumerate() {
+		put_tail(token, kinds.get(i), sink)?;
 	}
 
-	fn closing(&self, offset: u32) -> Vec<[u8; 32]> {
-		match *self {
-			Mediate::Raw(_) => vec![],
-			Mediate::Prefixed(ref pre) => pre.clone(),
-			Mediate::FixedArray(ref nes) => {
-				// offset is not taken into account, cause it would be counted twice
-				// fixed array is just raw representations of similar consecutive items
-				nes.iter()
-					.enumerate()
-					.flat_map(|(i, m)| m.closing(Mediate::offset_for(nes, i)))
-					.collect()
-			},
-			Mediate::Array(ref nes) => {
-				// + 32 added to offset represents len of the array prepanded to closing
-				let prefix = vec![pad_u32(nes.len() as u32)].into_iter();
+	Ok(())
+}
+
+/// Writes the token in place if it's static, the offset of its tail otherwise.
+fn put_head<S: Sink>(token: &Token, kind: Option<&ParamType>, tail_offset: usize, sink: &mut S) -> Result<()> {
+	if is_dynamic(token, kind) {
+		return sink.put(&pad_u32(tail_offset as u32));
+	}
 
-				let inits = nes.iter()
-					.enumerate()
-					.flat_map(|(i, m)| m.init(Mediate::offset_for(nes, i)));
+	match *token {
+		Token::Address(ref address) => {
+			sink.put(&[0u8; 12])?;
+			sink.put(address.as_ref())
+		},
+		Token::FixedBytes(ref bytes) => put_padded(bytes, sink),
+		Token::Int(int) | Token::Uint(int) => sink.put(&<[u8; 32]>::from(int)),
+		Token::Bool(b) => sink.put(&pad_u32(b as u32)),
+		// static fixed array is encoded like a static tuple of its elements
+		Token::FixedArray(ref tokens) | Token::Tuple(ref tokens) => {
+			let kinds = Kinds::of_elements(kind);
+			for (i, token) in tokens.iter().enumerate() {
+				put_head(token, kinds.get(i), 0, sink)?;
+			}
+			Ok(())
+		},
+		Token::Bytes(_) | Token::String(_) | Token::Array(_) => unreachable!("dynamic tokens are handled above; qed"),
+	}
+}
 
-				let closings = nes.iter()
-					.enumerate()
-					.flat_map(|(i, m)| m.closing(offset + Mediate::offset_for(nes, i)));
+/// Writes the tail of a dynamic token, nothing for static tokens.
+fn put_tail<S: Sink>(token: &Token, kind: Option<&ParamType>, sink: &mut S) -> Result<()> {
+	if !is_dynamic(token, kind) {
+		return Ok(());
+	}
 
-				prefix.chain(inits).chain(closings).collect()
-			},
-		}
+	match *token {
+		Token::Bytes(ref bytes) => {
+			sink.put(&pad_u32(bytes.len() as u32))?;
+			put_padded(bytes, sink)
+		},
+		Token::String(ref s) => {
+			sink.put(&pad_u32(s.len() as u32))?;
+			put_padded(s.as_bytes(), sink)
+		},
+		// offsets of the elements are relative to the first of them, after the length
+		Token::Array(ref tokens) => {
+			sink.put(&pad_u32(tokens.len() as u32))?;
+			put_tokens(tokens, Kinds::of_elements(kind), sink)
+		},
+		// offsets of the tuple params are relative to the beginning of the tuple
+		Token::FixedArray(ref tokens) | Token::Tuple(ref tokens) => put_tokens(tokens, Kinds::of_elements(kind), sink),
+		_ => unreachable!("static tokens are handled above; qed"),
 	}
 }
 
 /// Encodes vector of tokens into ABI compliant vector of bytes.
+///
+/// Whether a value is dynamic is decided from the tokens, so an empty fixed array of
+/// a dynamic type, like `string[0]`, is encoded as static. Use `encode_with_types`
+/// to encode such values like Solidity and `decode` do.
 pub fn encode(tokens: &[Token]) -> Bytes {
-	let mediates: Vec<Mediate> = tokens.iter()
-		.map(encode_token)
-		.collect();
+	let mut result = Vec::with_capacity(encoded_size(tokens));
+	put_tokens(tokens, Kinds::Unknown, &mut result).expect("writing to Vec never fails; qed");
+	result
+}
 
-	let inits = mediates.iter()
-		.enumerate()
-		.flat_map(|(i, m)| m.init(Mediate::offset_for(&mediates, i)));
+/// Encodes vector of tokens of given types into ABI compliant vector of bytes.
+///
+/// Fails if the tokens don't match the types.
+pub fn encode_with_types(types: &[ParamType], tokens: &[Token]) -> Result<Bytes> {
+	Token::validate_all(tokens, types)?;
+	let kinds = Kinds::Each(types);
+	let mut result = Vec::with_capacity(tokens_size(tokens, kinds));
+	put_tokens(tokens, kinds, &mut result).expect("writing to Vec never fails; qed");
+	Ok(result)
+}
 
-	let closings = mediates.iter()
-		.enumerate()
-		.flat_map(|(i, m)| m.closing(Mediate::offset_for(&mediates, i)));
+/// Encodes vector of tokens like `encode`, writing the encoding directly to `writer`.
+///
+/// Nothing is buffered, so for large encodings it's worth wrapping unbuffered writers,
+/// like files or sockets, in `io::BufWriter`.
+#[cfg(feature = "std")]
+pub fn encode_to_writer<W: io::Write>(tokens: &[Token], writer: &mut W) -> Result<()> {
+	put_tokens(tokens, Kinds::Unknown, writer)
+}
+
+/// Encodes vector of tokens into `0x` prefixed hex string.
+///
+/// Like `encode_function_input`, fails if any token doesn't match its own type.
+pub fn encode_to_hex(tokens: &[Token]) -> Result<String> {
+	validate_own_types(tokens)?;
+	Ok(format!("0x{}", encode(tokens).to_hex::<String>()))
+}
+
+/// Returns the exact length of `encode(tokens)` without encoding the tokens.
+pub fn encoded_size(tokens: &[Token]) -> usize {
+	tokens_size(tokens, Kinds::Unknown)
+}
 
-	inits.chain(closings)
-		.flat_map(|item| item.to_vec())
-		.collect()
+/// Returns the exact length of `encode_with_types(types, tokens)`, for tokens of given types.
+pub fn encoded_size_with_types(types: &[ParamType], tokens: &[Token]) -> usize {
+	tokens_size(tokens, Kinds::Each(types))
 }
 
-fn encode_token(token: &Token) -> Mediate {
+fn tokens_size(tokens: &[Token], kinds: Kinds) -> usize {
+	tokens.iter().enumerate().map(|(i, t)| init_size(t, kinds.get(i)) + closing_size(t, kinds.get(i))).sum()
+}
+
+/// Size of the token's head: the token itself if it's static, the offset of its tail otherwise.
+fn init_size(token: &Token, kind: Option<&ParamType>) -> usize {
+	if is_dynamic(token, kind) {
+		return 32;
+	}
+
 	match *token {
-		Token::Address(ref address) => {
-			let mut padded = [0u8; 32];
-			padded[12..].copy_from_slice(address.as_ref());
-			Mediate::Raw(vec![padded])
+		Token::FixedBytes(ref bytes) => padded_size(bytes.len()),
+		Token::Address(_) | Token::Int(_) | Token::Uint(_) | Token::Bool(_) => 32,
+		Token::FixedArray(ref tokens) | Token::Tuple(ref tokens) => {
+			let kinds = Kinds::of_elements(kind);
+			tokens.iter().enumerate().map(|(i, t)| init_size(t, kinds.get(i))).sum()
 		},
-		Token::Bytes(ref bytes) => Mediate::Prefixed(pad_bytes(bytes)),
-		Token::String(ref s) => Mediate::Prefixed(pad_bytes(s.as_bytes())),
-		Token::FixedBytes(ref bytes) => Mediate::Raw(pad_fixed_bytes(bytes)),
-		Token::Int(int) => Mediate::Raw(vec![int.into()]),
-		Token::Uint(uint) => Mediate::Raw(vec![uint.into()]),
-		Token::Bool(b) => {
-			let mut value = [0u8; 32];
-			if b {
-				value[31] = 1;
+		Token::Bytes(_) | Token::String(_) | Token::Array(_) => unreachable!("dynamic tokens are handled above; qed"),
+	}
+}
+
+/// Size of the token's tail, zero for static tokens.
+fn closing_size(token: &Token, kind: Option<&ParamType>) -> usize {
+	if !is_dynamic(token, kind) {
+		return 0;
+	}
+
+	match *token {
+		Token::Bytes(ref bytes) => 32 + padded_size(bytes.len()),
+		Token::String(ref s) => 32 + padded_size(s.len()),
+		Token::Array(ref tokens) => 32 + tokens_size(tokens, Kinds::of_elements(kind)),
+		Token::FixedArray(ref tokens) | Token::Tuple(ref tokens) => tokens_size(tokens, Kinds::of_elements(kind)),
+		_ => unreachable!("static tokens are handled above; qed"),
+	}
+}
+
+/// Returns whether the token is dynamic, decided from its type when known.
+fn is_dynamic(token: &Token, kind: Option<&ParamType>) -> bool {
+	if let Some(kind) = kind {
+		return kind.is_dynamic();
+	}
+
+	match *token {
+		Token::Bytes(_) | Token::String(_) | Token::Array(_) => true,
+		Token::FixedArray(ref tokens) | Token::Tuple(ref tokens) => tokens.iter().any(|t| is_dynamic(t, None)),
+		_ => false,
+	}
+}
+
+fn padded_size(len: usize) -> usize {
+	len.div_ceil(32) * 32
+}
+
+/// Encodes function call data: the 4 byte selector followed by ABI encoded tokens.
+///
+/// Each token is validated against its own type, see `Token::validate`, so arrays mixing
+/// elements of different types are rejected. Use `encode_with_types` to validate the tokens
+/// against the declared params of the function.
+pub fn encode_function_input(selector: [u8; 4], tokens: &[Token]) -> Result<Bytes> {
+	validate_own_types(tokens)?;
+	let mut result = Vec::with_capacity(4 + encoded_size(tokens));
+	result.extend_from_slice(&selector);
+	put_tokens(tokens, Kinds::Unknown, &mut result).expect("writing to Vec never fails; qed");
+	Ok(result)
+}
+
+fn validate_own_types(tokens: &[Token]) -> Result<()> {
+	tokens.iter().try_for_each(|token| token.validate(&token.type_of()))
+}
+
+/// Encodes vector of tokens in the non-standard packed mode, like Solidity's `abi.encodePacked`.
+///
+/// Values are concatenated without padding or length prefixes. Since tokens don't carry
+/// their declared width, integers always take 32 bytes. Elements of arrays are padded to
+/// 32 bytes, as in Solidity. Tuples, nested arrays and arrays of dynamic types are ambiguous
+/// in packed mode and are rejected.
+pub fn encode_packed(tokens: &[Token]) -> Result<Bytes> {
+	let mut result = vec![];
+	for token in tokens {
+		encode_token_packed(token, &mut result)?;
+	}
+	Ok(result)
+}
+
+fn encode_token_packed(token: &Token, result: &mut Bytes) -> Result<()> {
+	match *token {
+		Token::Address(ref address) => result.extend_from_slice(address.as_ref()),
+		Token::Bytes(ref bytes) | Token::FixedBytes(ref bytes) => result.extend_from_slice(bytes),
+		Token::String(ref s) => result.extend_from_slice(s.as_bytes()),
+		Token::Int(int) | Token::Uint(int) => result.extend_from_slice(&<[u8; 32]>::from(int)),
+		Token::Bool(b) => result.push(b as u8),
+		Token::Array(ref tokens) | Token::FixedArray(ref tokens) => {
+			for token in tokens {
+				match *token {
+					Token::Array(_) | Token::FixedArray(_) | Token::Tuple(_) | Token::Bytes(_) | Token::String(_) => {
+						bail!("Cannot encode array of {} in packed mode", token.type_of());
+					},
+					_ => result.extend(encode(slice::from_ref(token))),
+				}
 			}
-			Mediate::Raw(vec![value])
 		},
-		Token::Array(ref tokens) => {
-			let mediates = tokens.iter()
-				.map(encode_token)
-				.collect();
+		Token::Tuple(_) => bail!("Cannot encode tuple in packed mode"),
+	}
 
-			Mediate::Array(mediates)
-		},
-		Token::FixedArray(ref tokens) => {
-			let mediates = tokens.iter()
-				.map(encode_token)
-				.collect();
+	Ok(())
+}
 
-			Mediate::FixedArray(mediates)
-		},
+/// Value which can be encoded in the packed mode according to its declared type,
+/// like Solidity's `abi.encodePacked`.
+pub trait Pack {
+	/// Appends the packed encoding of the value of type `kind` to `out`.
+	fn pack(&self, kind: &ParamType, out: &mut Bytes) -> Result<()>;
+}
+
+/// Integers take as many bytes as their declared width, e.g. a single byte for `uint8`,
+/// negative integers in two's complement. Fixed bytes take exactly their declared length.
+/// Elements of arrays are padded to 32 bytes. Tuples, nested arrays and arrays of dynamic
+/// types are rejected.
+impl Pack for Token {
+	fn pack(&self, kind: &ParamType, out: &mut Bytes) -> Result<()> {
+		self.validate(kind)?;
+		match (self, kind) {
+			(Token::Int(int), ParamType::Int(size)) | (Token::Uint(int), ParamType::Uint(size)) => {
+				out.extend_from_slice(&<[u8; 32]>::from(*int)[32 - size / 8..]);
+			},
+			(Token::FixedBytes(bytes), ParamType::FixedBytes(len)) if bytes.len() != *len => {
+				bail!("Expected {} bytes, got {}", len, bytes.len());
+			},
+			(Token::Array(tokens), ParamType::Array(inner)) |
+			(Token::FixedArray(tokens), ParamType::FixedArray(inner, _)) => {
+				if let ParamType::Array(_) | ParamType::FixedArray(..) | ParamType::Tuple(_) | ParamType::Bytes | ParamType::String = **inner {
+					bail!("Cannot encode array of {} in packed mode", inner);
+				}
+				for token in tokens {
+					out.extend(encode(slice::from_ref(token)));
+				}
+			},
+			(Token::Tuple(_), _) => bail!("Cannot encode tuple in packed mode"),
+			_ => encode_token_packed(self, out)?,
+		}
+
+		Ok(())
+	}
+}
+
+/// Encodes values of the declared types in the packed mode, like Solidity's `abi.encodePacked`.
+///
+/// Unlike `encode_packed`, integers take as many bytes as their declared width, see `Pack`.
+pub fn pack_tokens(pairs: &[(&ParamType, &Token)]) -> Result<Bytes> {
+	let mut result = vec![];
+	for (i, &(kind, token)) in pairs.iter().enumerate() {
+		token.pack(kind, &mut result).chain_err(|| format!("Cannot pack value {} as {}", i, kind))?;
+	}
+	Ok(result)
+}
+
+/// Builds an ABI encoding value by value, without building tokens first.
+///
+/// Values are encoded as they are pushed. Offsets of dynamic values depend on the size of
+/// all the heads, so they are only computed by `finish`.
+///
+/// ```
+/// # extern crate ethabi;
+/// # use ethabi::{AbiEncoder, Token, encode};
+/// # fn main() {
+/// let mut encoder = AbiEncoder::new();
+/// encoder.push_uint(42.into()).push_address([0u8; 20]);
+/// encoder.push_tuple(|inner| {
+///     inner.push_bool(true).push_string("foo");
+/// });
+///
+/// let expected = encode(&[
+///     Token::Uint(42.into()),
+///     Token::Address([0u8; 20].into()),
+///     Token::Tuple(vec![Token::Bool(true), Token::String("foo".into())]),
+/// ]);
+/// assert_eq!(encoder.finish().unwrap(), expected);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct AbiEncoder {
+	/// Encoding of static values, and placeholders for the offsets of dynamic values.
+	head: Bytes,
+	/// Tails of dynamic values, along with the position of their offset in the head.
+	tails: Vec<(usize, Bytes)>,
+	/// Number of pushed values.
+	len: usize,
+	error: Option<Error>,
+}
+
+impl AbiEncoder {
+	/// Creates encoder without values.
+	pub fn new() -> Self {
+		AbiEncoder::default()
+	}
+
+	/// Appends `uintN` value.
+	pub fn push_uint(&mut self, value: Uint) -> &mut Self {
+		self.push_static(&<[u8; 32]>::from(value))
+	}
+
+	/// Appends `intN` value, negative values in two's complement.
+	pub fn push_int(&mut self, value: Int) -> &mut Self {
+		self.push_static(&<[u8; 32]>::from(value))
+	}
+
+	/// Appends `address` value.
+	pub fn push_address<A: Into<Address>>(&mut self, address: A) -> &mut Self {
+		let mut head = [0u8; 32];
+		head[12..].copy_from_slice(address.into().as_ref());
+		self.push_static(&head)
+	}
+
+	/// Appends `bool` value.
+	pub fn push_bool(&mut self, value: bool) -> &mut Self {
+		self.push_static(&pad_u32(value as u32))
+	}
+
+	/// Appends `bytesN` value. Values longer than 32 bytes are an error, reported by `finish`.
+	pub fn push_fixed_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+		if bytes.len() > 32 {
+			return self.fail(format!("Fixed bytes can have at most 32 bytes, got {}", bytes.len()).into());
+		}
+		let mut head = [0u8; 32];
+		head[..bytes.len()].copy_from_slice(bytes);
+		self.push_static(&head)
+	}
+
+	/// Appends `bytes` value.
+	pub fn push_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+		let mut tail = Vec::with_capacity(32 + padded_size(bytes.len()));
+		tail.extend_from_slice(&pad_u32(bytes.len() as u32));
+		put_padded(bytes, &mut tail).expect("writing to Vec never fails; qed");
+		self.push_dynamic(tail)
+	}
+
+	/// Appends `string` value.
+	pub fn push_string(&mut self, s: &str) -> &mut Self {
+		self.push_bytes(s.as_bytes())
+	}
+
+	/// Appends value of any type.
+	pub fn push_token(&mut self, token: &Token) -> &mut Self {
+		if is_dynamic(token, None) {
+			let mut tail = Vec::with_capacity(closing_size(token, None));
+			put_tail(token, None, &mut tail).expect("writing to Vec never fails; qed");
+			self.push_dynamic(tail)
+		} else {
+			put_head(token, None, 0, &mut self.head).expect("writing to Vec never fails; qed");
+			self.len += 1;
+			self
+		}
+	}
+
+	/// Appends tuple with the values pushed by `build` to the encoder passed to it.
+	pub fn push_tuple<F>(&mut self, build: F) -> &mut Self where F: FnOnce(&mut AbiEncoder) {
+		let mut inner = AbiEncoder::new();
+		build(&mut inner);
+		self.push_nested(inner, false)
+	}
+
+	/// Appends `T[k]` value, with the elements pushed by `build`, see `push_tuple`.
+	pub fn push_fixed_array<F>(&mut self, build: F) -> &mut Self where F: FnOnce(&mut AbiEncoder) {
+		self.push_tuple(build)
+	}
+
+	/// Appends `T[]` value, with the elements pushed by `build`, see `push_tuple`.
+	pub fn push_array<F>(&mut self, build: F) -> &mut Self where F: FnOnce(&mut AbiEncoder) {
+		let mut inner = AbiEncoder::new();
+		build(&mut inner);
+		self.push_nested(inner, true)
+	}
+
+	/// Returns the encoding of the pushed values, or the first error.
+	pub fn finish(self) -> Result<Bytes> {
+		let mut result = vec![];
+		self.encode_into(&mut result)?;
+		Ok(result)
+	}
+
+	/// Appends the encoding of the pushed values to `out`, see `finish`.
+	pub fn encode_into(self, out: &mut Bytes) -> Result<()> {
+		if let Some(error) = self.error {
+			return Err(error);
+		}
+
+		out.reserve(self.encoded_size());
+		let start = out.len();
+		out.extend_from_slice(&self.head);
+
+		let mut tail_offset = self.head.len();
+		for &(position, ref tail) in &self.tails {
+			out[start + position..start + position + 32].copy_from_slice(&pad_u32(tail_offset as u32));
+			tail_offset += tail.len();
+		}
+
+		for (_, tail) in self.tails {
+			out.extend(tail);
+		}
+
+		Ok(())
+	}
+
+	fn encoded_size(&self) -> usize {
+		self.head.len() + self.tails.iter().map(|(_, tail)| tail.len()).sum::<usize>()
+	}
+
+	fn push_static(&mut self, head: &[u8]) -> &mut Self {
+		self.head.extend_from_slice(head);
+		self.len += 1;
+		self
+	}
+
+	fn push_dynamic(&mut self, tail: Bytes) -> &mut Self {
+		self.tails.push((self.head.len(), tail));
+		self.head.extend_from_slice(&[0u8; 32]);
+		self.len += 1;
+		self
+	}
+
+	/// Appends the values of `inner` as a tuple, or an array prefixed by its length.
+	fn push_nested(&mut self, inner: AbiEncoder, is_array: bool) -> &mut Self {
+		if !is_array && inner.tails.is_empty() && inner.error.is_none() {
+			self.head.extend_from_slice(&inner.head);
+			self.len += 1;
+			return self;
+		}
+
+		let mut encoded = Vec::with_capacity(32 + inner.encoded_size());
+		if is_array {
+			encoded.extend_from_slice(&pad_u32(inner.len as u32));
+		}
+		match inner.encode_into(&mut encoded) {
+			Ok(()) => self.push_dynamic(encoded),
+			Err(error) => self.fail(error),
+		}
+	}
+
+	/// Keeps the first error, to be returned by `finish`.
+	fn fail(&mut self, error: Error) -> &mut Self {
+		if self.error.is_none() {
+			self.error = Some(error);
+		}
+		self
 	}
 }
 
 #[cfg(test)]
 mod tests {
+	use tiny_keccak::keccak256;
 	use util::pad_u32;
-	use {Token, encode};
+	use std::io;
+	use hex::ToHex;
+	use {AbiEncoder, Token, ParamType, ErrorKind, encode, encode_packed, pack_tokens, encode_function_input, encode_to_hex, encode_to_writer, encoded_size, encode_with_types, decode_strict, decode_from_hex};
+	use super::encoded_size_with_types;
 
 	#[test]
 	fn encode_address() {
@@ -229,6 +603,7 @@ mod tests {
 		let fixed = Token::FixedArray(vec![array0, array1]);
 		let encoded = encode(&vec![fixed]);
 		let expected = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
 			0000000000000000000000000000000000000000000000000000000000000040
 			00000000000000000000000000000000000000000000000000000000000000a0
 			0000000000000000000000000000000000000000000000000000000000000002
@@ -577,5 +952,338 @@ mod tests {
 		").to_vec();
 		assert_eq!(encoded, expected);
 	}
-}
 
+	#[test]
+	fn encode_static_tuple() {
+		let address1 = Token::Address([0x11u8; 20].into());
+		let address2 = Token::Address([0x22u8; 20].into());
+		let encoded = encode(&vec![Token::Tuple(vec![address1, Token::Bool(true), address2])]);
+		let expected = hex!("
+			0000000000000000000000001111111111111111111111111111111111111111
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000002222222222222222222222222222222222222222
+		").to_vec();
+		assert_eq!(encoded, expected);
+	}
+
+	#[test]
+	fn encode_dynamic_tuple() {
+		let encoded = encode(&vec![
+			Token::Tuple(vec![
+				Token::Uint(1.into()),
+				Token::String("gavofyork".to_owned()),
+			]),
+			Token::Bool(true),
+		]);
+		let expected = hex!("
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000009
+			6761766f66796f726b0000000000000000000000000000000000000000000000
+		").to_vec();
+		assert_eq!(encoded, expected);
+	}
+
+	#[test]
+	fn encode_nested_tuples() {
+		let encoded = encode(&vec![
+			Token::Tuple(vec![
+				Token::Bytes(vec![0x12, 0x34]),
+				Token::Tuple(vec![
+					Token::Bool(false),
+					Token::String("foo".to_owned()),
+				]),
+			]),
+		]);
+		let expected = hex!("
+			0000000000000000000000000000000000000000000000000000000000000020
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000080
+			0000000000000000000000000000000000000000000000000000000000000002
+			1234000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000003
+			666f6f0000000000000000000000000000000000000000000000000000000000
+		").to_vec();
+		assert_eq!(encoded, expected);
+	}
+
+	#[test]
+	fn encode_packed_scalars() {
+		let encoded = encode_packed(&[
+			Token::Address([0x11u8; 20].into()),
+			Token::Bool(true),
+			Token::FixedBytes(vec![0x12, 0x34]),
+			Token::Uint(0x45.into()),
+			Token::Bytes(vec![0xab, 0xcd, 0xef]),
+			Token::String("hi".into()),
+		]).unwrap();
+		let expected = hex!("
+			1111111111111111111111111111111111111111
+			01
+			1234
+			0000000000000000000000000000000000000000000000000000000000000045
+			abcdef
+			6869
+		").to_vec();
+		assert_eq!(encoded, expected);
+	}
+
+	#[test]
+	fn encode_packed_arrays() {
+		let encoded = encode_packed(&[
+			Token::Array(vec![Token::Bool(true), Token::Bool(false)]),
+			Token::FixedArray(vec![Token::Address([0x22u8; 20].into())]),
+		]).unwrap();
+		let expected = hex!("
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000002222222222222222222222222222222222222222
+		").to_vec();
+		assert_eq!(encoded, expected);
+		assert_eq!(encode_packed(&[Token::Array(vec![])]).unwrap(), Vec::<u8>::new());
+	}
+
+	#[test]
+	fn encode_packed_keccak() {
+		// keccak256(abi.encodePacked("hello"))
+		let hash = keccak256(&encode_packed(&[Token::String("hello".into())]).unwrap());
+		assert_eq!(hash, hex!("1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8"));
+
+		// keccak256(abi.encodePacked("hel", "lo")) collides with the above
+		let hash = keccak256(&encode_packed(&[Token::String("hel".into()), Token::Bytes(b"lo".to_vec())]).unwrap());
+		assert_eq!(hash, hex!("1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8"));
+	}
+
+	#[test]
+	fn pack_tokens_with_declared_types() {
+		// abi.encodePacked(int16(-1), bytes1(0x42), uint16(0x03), string("Hello, world!")), from the Solidity docs
+		let packed = pack_tokens(&[
+			(&ParamType::Int(16), &Token::Int(!::Uint::zero())),
+			(&ParamType::FixedBytes(1), &Token::FixedBytes(vec![0x42])),
+			(&ParamType::Uint(16), &Token::Uint(3.into())),
+			(&ParamType::String, &Token::String("Hello, world!".into())),
+		]).unwrap();
+		assert_eq!(packed, hex!("ffff42000348656c6c6f2c20776f726c6421").to_vec());
+
+		// abi.encodePacked(uint8(1), address(0x1111...), bool(true), uint8[](2, 3))
+		let kind = ParamType::Array(Box::new(ParamType::Uint(8)));
+		let packed = pack_tokens(&[
+			(&ParamType::Uint(8), &Token::Uint(1.into())),
+			(&ParamType::Address, &Token::Address([0x11u8; 20].into())),
+			(&ParamType::Bool, &Token::Bool(true)),
+			(&kind, &Token::Array(vec![Token::Uint(2.into()), Token::Uint(3.into())])),
+		]).unwrap();
+		let expected = hex!("
+			01
+			1111111111111111111111111111111111111111
+			01
+			0000000000000000000000000000000000000000000000000000000000000002
+			0000000000000000000000000000000000000000000000000000000000000003
+		").to_vec();
+		assert_eq!(packed, expected);
+	}
+
+	#[test]
+	fn pack_tokens_unsupported() {
+		let tuple = ParamType::Tuple(tuple_components![Box::new(ParamType::Bool)]);
+		assert!(pack_tokens(&[(&tuple, &Token::Tuple(vec![Token::Bool(true)]))]).is_err());
+
+		let strings = ParamType::Array(Box::new(ParamType::String));
+		assert!(pack_tokens(&[(&strings, &Token::Array(vec![]))]).is_err());
+
+		// value doesn't fit the declared type
+		assert!(pack_tokens(&[(&ParamType::Uint(8), &Token::Uint(256.into()))]).is_err());
+		assert!(pack_tokens(&[(&ParamType::FixedBytes(2), &Token::FixedBytes(vec![1]))]).is_err());
+	}
+
+	#[test]
+	fn encode_packed_unsupported() {
+		assert!(encode_packed(&[Token::Tuple(vec![Token::Bool(true)])]).is_err());
+		assert!(encode_packed(&[Token::Array(vec![Token::Array(vec![])])]).is_err());
+		assert!(encode_packed(&[Token::FixedArray(vec![Token::String("a".into())])]).is_err());
+		assert_eq!(
+			format!("{}", encode_packed(&[Token::Array(vec![Token::Bytes(vec![])])]).unwrap_err()),
+			"Cannot encode array of bytes in packed mode"
+		);
+	}
+
+	#[test]
+	fn encode_function_input_prepends_selector() {
+		let encoded = encode_function_input(hex!("cdcd77c0"), &[Token::Uint(0x45.into()), Token::Bool(true)]).unwrap();
+		let expected = hex!("
+			cdcd77c0
+			0000000000000000000000000000000000000000000000000000000000000045
+			0000000000000000000000000000000000000000000000000000000000000001
+		").to_vec();
+		assert_eq!(encoded, expected);
+		assert_eq!(encode_function_input(hex!("cdcd77c0"), &[]).unwrap(), hex!("cdcd77c0").to_vec());
+
+		let mixed = Token::Array(vec![Token::Uint(0x45.into()), Token::Bool(true)]);
+		assert_eq!(
+			format!("{}", encode_function_input(hex!("cdcd77c0"), &[mixed]).unwrap_err()),
+			"token at array index 1: expected uint256, got bool"
+		);
+	}
+
+	#[test]
+	fn encode_with_types_empty_fixed_array_of_dynamic_type() {
+		let types = [ParamType::FixedArray(Box::new(ParamType::String), 0), ParamType::Bool, ParamType::FixedArray(Box::new(ParamType::Uint(256)), 0)];
+		let tokens = [Token::FixedArray(vec![]), Token::Bool(true), Token::FixedArray(vec![])];
+		let encoded = encode_with_types(&types, &tokens).unwrap();
+		// `string[0]` is dynamic, its empty tail follows the head, `uint256[0]` takes no space
+		let expected = hex!("
+			0000000000000000000000000000000000000000000000000000000000000040
+			0000000000000000000000000000000000000000000000000000000000000001
+		").to_vec();
+		assert_eq!(encoded, expected);
+		assert_eq!(encoded_size_with_types(&types, &tokens), encoded.len());
+		assert_eq!(decode_strict(&types, &encoded).unwrap(), tokens.to_vec());
+
+		// without the types, an empty fixed array is assumed static
+		assert_eq!(encode(&tokens), pad_u32(1).to_vec());
+		assert!(encode_with_types(&types[..1], &[Token::Bool(true)]).is_err());
+	}
+
+	#[test]
+	fn encoded_size_matches_encoding() {
+		let address = Token::Address([0x11u8; 20].into());
+		let string = Token::String("gavofyork".into());
+		let long_bytes = Token::Bytes(vec![0x12; 70]);
+		let dynamic_tuple = Token::Tuple(vec![address.clone(), string.clone(), Token::Array(vec![long_bytes.clone()])]);
+		let cases = vec![
+			vec![],
+			vec![address.clone()],
+			vec![Token::FixedBytes(vec![]), Token::FixedBytes(vec![1; 33]), Token::Bool(true)],
+			vec![Token::Bytes(vec![]), string.clone(), long_bytes.clone()],
+			vec![Token::Array(vec![]), Token::Array(vec![address.clone(), address.clone()])],
+			vec![Token::FixedArray(vec![string.clone(), long_bytes.clone()]), Token::Uint(1.into())],
+			vec![Token::Array(vec![Token::FixedArray(vec![address.clone(), Token::Array(vec![address.clone()])])])],
+			vec![Token::Tuple(vec![]), Token::Tuple(vec![address.clone(), Token::Int(2.into())])],
+			vec![dynamic_tuple.clone(), Token::Tuple(vec![dynamic_tuple.clone(), Token::Tuple(vec![Token::Bool(false)])])],
+			vec![Token::Array(vec![dynamic_tuple.clone(), dynamic_tuple])],
+		];
+
+		for tokens in cases {
+			assert_eq!(encoded_size(&tokens), encode(&tokens).len(), "{:?}", tokens);
+		}
+	}
+
+	#[test]
+	fn encode_swap_input_to_hex() {
+		// input of `swapExactTokensForTokens(uint256,uint256,address[],address,uint256)` of the Uniswap V2
+		// router, in mainnet transaction 0xd1b449d8b1552156957309bffb988924569de34fbf21b51e7af31070cc80fe9a
+		let input = "0x38ed1739\
+			00000000000000000000000000000000000000000001a717cc0a3e4f84c00000\
+			0000000000000000000000000000000000000000000000000000000002835684\
+			00000000000000000000000000000000000000000000000000000000000000a0\
+			000000000000000000000000201f129111c60401630932d9f9811bd5b5fff34e\
+			000000000000000000000000000000000000000000000000000000006227723d\
+			0000000000000000000000000000000000000000000000000000000000000002\
+			00000000000000000000000095ad61b0a150d79219dcf64e1e6cc01f0b64c4ce\
+			000000000000000000000000dac17f958d2ee523a2206206994597c13d831ec7";
+		let tokens = [
+			// 1,998,000 SHIB for at least 42.161796 USDT
+			Token::Uint(::Uint::from(1_998_000u64) * ::Uint::exp10(18)),
+			Token::Uint(42_161_796.into()),
+			Token::Array(vec![
+				Token::Address("95ad61b0a150d79219dcf64e1e6cc01f0b64c4ce".parse().unwrap()),
+				Token::Address("dac17f958d2ee523a2206206994597c13d831ec7".parse().unwrap()),
+			]),
+			Token::Address("201f129111c60401630932d9f9811bd5b5fff34e".parse().unwrap()),
+			Token::Uint(0x6227723d.into()),
+		];
+		assert_eq!(encode_to_hex(&tokens).unwrap(), format!("0x{}", &input[10..]));
+		assert_eq!(encode_function_input(hex!("38ed1739"), &tokens).unwrap().to_hex::<String>(), &input[2..]);
+		assert_eq!(encode_to_hex(&[]).unwrap(), "0x");
+
+		let types: Vec<ParamType> = ["uint256", "uint256", "address[]", "address", "uint256"].iter().map(|t| t.parse().unwrap()).collect();
+		assert_eq!(decode_from_hex(&types, &input[10..]).unwrap(), tokens.to_vec());
+
+		let mixed = [Token::Array(vec![tokens[3].clone(), tokens[4].clone()])];
+		assert!(encode_to_hex(&mixed).is_err());
+	}
+
+	#[test]
+	fn encode_to_writer_matches_encode() {
+		let tokens = [
+			Token::Uint(1.into()),
+			Token::String("gavofyork".to_owned()),
+			Token::Array(vec![Token::Tuple(vec![Token::Bytes(vec![1; 33]), Token::Bool(true)])]),
+			Token::FixedArray(vec![Token::FixedBytes(vec![0x12, 0x34]), Token::FixedBytes(vec![0x56, 0x78])]),
+		];
+		let mut written = vec![];
+		encode_to_writer(&tokens, &mut written).unwrap();
+		assert_eq!(written, encode(&tokens));
+	}
+
+	#[test]
+	fn encode_to_writer_fails_with_writer() {
+		let tokens = [Token::String("gavofyork".to_owned())];
+		let mut buffer = [0u8; 64];
+		let error = encode_to_writer(&tokens, &mut &mut buffer[..]).unwrap_err();
+		match *error.kind() {
+			ErrorKind::Io(ref err) => assert_eq!(err.kind(), io::ErrorKind::WriteZero),
+			ref kind => panic!("unexpected error: {:?}", kind),
+		}
+	}
+
+	#[test]
+	fn abi_encoder() {
+		let mut encoder = AbiEncoder::new();
+		encoder
+			.push_uint(42.into())
+			.push_int(!::Int::zero())
+			.push_address([0x11u8; 20])
+			.push_fixed_bytes(b"abc")
+			.push_bytes(&[0x22; 40])
+			.push_tuple(|inner| {
+				inner.push_bool(true).push_fixed_array(|array| {
+					array.push_uint(1.into()).push_uint(2.into());
+				});
+			})
+			.push_array(|array| {
+				for s in &["foo", "bar"] {
+					array.push_tuple(|inner| {
+						inner.push_string(s).push_array(|_| {});
+					});
+				}
+			})
+			.push_token(&Token::Array(vec![Token::Bool(false)]))
+			.push_array(|_| {});
+
+		let tokens = [
+			Token::Uint(42.into()),
+			Token::Int(!::Int::zero()),
+			Token::Address([0x11u8; 20].into()),
+			Token::FixedBytes(b"abc".to_vec()),
+			Token::Bytes(vec![0x22; 40]),
+			Token::Tuple(vec![Token::Bool(true), Token::FixedArray(vec![Token::Uint(1.into()), Token::Uint(2.into())])]),
+			Token::Array(vec![
+				Token::Tuple(vec![Token::String("foo".into()), Token::Array(vec![])]),
+				Token::Tuple(vec![Token::String("bar".into()), Token::Array(vec![])]),
+			]),
+			Token::Array(vec![Token::Bool(false)]),
+			Token::Array(vec![]),
+		];
+		assert_eq!(encoder.finish().unwrap(), encode(&tokens));
+
+		let mut out = vec![0xff];
+		let mut encoder = AbiEncoder::new();
+		encoder.push_string("");
+		encoder.encode_into(&mut out).unwrap();
+		assert_eq!(out[0], 0xff);
+		assert_eq!(out[1..], encode(&[Token::String("".into())])[..]);
+		assert_eq!(AbiEncoder::new().finish().unwrap(), Vec::<u8>::new());
+
+		let mut encoder = AbiEncoder::new();
+		encoder.push_bool(true).push_tuple(|inner| {
+			inner.push_fixed_bytes(&[0u8; 33]);
+		});
+		let err = encoder.finish().unwrap_err();
+		assert_eq!(format!("{}", err), "Fixed bytes can have at most 32 bytes, got 33");
+	}
+}
@@ -1,15 +1,22 @@
 #![allow(unknown_lints)]
 #![allow(missing_docs)]
 
-use std::{num, string};
+#[cfg(feature = "std")]
+use std::{io, num, string};
+#[cfg(feature = "std")]
 use {serde_json, hex};
 
+#[cfg(not(feature = "std"))]
+pub use self::no_std::*;
+
+#[cfg(feature = "std")]
 error_chain! {
 	foreign_links {
 		SerdeJson(serde_json::Error);
 		ParseInt(num::ParseIntError);
 		Utf8(string::FromUtf8Error);
 		Hex(hex::FromHexError);
+		Io(io::Error);
 	}
 
 	errors {
@@ -18,9 +25,223 @@ error_chain! {
 			display("Invalid name `{}`", name),
 		}
 
+		InvalidNameAt(name: String, position: usize, type_name: String) {
+			description("Invalid name"),
+			display("Invalid name `{}` at position {} of `{}`", name, position, type_name),
+		}
+
+		InvalidTokenType(expected: &'static str, got: &'static str) {
+			description("Invalid token type"),
+			display("Invalid token type, expected `{}` but got `{}`", expected, got),
+		}
+
+		TokenMismatch(location: String, expected: String, got: String) {
+			description("Token does not match param type"),
+			display("{}: expected {}, got {}", location, expected, got),
+		}
+
 		InvalidData {
 			description("Invalid data"),
 			display("Invalid data"),
 		}
+
+		MaxDepthExceeded(depth: usize) {
+			description("Maximum nesting depth exceeded"),
+			display("Maximum nesting depth exceeded at depth {}", depth),
+		}
+
+		ArrayLengthLimitExceeded(claimed: usize, limit: usize) {
+			description("Array length limit exceeded"),
+			display("Array length {} exceeds the limit of {}", claimed, limit),
+		}
+
+		InvalidDataAt(offset: usize, reason: &'static str) {
+			description("Invalid data"),
+			display("Invalid data at byte offset {}: {}", offset, reason),
+		}
+	}
+}
+
+/// Error types mirroring the ones generated by `error_chain!`, for builds without `std`.
+#[cfg(not(feature = "std"))]
+#[macro_use]
+mod no_std {
+	use core::{fmt, num};
+	use alloc::string;
+	use {serde_json, hex};
+	use no_std_prelude::*;
+
+	/// Subset of `std::error::Error` available without the standard library.
+	pub trait StdError: fmt::Debug + fmt::Display {
+		/// Returns the error which caused this one.
+		fn source(&self) -> Option<&(dyn StdError + 'static)> {
+			None
+		}
+	}
+
+	/// The kind of an error.
+	#[derive(Debug)]
+	pub enum ErrorKind {
+		Msg(String),
+		SerdeJson(serde_json::Error),
+		ParseInt(num::ParseIntError),
+		Utf8(string::FromUtf8Error),
+		Hex(hex::FromHexError),
+		InvalidName(String),
+		InvalidNameAt(String, usize, String),
+		InvalidTokenType(&'static str, &'static str),
+		TokenMismatch(String, String, String),
+		InvalidData,
+		MaxDepthExceeded(usize),
+		ArrayLengthLimitExceeded(usize, usize),
+		InvalidDataAt(usize, &'static str),
+	}
+
+	impl fmt::Display for ErrorKind {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			match *self {
+				ErrorKind::Msg(ref message) => write!(f, "{}", message),
+				ErrorKind::SerdeJson(ref err) => write!(f, "{}", err),
+				ErrorKind::ParseInt(ref err) => write!(f, "{}", err),
+				ErrorKind::Utf8(ref err) => write!(f, "{}", err),
+				ErrorKind::Hex(ref err) => write!(f, "{}", err),
+				ErrorKind::InvalidName(ref name) => write!(f, "Invalid name `{}`", name),
+				ErrorKind::InvalidNameAt(ref name, position, ref type_name) =>
+					write!(f, "Invalid name `{}` at position {} of `{}`", name, position, type_name),
+				ErrorKind::InvalidTokenType(expected, got) =>
+					write!(f, "Invalid token type, expected `{}` but got `{}`", expected, got),
+				ErrorKind::TokenMismatch(ref location, ref expected, ref got) =>
+					write!(f, "{}: expected {}, got {}", location, expected, got),
+				ErrorKind::InvalidData => write!(f, "Invalid data"),
+				ErrorKind::MaxDepthExceeded(depth) => write!(f, "Maximum nesting depth exceeded at depth {}", depth),
+				ErrorKind::ArrayLengthLimitExceeded(claimed, limit) =>
+					write!(f, "Array length {} exceeds the limit of {}", claimed, limit),
+				ErrorKind::InvalidDataAt(offset, reason) => write!(f, "Invalid data at byte offset {}: {}", offset, reason),
+			}
+		}
+	}
+
+	impl<'a> From<&'a str> for ErrorKind {
+		fn from(message: &'a str) -> Self {
+			ErrorKind::Msg(message.into())
+		}
+	}
+
+	impl From<String> for ErrorKind {
+		fn from(message: String) -> Self {
+			ErrorKind::Msg(message)
+		}
+	}
+
+	/// The error, along with the error which caused it.
+	#[derive(Debug)]
+	pub struct Error(pub ErrorKind, pub Option<Box<Error>>);
+
+	impl Error {
+		/// Constructs an error from a kind.
+		pub fn from_kind(kind: ErrorKind) -> Self {
+			Error(kind, None)
+		}
+
+		/// Returns the kind of the error.
+		pub fn kind(&self) -> &ErrorKind {
+			&self.0
+		}
+
+		/// Iterates over the error and the errors which caused it.
+		pub fn iter(&self) -> Iter {
+			Iter(Some(self))
+		}
+	}
+
+	impl fmt::Display for Error {
+		fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+			self.0.fmt(f)
+		}
+	}
+
+	impl StdError for Error {
+		fn source(&self) -> Option<&(dyn StdError + 'static)> {
+			self.1.as_ref().map(|err| &**err as &(dyn StdError + 'static))
+		}
+	}
+
+	impl From<ErrorKind> for Error {
+		fn from(kind: ErrorKind) -> Self {
+			Error::from_kind(kind)
+		}
+	}
+
+	impl<'a> From<&'a str> for Error {
+		fn from(message: &'a str) -> Self {
+			Error::from_kind(message.into())
+		}
+	}
+
+	impl From<String> for Error {
+		fn from(message: String) -> Self {
+			Error::from_kind(message.into())
+		}
+	}
+
+	macro_rules! foreign_links {
+		($($variant: ident($error: ty);)*) => {$(
+			impl From<$error> for ErrorKind {
+				fn from(err: $error) -> Self {
+					ErrorKind::$variant(err)
+				}
+			}
+
+			impl From<$error> for Error {
+				fn from(err: $error) -> Self {
+					Error::from_kind(ErrorKind::$variant(err))
+				}
+			}
+		)*}
+	}
+
+	foreign_links! {
+		SerdeJson(serde_json::Error);
+		ParseInt(num::ParseIntError);
+		Utf8(string::FromUtf8Error);
+		Hex(hex::FromHexError);
+	}
+
+	/// Iterator over the error and the errors which caused it.
+	pub struct Iter<'a>(Option<&'a (dyn StdError + 'static)>);
+
+	impl<'a> Iterator for Iter<'a> {
+		type Item = &'a (dyn StdError + 'static);
+
+		fn next(&mut self) -> Option<Self::Item> {
+			let current = self.0.take();
+			self.0 = current.and_then(|err| err.source());
+			current
+		}
+	}
+
+	/// Result with the crate error.
+	pub type Result<T> = ::core::result::Result<T, Error>;
+
+	/// Chains errors, like `error_chain`'s `ResultExt`.
+	pub trait ResultExt<T> {
+		/// Wraps the error into an error of the kind returned by `callback`.
+		fn chain_err<F, EK>(self, callback: F) -> Result<T> where F: FnOnce() -> EK, EK: Into<ErrorKind>;
+	}
+
+	impl<T, E> ResultExt<T> for ::core::result::Result<T, E> where E: Into<Error> {
+		fn chain_err<F, EK>(self, callback: F) -> Result<T> where F: FnOnce() -> EK, EK: Into<ErrorKind> {
+			self.map_err(|err| Error(callback().into(), Some(Box::new(err.into()))))
+		}
+	}
+
+	/// Returns early with an error, like `error_chain`'s `bail!`.
+	macro_rules! bail {
+		($e:expr) => {
+			return Err($e.into())
+		};
+		($fmt:expr, $($arg:tt)+) => {
+			return Err(format!($fmt, $($arg)+).into())
+		};
 	}
 }
@@ -1,31 +1,38 @@
 //! Contract event.
 
-use std::collections::HashMap;
+use core::slice;
 use tiny_keccak::keccak256;
 use signature::long_signature;
+use human_readable;
+use param_type::Writer;
 use {
 	Log, Hash, RawLog, LogParam, RawTopicFilter, TopicFilter,
-	Topic, ParamType, EventParam, encode, decode, Token,
-	Result, ErrorKind
+	Topic, ParamType, EventParam, encode, encode_with_types, decode, Token,
+	Bytes, Result, ResultExt, ErrorKind, DecodedParams
 };
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Contract event.
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Event {
 	/// Event name.
 	pub name: String,
 	/// Event input.
 	pub inputs: Vec<EventParam>,
 	/// If anonymous, event cannot be found using `from` filter.
+	#[serde(default)]
 	pub anonymous: bool,
 }
 
 impl Event {
-	/// Returns names of all params.
-	fn params_names(&self) -> Vec<String> {
-		self.inputs.iter()
-			.map(|p| p.name.clone())
-			.collect()
+	/// Parses event from human readable signature, e.g.
+	/// `Transfer(address indexed from, address indexed to, uint256 value)`.
+	///
+	/// Param names are optional. Anonymous events are marked with the `anonymous` keyword,
+	/// either before the name or after the params.
+	pub fn from_str_signature(signature: &str) -> Result<Event> {
+		human_readable::parse_event(signature)
 	}
 
 	/// Returns types of all params.
@@ -43,25 +50,36 @@ impl Event {
 			.collect()
 	}
 
-	/// Event signature
+	/// Returns the canonical signature of the event, e.g. `Transfer(address,address,uint256)`,
+	/// which is hashed to compute the topic. Tuples are written as `(uint256,bool)`, param
+	/// names and `indexed` are not part of it.
+	pub fn canonical_signature(&self) -> String {
+		let types = self.inputs.iter()
+			.map(|p| Writer::write(&p.kind))
+			.collect::<Vec<String>>()
+			.join(",");
+
+		format!("{}({})", self.name, types)
+	}
+
+	/// Returns the keccak256 hash of the `canonical_signature`.
 	pub fn signature(&self) -> Hash {
 		long_signature(&self.name, &self.param_types())
 	}
 
+	/// Returns the topic identifying the event, the keccak256 hash of its signature.
+	/// It's the first topic of the logs of non-anonymous events.
+	pub fn topic(&self) -> Hash {
+		self.signature()
+	}
+
 	/// Creates topic filter
 	pub fn filter(&self, raw: RawTopicFilter) -> Result<TopicFilter> {
 		fn convert_token(token: Token, kind: &ParamType) -> Result<Hash> {
 			if !token.type_check(kind) {
 				return Err(ErrorKind::InvalidData.into());
 			}
-			let encoded = encode(&[token]);
-			if encoded.len() == 32 {
-				let mut data = [0u8; 32];
-				data.copy_from_slice(&encoded);
-				Ok(data.into())
-			} else {
-				Ok(keccak256(&encoded).into())
-			}
+			Ok(encode_topic(&token))
 		}
 
 		fn convert_topic(topic: Topic<Token>, kind: Option<&ParamType>) -> Result<Topic<Hash>> {
@@ -104,7 +122,7 @@ impl Event {
 	}
 
 	// Converts param types for indexed parameters to bytes32 where appropriate
-	// This applies to strings, arrays and bytes to follow the encoding of
+	// This applies to strings, arrays, bytes and tuples to follow the encoding of
 	// these indexed param types according to
 	// https://solidity.readthedocs.io/en/develop/abi-spec.html#encoding-of-indexed-event-parameters
 	fn convert_topic_param_type(&self, kind: &ParamType) -> ParamType {
@@ -112,12 +130,105 @@ impl Event {
 			ParamType::String
 			| ParamType::Bytes
 			| ParamType::Array(_)
-			| ParamType::FixedArray(_, _) => ParamType::FixedBytes(32),
+			| ParamType::FixedArray(_, _)
+			| ParamType::Tuple(_) => ParamType::FixedBytes(32),
 			_ => kind.clone()
 		}
 	}
 
+	/// Encodes named params into log topics and data, the inverse of `decode_log`.
+	///
+	/// The topics start with the event topic unless the event is anonymous, followed by
+	/// the indexed params. Indexed params of type `string`, `bytes`, arrays and tuples are
+	/// hashed. The remaining params are ABI encoded into the data.
+	pub fn encode_log(&self, named_tokens: &[(String, Token)]) -> Result<(Vec<Hash>, Bytes)> {
+		if let Some((name, _)) = named_tokens.iter().find(|(name, _)| !self.inputs.iter().any(|p| p.name == *name)) {
+			bail!("Unknown param `{}` of event `{}`", name, self.name);
+		}
+
+		let tokens = self.inputs.iter()
+			.map(|p| match named_tokens.iter().find(|(name, _)| *name == p.name) {
+				Some((_, token)) => Ok(token.clone()),
+				None => Err(format!("Missing param `{}` of event `{}`", p.name, self.name).into()),
+			})
+			.collect::<Result<Vec<Token>>>()?;
+		Token::validate_all(&tokens, &self.param_types())?;
+
+		let mut topics = Vec::with_capacity(self.topic_count());
+		if !self.anonymous {
+			topics.push(self.topic());
+		}
+
+		let mut data_types = vec![];
+		let mut data_tokens = vec![];
+		for (param, token) in self.inputs.iter().zip(tokens) {
+			if param.indexed {
+				topics.push(encode_topic(&token));
+			} else {
+				data_types.push(param.kind.clone());
+				data_tokens.push(token);
+			}
+		}
+
+		Ok((topics, encode_with_types(&data_types, &data_tokens)?))
+	}
+
+	/// Builds the `topics` of an `eth_getLogs` filter for the event from named indexed params.
+	///
+	/// The first topic is the event topic unless the event is anonymous, followed by one
+	/// topic per indexed param: `None` matches any value and is used for params given as
+	/// `None` or not given at all. Values are encoded like in `encode_log`, so params of type
+	/// `string`, `bytes`, arrays and tuples are hashed.
+	pub fn filter_topics(&self, indexed_params: &[(String, Option<Token>)]) -> Result<Vec<Option<[u8; 32]>>> {
+		if let Some((name, _)) = indexed_params.iter().find(|(name, _)| !self.inputs.iter().any(|p| p.indexed && p.name == *name)) {
+			bail!("Unknown indexed param `{}` of event `{}`", name, self.name);
+		}
+
+		let mut topics = Vec::with_capacity(self.topic_count());
+		if !self.anonymous {
+			topics.push(Some(self.topic().into()));
+		}
+
+		for param in self.inputs.iter().filter(|p| p.indexed) {
+			let token = indexed_params.iter()
+				.find(|(name, _)| *name == param.name)
+				.and_then(|(_, token)| token.as_ref());
+			let topic = match token {
+				Some(token) => {
+					token.validate(&param.kind).chain_err(|| format!("Invalid param `{}` of event `{}`", param.name, self.name))?;
+					Some(encode_topic(token).into())
+				},
+				None => None,
+			};
+			topics.push(topic);
+		}
+
+		Ok(topics)
+	}
+
+	/// Returns the number of topics of the event logs: one per indexed param, plus the
+	/// event topic unless the event is anonymous.
+	pub fn topic_count(&self) -> usize {
+		let indexed = self.inputs.iter().filter(|p| p.indexed).count();
+		if self.anonymous { indexed } else { indexed + 1 }
+	}
+
+	/// Decodes log topics and data into named params, in the order of the event inputs.
+	///
+	/// Indexed params of type `string`, `bytes`, arrays and tuples are only present in the topics
+	/// as the keccak256 hash of their value. Their value can't be recovered, and the raw 32 byte
+	/// hash is returned as `Token::FixedBytes` instead.
+	pub fn decode_log(&self, topics: &[Hash], data: &[u8]) -> Result<DecodedParams> {
+		let log = self.parse_log(RawLog {
+			topics: topics.to_vec(),
+			data: data.to_vec(),
+		})?;
+		Ok(log.into())
+	}
+
 	/// Parses `RawLog` and retrieves all log params from it.
+	///
+	/// See `decode_log` for how indexed params of dynamic types are returned.
 	pub fn parse_log(&self, log: RawLog) -> Result<Log> {
 		let topics = log.topics;
 		let data = log.data;
@@ -153,29 +264,20 @@ impl Event {
 			return Err(ErrorKind::InvalidData.into());
 		}
 
-		let topics_named_tokens = topic_params.into_iter()
-			.map(|p| p.name)
-			.zip(topic_tokens.into_iter());
-
 		let data_types = data_params.iter()
 			.map(|p| p.kind.clone())
 			.collect::<Vec<ParamType>>();
 
 		let data_tokens = try!(decode(&data_types, &data));
 
-		let data_named_tokens = data_params.into_iter()
-			.map(|p| p.name)
-			.zip(data_tokens.into_iter());
-
-		let named_tokens = topics_named_tokens
-			.chain(data_named_tokens)
-			.collect::<HashMap<String, Token>>();
-
-		let decoded_params = self.params_names()
-			.into_iter()
-			.map(|name| LogParam {
-				name: name.clone(),
-				value: named_tokens[&name].clone()
+		// params are matched with the decoded tokens by position, since names may repeat or be empty
+		let mut topic_tokens = topic_tokens.into_iter();
+		let mut data_tokens = data_tokens.into_iter();
+		let decoded_params = self.inputs.iter()
+			.map(|p| LogParam {
+				name: p.name.clone(),
+				value: if p.indexed { topic_tokens.next() } else { data_tokens.next() }
+					.expect("one token is decoded per param; qed"),
 			})
 			.collect();
 
@@ -187,13 +289,54 @@ impl Event {
 	}
 }
 
+/// Encodes a token as a log topic. Value types are stored in place, other types are hashed
+/// as described in the
+/// [ABI spec](https://solidity.readthedocs.io/en/develop/abi-spec.html#encoding-of-indexed-event-parameters).
+fn encode_topic(token: &Token) -> Hash {
+	fn encode_in_place(token: &Token, out: &mut Vec<u8>) {
+		match *token {
+			Token::Bytes(ref bytes) => pad_in_place(bytes, out),
+			Token::String(ref s) => pad_in_place(s.as_bytes(), out),
+			Token::FixedArray(ref tokens) | Token::Array(ref tokens) | Token::Tuple(ref tokens) => {
+				for token in tokens {
+					encode_in_place(token, out);
+				}
+			},
+			_ => out.extend(encode(slice::from_ref(token))),
+		}
+	}
+
+	fn pad_in_place(bytes: &[u8], out: &mut Vec<u8>) {
+		let padded_len = out.len() + bytes.len().div_ceil(32) * 32;
+		out.extend_from_slice(bytes);
+		out.resize(padded_len, 0);
+	}
+
+	match *token {
+		Token::Bytes(ref bytes) => keccak256(bytes).into(),
+		Token::String(ref s) => keccak256(s.as_bytes()).into(),
+		Token::FixedArray(_) | Token::Array(_) | Token::Tuple(_) => {
+			let mut preimage = vec![];
+			encode_in_place(token, &mut preimage);
+			keccak256(&preimage).into()
+		},
+		_ => {
+			let mut topic = [0u8; 32];
+			topic.copy_from_slice(&encode(slice::from_ref(token)));
+			topic.into()
+		},
+	}
+}
+
 #[cfg(test)]
 mod tests {
+	use serde_json;
 	use hex::FromHex;
 	use token::Token;
 	use signature::long_signature;
 	use log::{RawLog, Log};
-	use {EventParam, ParamType, Event, LogParam};
+	use tiny_keccak::keccak256;
+	use {EventParam, ParamType, Event, LogParam, Hash, DecodedParams, TupleParam, encode};
 
 	#[test]
 	fn test_decoding_event() {
@@ -203,30 +346,44 @@ mod tests {
 				name: "a".to_owned(),
 				kind: ParamType::Int(256),
 				indexed: false,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "b".to_owned(),
 				kind: ParamType::Int(256),
 				indexed: true,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "c".to_owned(),
 				kind: ParamType::Address,
 				indexed: false,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "d".to_owned(),
 				kind: ParamType::Address,
 				indexed: true,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "e".to_owned(),
 				kind: ParamType::String,
 				indexed: true,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "f".to_owned(),
 				kind: ParamType::Array(Box::new(ParamType::Int(256))),
-				indexed: true
+				indexed: true,
+				internal_type: None,
+				components: vec![],
 			}, EventParam {
 				name: "g".to_owned(),
 				kind: ParamType::FixedArray(Box::new(ParamType::Address), 5),
 				indexed: true,
+				internal_type: None,
+				components: vec![],
 			}],
 			anonymous: false,
 		};
@@ -265,4 +422,348 @@ mod tests {
 			("g".to_owned(), Token::FixedBytes("00000000000000000ccccccccccccccccccccccccccccccccccccccccccccccc".from_hex().unwrap())),
 		].into_iter().map(|(name, value)| LogParam { name, value }).collect::<Vec<_>>()});
 	}
+
+	fn transfer_event() -> Event {
+		Event {
+			name: "Transfer".to_owned(),
+			inputs: vec![EventParam {
+				name: "from".to_owned(),
+				kind: ParamType::Address,
+				indexed: true,
+				internal_type: None,
+				components: vec![],
+			}, EventParam {
+				name: "to".to_owned(),
+				kind: ParamType::Address,
+				indexed: true,
+				internal_type: None,
+				components: vec![],
+			}, EventParam {
+				name: "value".to_owned(),
+				kind: ParamType::Uint(256),
+				indexed: false,
+				internal_type: None,
+				components: vec![],
+			}],
+			anonymous: false,
+		}
+	}
+
+	#[test]
+	fn test_event_topic() {
+		let expected: Hash = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse().unwrap();
+		assert_eq!(transfer_event().topic(), expected);
+	}
+
+	#[test]
+	fn test_canonical_signature() {
+		let events = [
+			(
+				"Transfer(address indexed from, address indexed to, uint256 value)",
+				"Transfer(address,address,uint256)",
+				hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+			),
+			(
+				"Approval(address indexed owner, address indexed spender, uint256 value)",
+				"Approval(address,address,uint256)",
+				hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"),
+			),
+			(
+				"Swap(address indexed sender, uint amount0In, uint amount1In, uint amount0Out, uint amount1Out, address indexed to)",
+				"Swap(address,uint256,uint256,uint256,uint256,address)",
+				hex!("d78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822"),
+			),
+		];
+
+		for &(signature, canonical, topic) in &events {
+			let event = Event::from_str_signature(signature).unwrap();
+			assert_eq!(event.canonical_signature(), canonical);
+			assert_eq!(event.topic(), Hash::from(topic));
+			assert_eq!(event.topic(), Hash::from(keccak256(canonical.as_bytes())));
+		}
+
+		assert_eq!(transfer_event().canonical_signature(), "Transfer(address,address,uint256)");
+		let event = Event::from_str_signature("OrderFilled((address maker, uint256[] amounts) indexed order, bytes data)").unwrap();
+		assert_eq!(event.canonical_signature(), "OrderFilled((address,uint256[]),bytes)");
+	}
+
+	#[test]
+	fn test_decode_log() {
+		let event = transfer_event();
+		let topics = vec![
+			event.topic(),
+			"0000000000000000000000001111111111111111111111111111111111111111".parse().unwrap(),
+			"0000000000000000000000002222222222222222222222222222222222222222".parse().unwrap(),
+		];
+		let data: Vec<u8> = "00000000000000000000000000000000000000000000000000000000000003e8".from_hex().unwrap();
+
+		let log = event.decode_log(&topics, &data).unwrap();
+		assert_eq!(log.get("from"), Some(&Token::Address([0x11u8; 20].into())));
+		assert_eq!(log.get("to"), Some(&Token::Address([0x22u8; 20].into())));
+		assert_eq!(log.get("value"), Some(&Token::Uint(1000.into())));
+		assert_eq!(log.get_index(2), log.get("value"));
+		assert_eq!(log.get("spender"), None);
+
+		let mut wrong_topics = topics.clone();
+		wrong_topics[0] = Hash::zero();
+		assert!(event.decode_log(&wrong_topics, &data).is_err());
+		assert!(event.decode_log(&topics[..2], &data).is_err());
+	}
+
+	#[test]
+	fn test_decode_log_with_hashed_topics() {
+		let event = Event {
+			name: "Transfer".to_owned(),
+			inputs: vec![EventParam {
+				name: "from".to_owned(),
+				kind: ParamType::Address,
+				indexed: true,
+				internal_type: None,
+				components: vec![],
+			}, EventParam {
+				name: "data".to_owned(),
+				kind: ParamType::Bytes,
+				indexed: true,
+				internal_type: None,
+				components: vec![],
+			}, EventParam {
+				name: "order".to_owned(),
+				kind: ParamType::Tuple(tuple_components![Box::new(ParamType::Uint(256)), Box::new(ParamType::Bool)]),
+				indexed: true,
+				internal_type: None,
+				components: vec![],
+			}, EventParam {
+				name: "value".to_owned(),
+				kind: ParamType::Uint(256),
+				indexed: false,
+				internal_type: None,
+				components: vec![],
+			}],
+			anonymous: false,
+		};
+
+		let data_hash = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+		let order_hash = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+		let topics = vec![
+			event.topic(),
+			"0000000000000000000000001111111111111111111111111111111111111111".parse().unwrap(),
+			data_hash.parse().unwrap(),
+			order_hash.parse().unwrap(),
+		];
+		let data: Vec<u8> = "0000000000000000000000000000000000000000000000000000000000000001".from_hex().unwrap();
+
+		let log = event.decode_log(&topics, &data).unwrap();
+		assert_eq!(log, DecodedParams::from(vec![
+			("from".to_owned(), Token::Address([0x11u8; 20].into())),
+			("data".to_owned(), Token::FixedBytes(data_hash.from_hex().unwrap())),
+			("order".to_owned(), Token::FixedBytes(order_hash.from_hex().unwrap())),
+			("value".to_owned(), Token::Uint(1.into())),
+		]));
+	}
+
+	#[test]
+	fn test_decode_anonymous_log() {
+		let mut event = transfer_event();
+		let topics = vec![
+			"0000000000000000000000001111111111111111111111111111111111111111".parse().unwrap(),
+			"0000000000000000000000002222222222222222222222222222222222222222".parse().unwrap(),
+		];
+		let data: Vec<u8> = "00000000000000000000000000000000000000000000000000000000000003e8".from_hex().unwrap();
+
+		assert_eq!(event.topic_count(), 3);
+		assert!(event.decode_log(&topics, &data).is_err());
+
+		event.anonymous = true;
+		assert_eq!(event.topic_count(), 2);
+		let log = event.decode_log(&topics, &data).unwrap();
+		assert_eq!(log, DecodedParams::from(vec![
+			("from".to_owned(), Token::Address([0x11u8; 20].into())),
+			("to".to_owned(), Token::Address([0x22u8; 20].into())),
+			("value".to_owned(), Token::Uint(1000.into())),
+		]));
+	}
+
+	#[test]
+	fn test_decode_log_unnamed_and_duplicate_params() {
+		let event = Event::from_str_signature("D(uint256 indexed, uint256, bool indexed flag, bool flag)").unwrap();
+		let topics = vec![
+			event.topic(),
+			"0000000000000000000000000000000000000000000000000000000000000001".parse().unwrap(),
+			"0000000000000000000000000000000000000000000000000000000000000001".parse().unwrap(),
+		];
+		let data: Vec<u8> = "
+			0000000000000000000000000000000000000000000000000000000000000002
+			0000000000000000000000000000000000000000000000000000000000000000
+		".split_whitespace().collect::<String>().from_hex().unwrap();
+
+		let log = event.decode_log(&topics, &data).unwrap();
+		assert_eq!(log, DecodedParams::from(vec![
+			("".to_owned(), Token::Uint(1.into())),
+			("".to_owned(), Token::Uint(2.into())),
+			("flag".to_owned(), Token::Bool(true)),
+			("flag".to_owned(), Token::Bool(false)),
+		]));
+	}
+
+	#[test]
+	fn test_filter_topics() {
+		let mut event = transfer_event();
+		let to = Token::Address([0x22u8; 20].into());
+		let topics = event.filter_topics(&[("to".to_owned(), Some(to))]).unwrap();
+		assert_eq!(topics, vec![
+			Some(event.topic().into()),
+			None,
+			Some(hex!("0000000000000000000000002222222222222222222222222222222222222222")),
+		]);
+		assert_eq!(event.filter_topics(&[("from".to_owned(), None)]).unwrap(), vec![Some(event.topic().into()), None, None]);
+
+		assert!(event.filter_topics(&[("value".to_owned(), None)]).is_err());
+		assert!(event.filter_topics(&[("to".to_owned(), Some(Token::Bool(true)))]).is_err());
+
+		event.anonymous = true;
+		assert_eq!(event.filter_topics(&[]).unwrap(), vec![None, None]);
+	}
+
+	#[test]
+	fn test_filter_topics_hashes_dynamic_params() {
+		let event = Event::from_str_signature("Named(string indexed name, uint256[] indexed ids)").unwrap();
+		let topics = event.filter_topics(&[
+			("name".to_owned(), Some(Token::String("gavofyork".to_owned()))),
+			("ids".to_owned(), Some(Token::Array(vec![Token::Uint(1.into())]))),
+		]).unwrap();
+		assert_eq!(topics, vec![
+			Some(event.topic().into()),
+			Some(keccak256(b"gavofyork")),
+			Some(keccak256(&hex!("0000000000000000000000000000000000000000000000000000000000000001"))),
+		]);
+	}
+
+	#[test]
+	fn test_deserialize_anonymous() {
+		let s = r#"{
+			"name": "Foo",
+			"inputs": [{ "name": "a", "type": "address", "indexed": true }],
+			"anonymous": true
+		}"#;
+		let event: Event = serde_json::from_str(s).unwrap();
+		assert!(event.anonymous);
+		assert_eq!(event.topic_count(), 1);
+
+		let s = r#"{
+			"name": "Foo",
+			"inputs": [{ "name": "a", "type": "address", "indexed": true }]
+		}"#;
+		let event: Event = serde_json::from_str(s).unwrap();
+		assert!(!event.anonymous);
+		assert_eq!(event.topic_count(), 2);
+	}
+
+	#[test]
+	fn test_from_str_signature() {
+		fn param(name: &str, kind: ParamType, indexed: bool) -> EventParam {
+			EventParam { name: name.to_owned(), kind, indexed, internal_type: None, components: vec![] }
+		}
+
+		let transfer = Event::from_str_signature("Transfer(address indexed from, address indexed to, uint256 value)").unwrap();
+		assert_eq!(transfer, transfer_event());
+
+		let approval = Event::from_str_signature("event Approval(address indexed owner, address indexed spender, uint256 value)").unwrap();
+		assert_eq!(approval.inputs, vec![
+			param("owner", ParamType::Address, true),
+			param("spender", ParamType::Address, true),
+			param("value", ParamType::Uint(256), false),
+		]);
+		assert_eq!(approval.topic(), "8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925".parse().unwrap());
+
+		let anonymous = Event::from_str_signature("anonymous Deposit(address indexed, uint256)").unwrap();
+		assert!(anonymous.anonymous);
+		assert_eq!(anonymous.inputs, vec![param("", ParamType::Address, true), param("", ParamType::Uint(256), false)]);
+		assert!(Event::from_str_signature("Deposit(address indexed, uint256) anonymous").unwrap().anonymous);
+
+		let with_tuple = Event::from_str_signature("Order((address maker, uint256[2] amounts) indexed order, (bool,bytes) info)").unwrap();
+		let mut order = param("order", "(address,uint256[2])".parse().unwrap(), true);
+		order.components = vec![
+			TupleParam { name: Some("maker".to_owned()), kind: ParamType::Address, internal_type: None, components: vec![] },
+			TupleParam { name: Some("amounts".to_owned()), kind: "uint256[2]".parse().unwrap(), internal_type: None, components: vec![] },
+		];
+		assert_eq!(with_tuple.inputs, vec![order, param("info", "(bool,bytes)".parse().unwrap(), false)]);
+	}
+
+	#[test]
+	fn test_invalid_str_signature() {
+		assert!(Event::from_str_signature("Transfer").is_err());
+		assert!(Event::from_str_signature("Transfer(address from indexed)").is_err());
+		assert!(Event::from_str_signature("Transfer(address) returns (bool)").is_err());
+		assert!(::Function::from_str_signature("transfer(address indexed to)").is_err());
+	}
+
+	#[test]
+	fn test_encode_log_round_trip() {
+		let event = Event::from_str_signature(
+			"Static(address indexed a, uint256 indexed b, int8 indexed c, bool d, bytes32 e, string f)"
+		).unwrap();
+		let named_tokens = vec![
+			("f".to_owned(), Token::String("hello".to_owned())),
+			("a".to_owned(), Token::Address([0x11u8; 20].into())),
+			("b".to_owned(), Token::Uint(1000.into())),
+			("c".to_owned(), Token::Int(!::Uint::zero())),
+			("d".to_owned(), Token::Bool(true)),
+			("e".to_owned(), Token::FixedBytes(vec![0x22; 32])),
+		];
+
+		let (topics, data) = event.encode_log(&named_tokens).unwrap();
+		assert_eq!(topics.len(), event.topic_count());
+		assert_eq!(topics[0], event.topic());
+
+		let log = event.decode_log(&topics, &data).unwrap();
+		let expected = ["a", "b", "c", "d", "e", "f"].iter()
+			.map(|name| named_tokens.iter().find(|t| t.0 == *name).unwrap().clone())
+			.collect::<DecodedParams>();
+		assert_eq!(log, expected);
+
+		let mut anonymous = event.clone();
+		anonymous.anonymous = true;
+		let (topics, data) = anonymous.encode_log(&named_tokens).unwrap();
+		assert_eq!(topics.len(), 3);
+		assert_eq!(anonymous.decode_log(&topics, &data).unwrap(), expected);
+	}
+
+	#[test]
+	fn test_encode_log_hashes_dynamic_topics() {
+		let event = Event::from_str_signature("Dynamic(string indexed a, bytes indexed b, uint8[] indexed c, (string,bool) indexed d)").unwrap();
+		let (topics, data) = event.encode_log(&[
+			("a".to_owned(), Token::String("hello".to_owned())),
+			("b".to_owned(), Token::Bytes(vec![1, 2, 3])),
+			("c".to_owned(), Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())])),
+			("d".to_owned(), Token::Tuple(vec![Token::String("hi".to_owned()), Token::Bool(true)])),
+		]).unwrap();
+
+		let mut tuple = b"hi".to_vec();
+		tuple.resize(32, 0);
+		tuple.extend(encode(&[Token::Bool(true)]));
+
+		assert!(data.is_empty());
+		assert_eq!(topics[1..].to_vec(), vec![
+			Hash::from(keccak256(b"hello")),
+			Hash::from(keccak256(&[1, 2, 3])),
+			Hash::from(keccak256(&encode(&[Token::Uint(1.into()), Token::Uint(2.into())]))),
+			Hash::from(keccak256(&tuple)),
+		]);
+	}
+
+	#[test]
+	fn test_encode_log_invalid_params() {
+		let event = transfer_event();
+		let from = ("from".to_owned(), Token::Address([0x11u8; 20].into()));
+		let to = ("to".to_owned(), Token::Address([0x22u8; 20].into()));
+		let value = ("value".to_owned(), Token::Uint(1.into()));
+
+		let err = event.encode_log(&[from.clone(), to.clone()]).unwrap_err();
+		assert_eq!(format!("{}", err), "Missing param `value` of event `Transfer`");
+
+		let err = event.encode_log(&[from.clone(), to.clone(), value.clone(), ("fee".to_owned(), Token::Bool(true))]).unwrap_err();
+		assert_eq!(format!("{}", err), "Unknown param `fee` of event `Transfer`");
+
+		assert!(event.encode_log(&[from, to, ("value".to_owned(), Token::Bool(true))]).is_err());
+	}
 }
@@ -1,17 +1,71 @@
 //! Event param specification.
 
-use {ParamType};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tuple_param::AbiType;
+use human_readable;
+use {Error, ParamType, TupleParam};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Event param specification.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct EventParam {
 	/// Param name.
 	pub name: String,
 	/// Param type.
-	#[serde(rename="type")]
 	pub kind: ParamType,
 	/// Indexed flag. If true, param is used to build block bloom.
 	pub indexed: bool,
+	/// Solidity type of the param, e.g. `struct Pool.Key` or `enum State`, if known.
+	pub internal_type: Option<String>,
+	/// Components of the param type if it is a tuple or an array of tuples, keeping their names
+	/// and internal types. Empty for other types and if no component has a name or internal type.
+	pub components: Vec<TupleParam>,
+}
+
+impl EventParam {
+	/// Parses event param from its human readable form, a type followed by the optional `indexed`
+	/// keyword and name, e.g. `address indexed from`, `uint256 value` or `(address,uint256) indexed`.
+	/// `indexed` may be mixed with data location keywords, but must come before the name.
+	pub fn from_str_signature(signature: &str) -> Result<EventParam, Error> {
+		human_readable::parse_event_param(signature)
+	}
+}
+
+/// Event param as found in the ABI json.
+#[derive(Serialize, Deserialize)]
+struct AbiEventParam {
+	name: String,
+	#[serde(flatten)]
+	kind: AbiType,
+	indexed: bool,
+	#[serde(rename = "internalType", default, skip_serializing_if = "Option::is_none")]
+	internal_type: Option<String>,
+}
+
+impl<'a> Deserialize<'a> for EventParam {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+		let abi = AbiEventParam::deserialize(deserializer)?;
+		let (kind, components) = abi.kind.deserialize_parts()?;
+		Ok(EventParam {
+			name: abi.name,
+			kind,
+			indexed: abi.indexed,
+			internal_type: abi.internal_type,
+			components,
+		})
+	}
+}
+
+impl Serialize for EventParam {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		AbiEventParam {
+			name: self.name.clone(),
+			kind: AbiType::new(&self.kind, &self.components),
+			indexed: self.indexed,
+			internal_type: self.internal_type.clone(),
+		}.serialize(serializer)
+	}
 }
 
 #[cfg(test)]
@@ -33,6 +87,87 @@ mod tests {
 			name: "foo".to_owned(),
 			kind: ParamType::Address,
 			indexed: true,
+			internal_type: None,
+			components: vec![],
 		});
 	}
+
+	#[test]
+	fn tuple_event_param_serialization() {
+		let param = EventParam {
+			name: "order".to_owned(),
+			kind: "(address,uint256)[2]".parse().unwrap(),
+			indexed: true,
+			internal_type: None,
+			components: vec![],
+		};
+
+		let serialized = serde_json::to_string(&param).unwrap();
+		assert_eq!(serialized, r#"{"name":"order","type":"tuple[2]","components":[{"type":"address"},{"type":"uint256"}],"indexed":true}"#);
+		assert_eq!(serde_json::from_str::<EventParam>(&serialized).unwrap(), param);
+	}
+
+	#[test]
+	fn nested_tuple_event_param() {
+		let s = r#"{
+			"name": "order",
+			"type": "tuple",
+			"indexed": false,
+			"components": [
+				{ "name": "maker", "type": "address" },
+				{
+					"name": "fill",
+					"type": "tuple",
+					"components": [
+						{ "name": "partial", "type": "bool" },
+						{
+							"name": "legs",
+							"type": "tuple[]",
+							"components": [
+								{ "name": "amount", "type": "uint256" },
+								{ "name": "asset", "type": "bytes32" }
+							]
+						}
+					]
+				}
+			]
+		}"#;
+
+		let deserialized: EventParam = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.kind, "(address,(bool,(uint256,bytes32)[]))".parse().unwrap());
+		assert!(!deserialized.indexed);
+
+		let serialized = serde_json::to_string(&deserialized).unwrap();
+		assert_eq!(serde_json::from_str::<EventParam>(&serialized).unwrap(), deserialized);
+	}
+
+	#[test]
+	fn from_str_signature() {
+		fn param(signature: &str) -> (String, String, bool) {
+			let param = EventParam::from_str_signature(signature).unwrap();
+			(param.name, param.kind.to_string(), param.indexed)
+		}
+
+		for kind in &["address", "bytes", "bytes32", "int8", "uint256", "bool", "string", "uint256[]", "bytes4[3]", "(address,uint256)"] {
+			assert_eq!(param(kind), ("".to_owned(), kind.to_string(), false));
+			assert_eq!(param(&format!("{} value", kind)), ("value".to_owned(), kind.to_string(), false));
+			assert_eq!(param(&format!("{} indexed", kind)), ("".to_owned(), kind.to_string(), true));
+			assert_eq!(param(&format!("{} indexed value", kind)), ("value".to_owned(), kind.to_string(), true));
+		}
+
+		assert_eq!(param("tuple(address maker, uint256 amount)[] indexed orders"), ("orders".to_owned(), "(address,uint256)[]".to_owned(), true));
+		assert_eq!(param("((bool,string),bytes32)  indexed  info "), ("info".to_owned(), "((bool,string),bytes32)".to_owned(), true));
+		assert_eq!(param("address payable indexed to"), ("to".to_owned(), "address".to_owned(), true));
+		assert_eq!(param("string indexed memory name"), ("name".to_owned(), "string".to_owned(), true));
+		let state = EventParam::from_str_signature("enum Vault.State indexed state").unwrap();
+		assert_eq!(state.internal_type, Some("enum Vault.State".to_owned()));
+		assert!(state.indexed);
+
+		assert!(EventParam::from_str_signature("").is_err());
+		assert!(EventParam::from_str_signature("address indexed from to").is_err());
+		assert!(EventParam::from_str_signature("address from to").is_err());
+		assert!(EventParam::from_str_signature("address from indexed").is_err());
+		assert!(EventParam::from_str_signature("uint7 indexed").is_err());
+		assert!(EventParam::from_str_signature("(address,bool indexed").is_err());
+	}
 }
@@ -1,7 +1,9 @@
-use std::ops;
+use core::ops;
 use serde::{Serialize, Serializer};
 use serde_json::Value;
 use {Hash, Token};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Raw topic filter.
 #[derive(Debug, PartialEq, Default)]
@@ -1,10 +1,19 @@
 //! Contract function call builder.
 
 use signature::short_signature;
-use {Param, Token, Result, ErrorKind, Bytes, decode, ParamType, encode};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+use serde::{Deserialize, Deserializer};
+use hex::ToHex;
+use param_type::Writer;
+use tuple_param::write_named;
+use human_readable;
+use {Param, Token, Result, Bytes, DecodedParams, DefaultTypeMapper, TypeMapper, decode_with_names, ParamType, StateMutability, encode_with_types, decode_function_input};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Contract function specification.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Function {
 	/// Function name.
 	pub name: String,
@@ -12,9 +21,65 @@ pub struct Function {
 	pub inputs: Vec<Param>,
 	/// Function output.
 	pub outputs: Vec<Param>,
-	/// Constant function.
+	/// Function state mutability.
+	#[serde(rename = "stateMutability")]
+	pub state_mutability: StateMutability,
+}
+
+/// Function as found in the ABI json, with both the current and legacy mutability fields.
+///
+/// Unlike fallback and receive functions, named functions always list their name and params.
+#[derive(Deserialize)]
+struct AbiFunction {
+	name: String,
+	inputs: Vec<Param>,
+	outputs: Vec<Param>,
+	#[serde(rename = "stateMutability")]
+	state_mutability: Option<StateMutability>,
+	#[serde(default)]
+	payable: bool,
 	#[serde(default)]
-	pub constant: bool,
+	constant: bool,
+}
+
+impl<'a> Deserialize<'a> for Function {
+	fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: Deserializer<'a> {
+		let abi = AbiFunction::deserialize(deserializer)?;
+
+		// `stateMutability` supersedes `payable` and `constant` since solidity 0.4.16
+		let state_mutability = match abi.state_mutability {
+			Some(state_mutability) => state_mutability,
+			None if abi.payable => StateMutability::Payable,
+			None if abi.constant => StateMutability::View,
+			None => StateMutability::NonPayable,
+		};
+
+		Ok(Function {
+			name: abi.name,
+			inputs: abi.inputs,
+			outputs: abi.outputs,
+			state_mutability,
+		})
+	}
+}
+
+/// Strict and reserved keywords of Rust, which can't be used as names of functions or params.
+const RUST_KEYWORDS: &[&str] = &[
+	"abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+	"do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+	"let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+	"return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+	"unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Returns `name` with a trailing underscore if it is a Rust keyword. Raw identifiers like
+/// `r#type` aren't used, since `self`, `Self`, `super` and `crate` can't be raw identifiers.
+fn rust_identifier(name: &str) -> String {
+	if RUST_KEYWORDS.contains(&name) {
+		format!("{}_", name)
+	} else {
+		name.to_owned()
+	}
 }
 
 impl Function {
@@ -25,35 +90,192 @@ impl Function {
 			.collect()
 	}
 
-	/// Returns all output params of given function.
-	fn output_param_types(&self) -> Vec<ParamType> {
-		self.outputs.iter()
-			.map(|p| p.kind.clone())
-			.collect()
+	/// Parses function from human readable signature, e.g.
+	/// `transfer(address to, uint256 amount) returns (bool)`.
+	///
+	/// Param names are optional. Tuples are written as `(uint256,bool)` and may name their
+	/// components. State mutability keywords `pure`, `view` and `payable` are recognized.
+	pub fn from_str_signature(signature: &str) -> Result<Function> {
+		human_readable::parse_function(signature)
+	}
+
+	/// Returns the canonical signature of the function, e.g. `baz(uint32,bool)`, which is
+	/// hashed to compute the selector. Tuples are written as `(uint256,bool)`.
+	///
+	/// Param names, outputs and state mutability are not part of it, see `full_signature`.
+	pub fn canonical_signature(&self) -> String {
+		let types = self.inputs.iter()
+			.map(|p| Writer::write(&p.kind))
+			.collect::<Vec<String>>()
+			.join(",");
+
+		format!("{}({})", self.name, types)
+	}
+
+	/// Returns the human readable signature of the function, with param names, state
+	/// mutability and outputs, e.g. `balanceOf(address owner) view returns (uint256)`.
+	///
+	/// Tuple components are written with their names, e.g. `(address token, uint256 amount)`,
+	/// so `from_str_signature` parses it back to the same function, except for the internal
+	/// types of params, which aren't part of it. It isn't used for the selector, which is
+	/// computed from the `canonical_signature`.
+	pub fn full_signature(&self) -> String {
+		fn params(params: &[Param]) -> String {
+			params.iter()
+				.map(|p| match p.name.as_str() {
+					"" => write_named(&p.kind, &p.components),
+					name => format!("{} {}", write_named(&p.kind, &p.components), name),
+				})
+				.collect::<Vec<String>>()
+				.join(", ")
+		}
+
+		let mut signature = format!("{}({})", self.name, params(&self.inputs));
+		match self.state_mutability {
+			StateMutability::Pure => signature.push_str(" pure"),
+			StateMutability::View => signature.push_str(" view"),
+			StateMutability::Payable => signature.push_str(" payable"),
+			StateMutability::NonPayable => {},
+		}
+		if !self.outputs.is_empty() {
+			signature.push_str(&format!(" returns ({})", params(&self.outputs)));
+		}
+		signature
+	}
+
+	/// Returns the 4 byte selector of the function, the first bytes of the signature hash.
+	pub fn selector(&self) -> [u8; 4] {
+		short_signature(&self.name, &self.input_param_types())
+	}
+
+	/// Returns the signature of a Rust function calling the function, for generating bindings,
+	/// e.g. `fn transfer(to: [u8; 20], amount: U256) -> Result<bool>`.
+	///
+	/// Types are mapped by `DefaultTypeMapper`. Unnamed inputs are named after their index,
+	/// e.g. `param0`, and names which are Rust keywords get a trailing underscore, e.g. `type_`.
+	/// Multiple outputs are returned as a tuple.
+	pub fn generate_rust_signature(&self) -> String {
+		self.generate_rust_signature_with(&DefaultTypeMapper)
+	}
+
+	/// Returns the signature of a Rust function calling the function like `generate_rust_signature`,
+	/// with types mapped by `mapper`.
+	pub fn generate_rust_signature_with<M: TypeMapper + ?Sized>(&self, mapper: &M) -> String {
+		let inputs: Vec<_> = self.inputs.iter()
+			.enumerate()
+			.map(|(index, p)| {
+				let name = match p.name.as_str() {
+					"" => format!("param{}", index),
+					name => rust_identifier(name),
+				};
+				format!("{}: {}", name, mapper.rust_type(&p.kind))
+			})
+			.collect();
+
+		let output = match self.outputs.len() {
+			0 => "()".to_owned(),
+			1 => mapper.rust_type(&self.outputs[0].kind),
+			_ => {
+				let outputs: Vec<_> = self.outputs.iter().map(|p| mapper.rust_type(&p.kind)).collect();
+				format!("({})", outputs.join(", "))
+			},
+		};
+
+		format!("fn {}({}) -> Result<{}>", rust_identifier(&self.name), inputs.join(", "), output)
+	}
+
+	/// Returns the selector as `0x` prefixed hex string.
+	pub fn selector_hex(&self) -> String {
+		format!("0x{}", self.selector().to_hex::<String>())
+	}
+
+	/// Returns true if the call data starts with the selector of the function.
+	pub fn match_selector(&self, data: &[u8]) -> bool {
+		data.len() >= 4 && data[..4] == self.selector()
 	}
 
 	/// Prepares ABI function call with given input params.
 	pub fn encode_input(&self, tokens: &[Token]) -> Result<Bytes> {
 		let params = self.input_param_types();
+		let encoded = encode_with_types(&params, tokens)?;
+
+		Ok(self.selector().iter().cloned().chain(encoded).collect())
+	}
 
-		if !Token::types_check(tokens, &params) {
-			return Err(ErrorKind::InvalidData.into());
+	/// Returns the keys of the inputs in maps of input values, their names, or their index
+	/// like `"0"` for unnamed inputs. Fails if two inputs have the same key.
+	#[cfg(feature = "std")]
+	fn input_keys(&self) -> Result<Vec<String>> {
+		let mut keys: Vec<String> = Vec::with_capacity(self.inputs.len());
+		for (index, p) in self.inputs.iter().enumerate() {
+			let key = match p.name.as_str() {
+				"" => index.to_string(),
+				name => name.to_owned(),
+			};
+			if keys.contains(&key) {
+				bail!("Function `{}` has more than one input named `{}`", self.name, key);
+			}
+			keys.push(key);
 		}
+		Ok(keys)
+	}
+
+	/// Prepares ABI function call with input params given by name. Unnamed inputs are given by
+	/// their index, e.g. `"0"`, like in `decode_with_names`.
+	///
+	/// Fails if any input is missing, if there are values for unknown inputs, or if two inputs
+	/// have the same name.
+	#[cfg(feature = "std")]
+	pub fn encode_input_by_name(&self, params: &HashMap<String, Token>) -> Result<Bytes> {
+		let keys = self.input_keys()?;
+		if let Some(name) = params.keys().find(|name| !keys.contains(name)) {
+			bail!("Unknown input `{}` of function `{}`", name, self.name);
+		}
+
+		let tokens = keys.iter()
+			.map(|key| match params.get(key) {
+				Some(token) => Ok(token.clone()),
+				None => Err(format!("Missing input `{}` of function `{}`", key, self.name).into()),
+			})
+			.collect::<Result<Vec<Token>>>()?;
+
+		self.encode_input(&tokens)
+	}
+
+	/// Parses the ABI function call data, including the selector, to tokens keyed by input name.
+	/// Unnamed inputs are keyed by their index, e.g. `"0"`, like in `decode_with_names`.
+	///
+	/// Fails if two inputs have the same name, use `decode_input` for such functions.
+	#[cfg(feature = "std")]
+	pub fn decode_input_named(&self, data: &[u8]) -> Result<HashMap<String, Token>> {
+		let keys = self.input_keys()?;
+		let tokens = self.decode_input(data)?;
+		Ok(keys.into_iter().zip(tokens).collect())
+	}
 
-		let signed = short_signature(&self.name, &params).to_vec();
-		let encoded = encode(tokens);
-		Ok(signed.into_iter().chain(encoded.into_iter()).collect())
+	/// Parses the ABI function call data, including the selector, to list of tokens.
+	pub fn decode_input(&self, data: &[u8]) -> Result<Vec<Token>> {
+		decode_function_input(self.selector(), data, &self.input_param_types())
 	}
 
 	/// Parses the ABI function output to list of tokens.
+	#[deprecated(note = "use `decode_output_named`, which keeps the names of the outputs")]
 	pub fn decode_output(&self, data: &[u8]) -> Result<Vec<Token>> {
-		decode(&self.output_param_types(), &data)
+		self.decode_output_named(data).map(DecodedParams::into_tokens)
+	}
+
+	/// Parses the ABI function output to tokens named after the outputs, unnamed outputs
+	/// after their index, see `decode_with_names`.
+	pub fn decode_output_named(&self, data: &[u8]) -> Result<DecodedParams> {
+		decode_with_names(&self.outputs, data).map(DecodedParams::from)
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use {Token, Param, Function, ParamType};
+	use std::collections::HashMap;
+	use serde_json;
+	use {Token, Param, Function, ParamType, StateMutability, encode};
 
 	#[test]
 	fn test_function_encode_call() {
@@ -62,12 +284,16 @@ mod tests {
 			inputs: vec![Param {
 				name: "a".to_owned(),
 				kind: ParamType::Uint(32),
+				internal_type: None,
+				components: vec![],
 			}, Param {
 				name: "b".to_owned(),
 				kind: ParamType::Bool,
+				internal_type: None,
+				components: vec![],
 			}],
 			outputs: vec![],
-			constant: false,
+			state_mutability: StateMutability::NonPayable,
 		};
 
 		let func = Function::from(interface);
@@ -77,4 +303,489 @@ mod tests {
 		let expected = hex!("cdcd77c000000000000000000000000000000000000000000000000000000000000000450000000000000000000000000000000000000000000000000000000000000001").to_vec();
 		assert_eq!(encoded, expected);
 	}
+
+	#[test]
+	fn test_function_signature_and_selector() {
+		let interface = Function {
+			name: "baz".to_owned(),
+			inputs: vec![Param {
+				name: "a".to_owned(),
+				kind: ParamType::Uint(32),
+				internal_type: None,
+				components: vec![],
+			}, Param {
+				name: "b".to_owned(),
+				kind: ParamType::Bool,
+				internal_type: None,
+				components: vec![],
+			}],
+			outputs: vec![],
+			state_mutability: StateMutability::NonPayable,
+		};
+
+		assert_eq!(interface.canonical_signature(), "baz(uint32,bool)");
+		assert_eq!(interface.selector(), hex!("cdcd77c0"));
+	}
+
+	#[test]
+	fn test_canonical_signature_selectors() {
+		fn function(name: &str, inputs: &[&str]) -> Function {
+			Function {
+				name: name.to_owned(),
+				inputs: inputs.iter().map(|kind| Param {
+					name: "".to_owned(),
+					kind: kind.parse().unwrap(),
+					internal_type: None,
+					components: vec![],
+				}).collect(),
+				outputs: vec![],
+				state_mutability: StateMutability::NonPayable,
+			}
+		}
+
+		let transfer = function("transfer", &["address", "uint256"]);
+		assert_eq!(transfer.canonical_signature(), "transfer(address,uint256)");
+		assert_eq!(transfer.selector(), hex!("a9059cbb"));
+
+		let approve = function("approve", &["address", "uint256"]);
+		assert_eq!(approve.selector(), hex!("095ea7b3"));
+
+		let batch_transfer = function("safeBatchTransferFrom", &["address", "address", "uint256[]", "uint256[]", "bytes"]);
+		assert_eq!(batch_transfer.canonical_signature(), "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)");
+		assert_eq!(batch_transfer.selector(), hex!("2eb2c2d6"));
+
+		let exact_input_single = function("exactInputSingle", &["(address,address,uint24,address,uint256,uint256,uint256,uint160)"]);
+		assert_eq!(
+			exact_input_single.canonical_signature(),
+			"exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))"
+		);
+		assert_eq!(exact_input_single.selector(), hex!("414bf389"));
+
+		let fixed = function("foo", &["bool[2][]", "(uint8,bytes)[3]"]);
+		assert_eq!(fixed.canonical_signature(), "foo(bool[2][],(uint8,bytes)[3])");
+	}
+
+	#[test]
+	fn test_match_selector() {
+		let interface = Function {
+			name: "baz".to_owned(),
+			inputs: vec![Param {
+				name: "a".to_owned(),
+				kind: ParamType::Uint(32),
+				internal_type: None,
+				components: vec![],
+			}, Param {
+				name: "b".to_owned(),
+				kind: ParamType::Bool,
+				internal_type: None,
+				components: vec![],
+			}],
+			outputs: vec![],
+			state_mutability: StateMutability::NonPayable,
+		};
+
+		assert_eq!(interface.selector_hex(), "0xcdcd77c0");
+		assert!(interface.match_selector(&hex!("cdcd77c0")));
+		assert!(interface.match_selector(&interface.encode_input(&[Token::Uint(1.into()), Token::Bool(false)]).unwrap()));
+		assert!(!interface.match_selector(&hex!("cdcd77c1")));
+		assert!(!interface.match_selector(&hex!("cdcd77")));
+		assert!(!interface.match_selector(&[]));
+	}
+
+	#[test]
+	fn test_function_decode_input() {
+		let interface = Function {
+			name: "baz".to_owned(),
+			inputs: vec![Param {
+				name: "a".to_owned(),
+				kind: ParamType::Uint(32),
+				internal_type: None,
+				components: vec![],
+			}, Param {
+				name: "b".to_owned(),
+				kind: ParamType::Bool,
+				internal_type: None,
+				components: vec![],
+			}],
+			outputs: vec![],
+			state_mutability: StateMutability::NonPayable,
+		};
+
+		let tokens = vec![Token::Uint(69.into()), Token::Bool(true)];
+		let encoded = interface.encode_input(&tokens).unwrap();
+		assert_eq!(interface.decode_input(&encoded).unwrap(), tokens);
+		assert!(interface.decode_input(&encoded[4..]).is_err());
+	}
+
+	#[test]
+	fn deserialize_state_mutability() {
+		fn state_mutability(fields: &str) -> StateMutability {
+			let s = format!(r#"{{"name":"foo","inputs":[],"outputs":[]{}}}"#, fields);
+			serde_json::from_str::<Function>(&s).unwrap().state_mutability
+		}
+
+		assert_eq!(state_mutability(r#","stateMutability":"pure""#), StateMutability::Pure);
+		assert_eq!(state_mutability(r#","stateMutability":"view""#), StateMutability::View);
+		assert_eq!(state_mutability(r#","stateMutability":"nonpayable""#), StateMutability::NonPayable);
+		assert_eq!(state_mutability(r#","stateMutability":"payable""#), StateMutability::Payable);
+		assert_eq!(state_mutability(r#","stateMutability":"view","constant":false,"payable":false"#), StateMutability::View);
+
+		assert_eq!(state_mutability(r#","payable":true"#), StateMutability::Payable);
+		assert_eq!(state_mutability(r#","payable":false"#), StateMutability::NonPayable);
+		assert_eq!(state_mutability(r#","constant":true"#), StateMutability::View);
+		assert_eq!(state_mutability(r#","constant":false,"payable":false"#), StateMutability::NonPayable);
+		assert_eq!(state_mutability(""), StateMutability::NonPayable);
+	}
+
+	#[test]
+	fn deserialize_requires_name_and_params() {
+		assert!(serde_json::from_str::<Function>(r#"{"type":"function"}"#).is_err());
+		assert!(serde_json::from_str::<Function>(r#"{"type":"function","inputs":[],"outputs":[]}"#).is_err());
+		assert!(serde_json::from_str::<Function>(r#"{"type":"function","name":"foo","outputs":[]}"#).is_err());
+		assert!(serde_json::from_str::<Function>(r#"{"type":"function","name":"foo","inputs":[],"outputs":[]}"#).is_ok());
+	}
+
+	#[test]
+	fn test_from_str_signature() {
+		fn param(name: &str, kind: &str) -> Param {
+			Param {
+				name: name.to_owned(),
+				kind: kind.parse().unwrap(),
+				internal_type: None,
+				components: vec![],
+			}
+		}
+
+		assert_eq!(Function::from_str_signature("transfer(address,uint256)").unwrap(), Function {
+			name: "transfer".to_owned(),
+			inputs: vec![param("", "address"), param("", "uint256")],
+			outputs: vec![],
+			state_mutability: StateMutability::NonPayable,
+		});
+
+		assert_eq!(Function::from_str_signature("transfer(address to, uint256 amount) returns (bool)").unwrap(), Function {
+			name: "transfer".to_owned(),
+			inputs: vec![param("to", "address"), param("amount", "uint256")],
+			outputs: vec![param("", "bool")],
+			state_mutability: StateMutability::NonPayable,
+		});
+
+		assert_eq!(Function::from_str_signature("function balanceOf(address owner) external view returns (uint256 balance)").unwrap(), Function {
+			name: "balanceOf".to_owned(),
+			inputs: vec![param("owner", "address")],
+			outputs: vec![param("balance", "uint256")],
+			state_mutability: StateMutability::View,
+		});
+
+		let f = Function::from_str_signature("deposit() payable").unwrap();
+		assert_eq!(f.state_mutability, StateMutability::Payable);
+		assert!(f.inputs.is_empty());
+		let f = Function::from_str_signature("add(uint8 a, uint8 b) pure returns (uint8)").unwrap();
+		assert_eq!(f.state_mutability, StateMutability::Pure);
+		let f = Function::from_str_signature("send(bytes memory data, address payable to)").unwrap();
+		assert_eq!(f.inputs, vec![param("data", "bytes"), param("to", "address")]);
+	}
+
+	#[test]
+	fn test_from_str_signature_with_tuples() {
+		let f = Function::from_str_signature(
+			"swap((address token, (uint256 amount, bool exact) limits)[] orders, tuple(bytes32,uint8)[2] sigs) returns ((uint256,uint256))"
+		).unwrap();
+
+		assert_eq!(f.canonical_signature(), "swap((address,(uint256,bool))[],(bytes32,uint8)[2])");
+		assert_eq!(f.inputs[0].name, "orders");
+		assert_eq!(f.inputs[1].name, "sigs");
+		assert_eq!(f.outputs, vec![Param {
+			name: "".to_owned(),
+			kind: "(uint256,uint256)".parse().unwrap(),
+			internal_type: None,
+			components: vec![],
+		}]);
+
+		let f = Function::from_str_signature("empty(())").unwrap();
+		assert_eq!(f.inputs[0].kind, ParamType::Tuple(tuple_components![]));
+
+		let f = Function::from_str_signature("approve(contract IERC20 token, enum Vault.State state)").unwrap();
+		assert_eq!(f.canonical_signature(), "approve(address,uint8)");
+		assert_eq!(f.inputs[0].internal_type, Some("contract IERC20".to_owned()));
+		assert_eq!(f.inputs[1].internal_type, Some("enum Vault.State".to_owned()));
+	}
+
+	#[test]
+	fn test_invalid_str_signature() {
+		assert!(Function::from_str_signature("").is_err());
+		assert!(Function::from_str_signature("transfer").is_err());
+		assert!(Function::from_str_signature("transfer(address").is_err());
+		assert!(Function::from_str_signature("transfer(address,)").is_err());
+		assert!(Function::from_str_signature("transfer(address to from)").is_err());
+		assert!(Function::from_str_signature("transfer(address) returns").is_err());
+		assert!(Function::from_str_signature("transfer(address) view extra").is_err());
+		assert_eq!(
+			format!("{}", Function::from_str_signature("transfer(address, uint7)").unwrap_err()),
+			"Invalid name `uint7` at position 18 of `transfer(address, uint7)`"
+		);
+	}
+
+	#[test]
+	fn test_encode_input_by_name() {
+		let function = Function::from_str_signature("transferFrom(address from, address to, uint256 value)").unwrap();
+		let from = Token::Address([0x11u8; 20].into());
+		let to = Token::Address([0x22u8; 20].into());
+		let value = Token::Uint(1000.into());
+
+		let mut params = HashMap::new();
+		params.insert("value".to_owned(), value.clone());
+		params.insert("to".to_owned(), to.clone());
+		params.insert("from".to_owned(), from.clone());
+
+		let encoded = function.encode_input_by_name(&params).unwrap();
+		assert_eq!(encoded, function.encode_input(&[from, to, value]).unwrap());
+		assert_eq!(function.decode_input_named(&encoded).unwrap(), params);
+	}
+
+	#[test]
+	fn test_encode_input_by_name_errors() {
+		let function = Function::from_str_signature("approve(address spender, uint256 value)").unwrap();
+
+		let mut params = HashMap::new();
+		params.insert("spender".to_owned(), Token::Address([0x11u8; 20].into()));
+		assert_eq!(
+			format!("{}", function.encode_input_by_name(&params).unwrap_err()),
+			"Missing input `value` of function `approve`"
+		);
+
+		params.insert("value".to_owned(), Token::Uint(1.into()));
+		params.insert("amount".to_owned(), Token::Uint(1.into()));
+		assert_eq!(
+			format!("{}", function.encode_input_by_name(&params).unwrap_err()),
+			"Unknown input `amount` of function `approve`"
+		);
+	}
+
+	#[test]
+	fn test_inputs_by_name_unnamed_and_duplicate() {
+		let function = Function::from_str_signature("deposit(address, uint256 amount, bool)").unwrap();
+		let mut params = HashMap::new();
+		params.insert("0".to_owned(), Token::Address([0x11u8; 20].into()));
+		params.insert("amount".to_owned(), Token::Uint(1000.into()));
+		params.insert("2".to_owned(), Token::Bool(true));
+
+		let encoded = function.encode_input_by_name(&params).unwrap();
+		assert_eq!(function.decode_input_named(&encoded).unwrap(), params);
+
+		params.remove("2");
+		assert_eq!(
+			format!("{}", function.encode_input_by_name(&params).unwrap_err()),
+			"Missing input `2` of function `deposit`"
+		);
+
+		let function = Function::from_str_signature("swap(address token, uint256 amount, address token)").unwrap();
+		let encoded = function.encode_input(&[Token::Address([0x11u8; 20].into()), Token::Uint(1.into()), Token::Address([0x22u8; 20].into())]).unwrap();
+		assert_eq!(
+			format!("{}", function.decode_input_named(&encoded).unwrap_err()),
+			"Function `swap` has more than one input named `token`"
+		);
+		assert!(function.encode_input_by_name(&HashMap::new()).is_err());
+
+		// the key of an unnamed input is its index, which collides with the input named `0`
+		let mut function = Function::from_str_signature("f(uint256, uint256)").unwrap();
+		function.inputs[1].name = "0".to_owned();
+		assert_eq!(
+			format!("{}", function.encode_input_by_name(&HashMap::new()).unwrap_err()),
+			"Function `f` has more than one input named `0`"
+		);
+	}
+
+	#[test]
+	fn test_decode_output_named() {
+		let function = Function::from_str_signature("getReserves() view returns (uint112 reserve0, uint112 reserve1, uint32)").unwrap();
+		let data = encode(&[Token::Uint(1.into()), Token::Uint(2.into()), Token::Uint(3.into())]);
+
+		let outputs = function.decode_output_named(&data).unwrap();
+		assert_eq!(outputs.len(), 3);
+		assert_eq!(outputs.get("reserve0"), Some(&Token::Uint(1.into())));
+		assert_eq!(outputs.get("reserve1"), Some(&Token::Uint(2.into())));
+		assert_eq!(outputs.get("blockTimestampLast"), None);
+		assert_eq!(outputs.get_index(2), Some(&Token::Uint(3.into())));
+		assert_eq!(outputs.get("2"), Some(&Token::Uint(3.into())));
+		assert_eq!(outputs.get_index(3), None);
+
+		#[allow(deprecated)]
+		let tokens = function.decode_output(&data).unwrap();
+		assert_eq!(tokens, outputs.into_tokens());
+	}
+
+	#[test]
+	fn test_decode_output_path_get() {
+		let function = Function::from_str_signature("order() returns ((address maker, uint256[] amounts) order, bool filled)").unwrap();
+		let data = encode(&[
+			Token::Tuple(vec![Token::Address([1u8; 20].into()), Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())])]),
+			Token::Bool(true),
+		]);
+
+		let outputs = function.decode_output_named(&data).unwrap();
+		assert_eq!(outputs.path_get("order.1.1"), Some(&Token::Uint(2.into())));
+		assert_eq!(outputs.path_get("order.0"), Some(&Token::Address([1u8; 20].into())));
+		assert_eq!(outputs.path_get("filled"), Some(&Token::Bool(true)));
+		assert_eq!(outputs.path_get("order.1.2"), None);
+		assert_eq!(outputs.path_get("filled.0"), None);
+		assert_eq!(outputs.path_get("maker"), None);
+	}
+
+	#[test]
+	fn test_generate_rust_signature() {
+		let signature = |s: &str| Function::from_str_signature(s).unwrap().generate_rust_signature();
+
+		assert_eq!(signature("transfer(address to, uint256 amount) returns (bool)"), "fn transfer(to: [u8; 20], amount: U256) -> Result<bool>");
+		assert_eq!(signature("deposit() payable"), "fn deposit() -> Result<()>");
+		assert_eq!(signature("f(int8, bytes, bytes4 data)"), "fn f(param0: U256, param1: Vec<u8>, data: [u8; 4]) -> Result<()>");
+		assert_eq!(
+			signature("getReserves() view returns (uint112 reserve0, uint112 reserve1, uint32)"),
+			"fn getReserves() -> Result<(U256, U256, U256)>"
+		);
+
+		let function = Function::from_str_signature("pack(uint8[] values, address owner) returns (uint256)").unwrap();
+		let mapper = |kind: &ParamType| match *kind {
+			ParamType::Uint(8) => Some("u8".to_owned()),
+			_ => None,
+		};
+		assert_eq!(function.generate_rust_signature_with(&mapper), "fn pack(values: Vec<u8>, owner: [u8; 20]) -> Result<U256>");
+	}
+
+	#[test]
+	fn test_generate_rust_signature_escapes_keywords() {
+		let signature = |s: &str| Function::from_str_signature(s).unwrap().generate_rust_signature();
+
+		assert_eq!(signature("f(bytes4 self, uint8 type, bool move)"), "fn f(self_: [u8; 4], type_: U256, move_: bool) -> Result<()>");
+		assert_eq!(signature("g(address Self, address super, address crate)"), "fn g(Self_: [u8; 20], super_: [u8; 20], crate_: [u8; 20]) -> Result<()>");
+		assert_eq!(signature("match(string async, uint256 yield) returns (bool)"), "fn match_(async_: String, yield_: U256) -> Result<bool>");
+		assert_eq!(signature("type()"), "fn type_() -> Result<()>");
+		assert_eq!(signature("types(uint256 types, uint256 self2)"), "fn types(types: U256, self2: U256) -> Result<()>");
+	}
+
+	#[test]
+	fn uniswap_v3_exact_input() {
+		// `exactInput` of the Uniswap V3 SwapRouter
+		let s = r#"{
+			"inputs": [
+				{
+					"components": [
+						{ "internalType": "bytes", "name": "path", "type": "bytes" },
+						{ "internalType": "address", "name": "recipient", "type": "address" },
+						{ "internalType": "uint256", "name": "deadline", "type": "uint256" },
+						{ "internalType": "uint256", "name": "amountIn", "type": "uint256" },
+						{ "internalType": "uint256", "name": "amountOutMinimum", "type": "uint256" }
+					],
+					"internalType": "struct ISwapRouter.ExactInputParams",
+					"name": "params",
+					"type": "tuple"
+				}
+			],
+			"name": "exactInput",
+			"outputs": [{ "internalType": "uint256", "name": "amountOut", "type": "uint256" }],
+			"stateMutability": "payable",
+			"type": "function"
+		}"#;
+
+		let function: Function = serde_json::from_str(s).unwrap();
+		assert_eq!(function.inputs[0].kind, "(bytes,address,uint256,uint256,uint256)".parse().unwrap());
+		assert_eq!(function.inputs[0].internal_type, Some("struct ISwapRouter.ExactInputParams".to_owned()));
+		assert_eq!(function.canonical_signature(), "exactInput((bytes,address,uint256,uint256,uint256))");
+		assert_eq!(function.selector(), hex!("c04b8d59"));
+
+		let params = Token::Tuple(vec![
+			Token::Bytes(hex!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb480001f4c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2").to_vec()),
+			Token::Address([0x11u8; 20].into()),
+			Token::from(1_700_000_000u64),
+			Token::from(1_000_000u64),
+			Token::from(0u64),
+		]);
+		let encoded = function.encode_input(&[params.clone()]).unwrap();
+		assert_eq!(function.decode_input(&encoded).unwrap(), vec![params]);
+
+		let serialized = serde_json::to_string(&function).unwrap();
+		assert_eq!(serde_json::from_str::<Function>(&serialized).unwrap(), function);
+	}
+
+	#[test]
+	fn canonical_and_full_signatures() {
+		// functions of mainnet contracts with their well known selectors
+		let functions = [
+			("transfer(address to, uint256 amount) returns (bool)", "transfer(address,uint256)", hex!("a9059cbb")),
+			("transferFrom(address from, address to, uint256 amount) returns (bool)", "transferFrom(address,address,uint256)", hex!("23b872dd")),
+			("approve(address spender, uint256 amount) returns (bool)", "approve(address,uint256)", hex!("095ea7b3")),
+			("balanceOf(address owner) view returns (uint256)", "balanceOf(address)", hex!("70a08231")),
+			("deposit() payable", "deposit()", hex!("d0e30db0")),
+			(
+				"swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) returns (uint256[] amounts)",
+				"swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+				hex!("38ed1739"),
+			),
+			(
+				"exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160) params) payable returns (uint256 amountOut)",
+				"exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+				hex!("414bf389"),
+			),
+			(
+				concat!(
+					"exactInputSingle((address tokenIn, address tokenOut, uint24 fee, address recipient, uint256 deadline, ",
+					"uint256 amountIn, uint256 amountOutMinimum, uint160 sqrtPriceLimitX96) params) payable returns (uint256 amountOut)"
+				),
+				"exactInputSingle((address,address,uint24,address,uint256,uint256,uint256,uint160))",
+				hex!("414bf389"),
+			),
+			(
+				"multicall(bytes[] data) payable returns (bytes[] results)",
+				"multicall(bytes[])",
+				hex!("ac9650d8"),
+			),
+			("getReserves() view returns (uint112, uint112, uint32)", "getReserves()", hex!("0902f1ac")),
+		];
+
+		for &(full, canonical, selector) in &functions {
+			let function = Function::from_str_signature(full).unwrap();
+			assert_eq!(function.full_signature(), full);
+			assert_eq!(function.canonical_signature(), canonical);
+			assert_eq!(function.selector(), selector);
+			assert_eq!(Function::from_str_signature(&function.full_signature()).unwrap(), function);
+		}
+	}
+
+	#[test]
+	fn full_signature_keeps_component_names() {
+		// `exactInput` of the Uniswap V3 `SwapRouter`, as in its json ABI
+		let function: Function = serde_json::from_str(r#"{
+			"name": "exactInput",
+			"type": "function",
+			"stateMutability": "payable",
+			"inputs": [{
+				"name": "params",
+				"type": "tuple",
+				"internalType": "struct ISwapRouter.ExactInputParams",
+				"components": [
+					{ "name": "path", "type": "bytes", "internalType": "bytes" },
+					{ "name": "recipient", "type": "address", "internalType": "address" },
+					{ "name": "deadline", "type": "uint256", "internalType": "uint256" },
+					{ "name": "amountIn", "type": "uint256", "internalType": "uint256" },
+					{ "name": "amountOutMinimum", "type": "uint256", "internalType": "uint256" }
+				]
+			}],
+			"outputs": [{ "name": "amountOut", "type": "uint256", "internalType": "uint256" }]
+		}"#).unwrap();
+
+		let full = function.full_signature();
+		assert_eq!(full, concat!(
+			"exactInput((bytes path, address recipient, uint256 deadline, uint256 amountIn, uint256 amountOutMinimum) params) ",
+			"payable returns (uint256 amountOut)"
+		));
+		assert_eq!(function.selector(), hex!("c04b8d59"));
+
+		// internal types aren't part of the human readable signature
+		let parsed = Function::from_str_signature(&full).unwrap();
+		assert_eq!(parsed.canonical_signature(), function.canonical_signature());
+		assert_eq!(parsed.full_signature(), full);
+		let names = |f: &Function| f.inputs[0].components.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+		assert_eq!(names(&parsed), names(&function));
+		assert_eq!(parsed.inputs[0].internal_type, None);
+	}
 }
@@ -0,0 +1,360 @@
+//! Parser of human readable signatures, e.g. `transfer(address to, uint256 amount) returns (bool)`.
+
+use param_type::Reader;
+use tuple_param::named_components;
+use {CustomError, Error, ErrorKind, Event, EventParam, Function, Param, ParamType, Result, StateMutability, TupleParam};
+#[cfg(feature = "std")]
+use Constructor;
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Recursive descent parser over a signature.
+pub struct Parser<'a> {
+	input: &'a str,
+	position: usize,
+}
+
+impl<'a> Parser<'a> {
+	pub fn new(input: &'a str) -> Self {
+		Parser {
+			input,
+			position: 0,
+		}
+	}
+
+	fn rest(&self) -> &'a str {
+		&self.input[self.position..]
+	}
+
+	/// Error pointing at the unparsed part of the signature.
+	pub fn error(&self) -> Error {
+		let rest = self.rest();
+		let name = if rest.is_empty() { self.input } else { rest };
+		ErrorKind::InvalidNameAt(name.to_owned(), self.position, self.input.to_owned()).into()
+	}
+
+	fn skip_whitespace(&mut self) {
+		let rest = self.rest();
+		self.position += rest.len() - rest.trim_start().len();
+	}
+
+	fn peek(&mut self) -> Option<char> {
+		self.skip_whitespace();
+		self.rest().chars().next()
+	}
+
+	/// Consumes `c` if it's the next char.
+	pub fn eat(&mut self, c: char) -> bool {
+		if self.peek() == Some(c) {
+			self.position += c.len_utf8();
+			true
+		} else {
+			false
+		}
+	}
+
+	fn expect(&mut self, c: char) -> Result<()> {
+		if self.eat(c) {
+			Ok(())
+		} else {
+			Err(self.error())
+		}
+	}
+
+	/// Returns the next identifier without consuming it.
+	fn peek_identifier(&mut self) -> Option<&'a str> {
+		self.skip_whitespace();
+		let rest = self.rest();
+		let len = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$')).unwrap_or(rest.len());
+		match len {
+			0 => None,
+			len => Some(&rest[..len]),
+		}
+	}
+
+	/// Consumes the next identifier.
+	pub fn identifier(&mut self) -> Result<&'a str> {
+		let identifier = self.peek_identifier().ok_or_else(|| self.error())?;
+		self.position += identifier.len();
+		Ok(identifier)
+	}
+
+	/// Consumes `keyword` if it's the next identifier.
+	pub fn eat_keyword(&mut self, keyword: &str) -> bool {
+		if self.peek_identifier() == Some(keyword) {
+			self.position += keyword.len();
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Fails unless the whole signature was consumed.
+	pub fn finish(&mut self) -> Result<()> {
+		self.skip_whitespace();
+		if self.rest().is_empty() {
+			Ok(())
+		} else {
+			Err(self.error())
+		}
+	}
+
+	/// Parses parenthesized, comma separated list of items.
+	fn list<T, F>(&mut self, mut item: F) -> Result<Vec<T>> where F: FnMut(&mut Self) -> Result<T> {
+		self.expect('(')?;
+		let mut items = vec![];
+		if self.eat(')') {
+			return Ok(items);
+		}
+
+		loop {
+			items.push(item(self)?);
+			if !self.eat(',') {
+				self.expect(')')?;
+				return Ok(items);
+			}
+		}
+	}
+
+	/// Parses parenthesized list of function params.
+	pub fn params(&mut self) -> Result<Vec<Param>> {
+		self.list(Parser::param)
+	}
+
+	/// Parses comma separated list of function params, without parentheses.
+	pub fn param_list(&mut self) -> Result<Vec<Param>> {
+		let mut params = vec![];
+		if self.peek().is_none() {
+			return Ok(params);
+		}
+
+		loop {
+			params.push(self.param()?);
+			if !self.eat(',') {
+				return Ok(params);
+			}
+		}
+	}
+
+	/// Parses parenthesized list of event params.
+	pub fn event_params(&mut self) -> Result<Vec<EventParam>> {
+		self.list(Parser::event_param)
+	}
+
+	/// Parses function param type, followed by optional data location and name.
+	pub fn param(&mut self) -> Result<Param> {
+		let EventParam { name, kind, internal_type, components, .. } = self.param_parts(false)?;
+		Ok(Param { name, kind, internal_type, components })
+	}
+
+	/// Parses event param type, followed by optional `indexed` keyword and name.
+	pub fn event_param(&mut self) -> Result<EventParam> {
+		self.param_parts(true)
+	}
+
+	/// Parses param, which is an event param only if `indexed` is allowed.
+	fn param_parts(&mut self, allow_indexed: bool) -> Result<EventParam> {
+		let (kind, internal_type, components) = match self.peek_identifier() {
+			Some("contract") | Some("enum") => {
+				let (kind, internal_type) = self.internal_param_type()?;
+				(kind, internal_type, vec![])
+			},
+			_ => {
+				let (kind, components) = self.param_type()?;
+				(kind, None, components)
+			},
+		};
+		let mut name = String::new();
+		let mut indexed = false;
+
+		while let Some(identifier) = self.peek_identifier() {
+			match identifier {
+				"indexed" if allow_indexed && name.is_empty() => indexed = true,
+				"memory" | "calldata" | "storage" | "payable" if name.is_empty() => {},
+				_ if name.is_empty() => name = identifier.to_owned(),
+				_ => return Err(self.error()),
+			}
+			self.position += identifier.len();
+		}
+
+		Ok(EventParam { name, kind, indexed, internal_type, components })
+	}
+
+	/// Parses Solidity type which is encoded as an elementary type, `contract IERC20` as `address`
+	/// and `enum Vault.State` as `uint8`, returning the encoded type and the Solidity type.
+	fn internal_param_type(&mut self) -> Result<(ParamType, Option<String>)> {
+		let keyword = self.identifier()?;
+		let mut kind = if keyword == "contract" { ParamType::Address } else { ParamType::Uint(8) };
+		let mut internal_type = format!("{} {}", keyword, self.identifier()?);
+		while self.rest().starts_with('.') {
+			self.position += 1;
+			internal_type.push('.');
+			internal_type.push_str(self.identifier()?);
+		}
+
+		let start = self.position;
+		kind = self.array_suffixes(kind)?;
+		internal_type.push_str(&self.input[start..self.position]);
+		Ok((kind, Some(internal_type)))
+	}
+
+	/// Parses type, either elementary like `uint256[2]` or a tuple like `(uint256 id, bool)[]`,
+	/// returning the tuple components with their names too.
+	fn param_type(&mut self) -> Result<(ParamType, Vec<TupleParam>)> {
+		self.eat_keyword("tuple");
+		if self.peek() != Some('(') {
+			let start = self.position;
+			let rest = self.rest();
+			let len = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '[' || c == ']')).unwrap_or(rest.len());
+			self.position += len;
+			let kind = Reader::read(&rest[..len]).map_err(|_| -> Error {
+				ErrorKind::InvalidNameAt(rest[..len].to_owned(), start, self.input.to_owned()).into()
+			})?;
+			return Ok((kind, vec![]));
+		}
+
+		let components: Vec<TupleParam> = self.params()?.into_iter()
+			.map(|p| TupleParam {
+				name: if p.name.is_empty() { None } else { Some(p.name) },
+				kind: p.kind,
+				internal_type: p.internal_type,
+				components: p.components,
+			})
+			.collect();
+		let kind = ParamType::Tuple(components.iter().map(|c| Box::new(c.kind.clone())).collect());
+		Ok((self.array_suffixes(kind)?, named_components(components)))
+	}
+
+	/// Parses array suffixes of type `kind`, e.g. `[2][]`.
+	fn array_suffixes(&mut self, mut kind: ParamType) -> Result<ParamType> {
+		while self.rest().starts_with('[') {
+			let end = self.rest().find(']').ok_or_else(|| self.error())?;
+			let len = &self.rest()[1..end];
+			kind = if len.is_empty() {
+				ParamType::Array(Box::new(kind))
+			} else {
+				let len = len.parse().map_err(|_| self.error())?;
+				ParamType::FixedArray(Box::new(kind), len)
+			};
+			self.position += end + 1;
+		}
+
+		Ok(kind)
+	}
+}
+
+/// Parses single param like `(address,uint256)[] orders` or `contract IERC20 token`.
+pub fn parse_param(signature: &str) -> Result<Param> {
+	let mut parser = Parser::new(signature);
+	let param = parser.param()?;
+	parser.finish()?;
+	Ok(param)
+}
+
+/// Parses single event param like `address indexed from` or `(address,uint256) order`.
+pub fn parse_event_param(signature: &str) -> Result<EventParam> {
+	let mut parser = Parser::new(signature);
+	let param = parser.event_param()?;
+	parser.finish()?;
+	Ok(param)
+}
+
+/// Parses function signature like `balanceOf(address owner) view returns (uint256)`.
+pub fn parse_function(signature: &str) -> Result<Function> {
+	let mut parser = Parser::new(signature);
+	parser.eat_keyword("function");
+	let name = parser.identifier()?.to_owned();
+	let inputs = parser.params()?;
+	let mut outputs = vec![];
+	let mut state_mutability = StateMutability::NonPayable;
+
+	loop {
+		if parser.eat_keyword("returns") {
+			outputs = parser.params()?;
+		} else if parser.eat_keyword("pure") {
+			state_mutability = StateMutability::Pure;
+		} else if parser.eat_keyword("view") || parser.eat_keyword("constant") {
+			state_mutability = StateMutability::View;
+		} else if parser.eat_keyword("payable") {
+			state_mutability = StateMutability::Payable;
+		} else if parser.eat_keyword("nonpayable") || parser.eat_keyword("external") || parser.eat_keyword("public") {
+			continue;
+		} else {
+			break;
+		}
+	}
+
+	parser.finish()?;
+
+	Ok(Function {
+		name,
+		inputs,
+		outputs,
+		state_mutability,
+	})
+}
+
+/// Parses event signature like `Transfer(address indexed from, address indexed to, uint256 value)`.
+pub fn parse_event(signature: &str) -> Result<Event> {
+	let mut parser = Parser::new(signature);
+	parser.eat_keyword("event");
+	let mut anonymous = parser.eat_keyword("anonymous");
+	let name = parser.identifier()?.to_owned();
+	let inputs = parser.event_params()?;
+	anonymous |= parser.eat_keyword("anonymous");
+	parser.finish()?;
+
+	Ok(Event {
+		name,
+		inputs,
+		anonymous,
+	})
+}
+
+/// Parses constructor signature like `constructor(address owner, string name) payable`.
+#[cfg(feature = "std")]
+pub fn parse_constructor(signature: &str) -> Result<Constructor> {
+	let mut parser = Parser::new(signature);
+	if !parser.eat_keyword("constructor") {
+		return Err(parser.error());
+	}
+	let inputs = parser.params()?;
+	while parser.eat_keyword("payable") || parser.eat_keyword("nonpayable") || parser.eat_keyword("public") {}
+	parser.finish()?;
+
+	Ok(Constructor { inputs })
+}
+
+/// Parses signature of the `fallback` or `receive` function, like `receive() external payable`.
+///
+/// Returns the function state mutability, the only property fallback and receive functions have.
+#[cfg(feature = "std")]
+pub fn parse_special_function(signature: &str, keyword: &str) -> Result<StateMutability> {
+	let mut parser = Parser::new(signature);
+	if !parser.eat_keyword(keyword) {
+		return Err(parser.error());
+	}
+	parser.params()?;
+	let mut state_mutability = StateMutability::NonPayable;
+	loop {
+		if parser.eat_keyword("payable") {
+			state_mutability = StateMutability::Payable;
+		} else if !parser.eat_keyword("nonpayable") && !parser.eat_keyword("external") {
+			break;
+		}
+	}
+	parser.finish()?;
+
+	Ok(state_mutability)
+}
+
+/// Parses error signature like `InsufficientBalance(uint256 available, uint256 required)`.
+pub fn parse_error(signature: &str) -> Result<CustomError> {
+	let mut parser = Parser::new(signature);
+	parser.eat_keyword("error");
+	let name = parser.identifier()?.to_owned();
+	let inputs = parser.params()?;
+	parser.finish()?;
+
+	Ok(CustomError { name, inputs })
+}
@@ -0,0 +1,111 @@
+//! Human readable ABI, as popularized by ethers.js.
+
+use std::collections::HashMap;
+use human_readable;
+use {Contract, Fallback, Receive, Result, ResultExt};
+
+/// Human readable ABI, a list of Solidity-like declarations such as
+/// `function transfer(address to, uint256 amount) returns (bool)` or
+/// `event Transfer(address indexed from, address indexed to, uint256 value)`.
+pub struct HumanReadableAbi;
+
+impl HumanReadableAbi {
+	/// Parses the declarations to a contract.
+	///
+	/// Each declaration starts with its kind: `function`, `event`, `error`, `constructor`,
+	/// `fallback` or `receive`. Tuples are written in the parenthesized form, e.g. `(address,uint256)[]`.
+	// parses a list of declarations, so it can't implement `FromStr`
+	#[allow(clippy::should_implement_trait)]
+	pub fn from_str(items: &[&str]) -> Result<Contract> {
+		let mut contract = Contract {
+			constructor: None,
+			functions: HashMap::new(),
+			events: HashMap::new(),
+			errors: HashMap::new(),
+			fallback: None,
+			receive: None,
+		};
+
+		for item in items {
+			HumanReadableAbi::parse_item(&mut contract, item.trim())
+				.chain_err(|| format!("Invalid human readable ABI item `{}`", item))?;
+		}
+
+		Ok(contract)
+	}
+
+	fn parse_item(contract: &mut Contract, item: &str) -> Result<()> {
+		let kind = item.split(|c: char| !c.is_ascii_alphanumeric()).next().unwrap_or_default();
+		match kind {
+			"function" => {
+				let function = human_readable::parse_function(item)?;
+				contract.functions.entry(function.name.clone()).or_default().push(function);
+			},
+			"event" => {
+				let event = human_readable::parse_event(item)?;
+				contract.events.entry(event.name.clone()).or_default().push(event);
+			},
+			"error" => {
+				let error = human_readable::parse_error(item)?;
+				contract.errors.insert(error.name.clone(), error);
+			},
+			"constructor" => contract.constructor = Some(human_readable::parse_constructor(item)?),
+			"fallback" => contract.fallback = Some(Fallback { state_mutability: human_readable::parse_special_function(item, kind)? }),
+			"receive" => contract.receive = Some(Receive { state_mutability: human_readable::parse_special_function(item, kind)? }),
+			_ => bail!("Unknown kind `{}`, expected `function`, `event`, `error`, `constructor`, `fallback` or `receive`", kind),
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {Contract, HumanReadableAbi, ParamType, StateMutability};
+
+	#[test]
+	fn erc20() {
+		let contract = HumanReadableAbi::from_str(&[
+			"function approve(address _spender, uint256 _value) returns (bool success)",
+			"function totalSupply() view returns (uint256 total)",
+			"function transferFrom(address _from, address _to, uint256 _value) returns (bool success)",
+			"function balanceOf(address _owner) view returns (uint256 balance)",
+			"function transfer(address _to, uint256 _value) returns (bool success)",
+			"function allowance(address _owner, address _spender) view returns (uint256 remaining)",
+			"event Transfer(address indexed from, address indexed to, uint256 value)",
+			"event Approval(address indexed owner, address indexed spender, uint256 value)",
+		]).unwrap();
+
+		let expected = Contract::from_json(include_str!("../../res/eip20.abi")).unwrap();
+		assert_eq!(contract, expected);
+	}
+
+	#[test]
+	fn constructor_error_fallback_receive_and_tuples() {
+		let contract = HumanReadableAbi::from_str(&[
+			"constructor(address owner, (uint256,bool) config) payable",
+			"  function swap((address token, uint24 fee)[] path) external payable returns (uint256)",
+			"error Unauthorized(address caller)",
+			"fallback() external",
+			"receive() external payable",
+		]).unwrap();
+
+		let constructor = contract.constructor().unwrap();
+		assert_eq!(constructor.inputs[1].kind, ParamType::Tuple(tuple_components![Box::new(ParamType::Uint(256)), Box::new(ParamType::Bool)]));
+		let swap = contract.function("swap").unwrap();
+		assert_eq!(swap.canonical_signature(), "swap((address,uint24)[])");
+		assert_eq!(swap.state_mutability, StateMutability::Payable);
+		assert_eq!(contract.fallback().unwrap().state_mutability, StateMutability::NonPayable);
+		assert!(contract.receive().unwrap().state_mutability.is_payable());
+		assert_eq!(contract.error("Unauthorized").unwrap().canonical_signature(), "Unauthorized(address)");
+	}
+
+	#[test]
+	fn invalid_items() {
+		let err = HumanReadableAbi::from_str(&["modifier onlyOwner()"]).unwrap_err();
+		assert_eq!(format!("{}", err), "Invalid human readable ABI item `modifier onlyOwner()`");
+		assert!(HumanReadableAbi::from_str(&["transfer(address,uint256)"]).is_err());
+		assert!(HumanReadableAbi::from_str(&["function transfer(address"]).is_err());
+		assert!(HumanReadableAbi::from_str(&["constructor(address) view"]).is_err());
+	}
+}
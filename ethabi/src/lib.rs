@@ -1,15 +1,50 @@
 //! Ethereum ABI encoding decoding library.
+//!
+//! Calling a contract function takes the contract ABI, the function name and the input values:
+//!
+//! ```
+//! # extern crate ethabi;
+//! # use ethabi::{Contract, Token, encode, encode_function_call, decode_function_output};
+//! # fn main() {
+//! let abi = r#"[{
+//!     "type": "function",
+//!     "name": "transfer",
+//!     "inputs": [{ "name": "to", "type": "address" }, { "name": "amount", "type": "uint256" }],
+//!     "outputs": [{ "name": "success", "type": "bool" }]
+//! }]"#;
+//! let contract = Contract::from_json(abi).unwrap();
+//!
+//! let to = Token::Address([0x11; 20].into());
+//! let call_data = encode_function_call(&contract, "transfer", &[to, Token::Uint(100.into())]).unwrap();
+//! assert_eq!(call_data[..4], [0xa9, 0x05, 0x9c, 0xbb]);
+//!
+//! // output returned by `eth_call`
+//! let output = encode(&[Token::Bool(true)]);
+//! let decoded = decode_function_output(&contract, "transfer", &output).unwrap();
+//! assert_eq!(decoded, vec![("success".to_owned(), Token::Bool(true))]);
+//! # }
+//! ```
+//!
+//! Without the default `std` feature the library is `no_std` and depends only on `alloc`.
 
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg_attr(not(feature = "std"), macro_use)]
+extern crate alloc;
 
 extern crate rustc_hex as hex;
 extern crate serde;
+#[cfg_attr(test, macro_use)]
 extern crate serde_json;
 extern crate tiny_keccak;
 
 #[macro_use]
 extern crate serde_derive;
 
+#[cfg(feature = "std")]
 #[macro_use]
 extern crate error_chain;
 
@@ -19,36 +54,126 @@ extern crate hex_literal;
 
 extern crate ethereum_types;
 
+#[cfg(feature = "proptest")]
+extern crate proptest;
+
+#[cfg(feature = "smallvec")]
+pub extern crate smallvec;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
+/// Creates `TupleComponents` like `vec!`, whether or not they are stored in a `SmallVec`.
+///
+/// ```
+/// # #[macro_use] extern crate ethabi;
+/// # use ethabi::ParamType;
+/// # fn main() {
+/// let pair = ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]);
+/// assert_eq!(pair.to_string(), "(address,bool)");
+/// # }
+/// ```
+#[cfg(not(feature = "smallvec"))]
+#[macro_export]
+macro_rules! tuple_components {
+	($($x:tt)*) => { vec![$($x)*] };
+}
+
+/// Creates `TupleComponents` like `vec!`, whether or not they are stored in a `SmallVec`.
+#[cfg(feature = "smallvec")]
+#[macro_export]
+macro_rules! tuple_components {
+	($($x:tt)*) => { $crate::smallvec::smallvec![$($x)*] };
+}
+
+// first, so that `bail!` is available to other modules in `no_std` builds
+#[cfg_attr(not(feature = "std"), macro_use)]
+mod errors;
+
+#[cfg(feature = "std")]
+pub mod eip712;
 pub mod param_type;
+#[cfg(feature = "proptest")]
+pub mod proptest_strategies;
+#[cfg(feature = "std")]
+pub mod selector_registry;
 pub mod token;
+pub mod utils;
+#[cfg(feature = "std")]
+mod artifact;
 mod constructor;
+#[cfg(feature = "std")]
 mod contract;
+#[cfg(feature = "std")]
+mod contract_registry;
+mod custom_error;
+mod decoded_params;
 mod decoder;
 mod encoder;
-mod errors;
 mod event;
 mod event_param;
 mod filter;
 mod function;
+mod human_readable;
+#[cfg(feature = "std")]
+mod human_readable_abi;
 mod log;
+#[cfg(feature = "std")]
 mod operation;
 mod param;
+mod revert;
 mod signature;
+mod special_function;
+mod state_mutability;
+mod tuple_param;
+mod type_mapper;
 mod util;
 
-pub use param_type::ParamType;
+pub use param_type::{ParamType, TupleComponents};
+#[cfg(feature = "std")]
+pub use artifact::Artifact;
 pub use constructor::Constructor;
-pub use contract::{Contract, Functions, Events};
-pub use token::Token;
+pub use custom_error::CustomError;
+pub use decoded_params::DecodedParams;
+#[cfg(feature = "std")]
+pub use contract::{AbiWarning, AbiWarningKind, Contract, ContractDiff, Functions, Events, encode_function_call, decode_function_output, encode_log, decode_log};
+#[cfg(feature = "std")]
+pub use contract_registry::ContractRegistry;
+pub use token::{Token, BorrowedToken, TokenWithType, TokenSeed};
 pub use errors::{Error, ErrorKind, Result, ResultExt};
-pub use encoder::encode;
-pub use decoder::decode;
+#[cfg(not(feature = "std"))]
+pub use errors::StdError;
+pub use encoder::{AbiEncoder, encode, encode_with_types, encode_packed, encode_function_input, encode_to_hex, encoded_size, pack_tokens, Pack};
+#[cfg(feature = "std")]
+pub use encoder::encode_to_writer;
+pub use decoder::{decode, decode_with_options, decode_with_names, decode_many, decode_borrowed, decode_borrowed_with_options, decode_strict, decode_from_hex, decode_function_input, DecodeOptions};
 pub use filter::{Topic, TopicFilter, RawTopicFilter};
 pub use function::Function;
+#[cfg(feature = "std")]
+pub use human_readable_abi::HumanReadableAbi;
 pub use param::Param;
+pub use revert::{decode_revert, RevertReason};
+#[cfg(feature = "std")]
+pub use selector_registry::SelectorRegistry;
 pub use log::{Log, RawLog, LogParam, ParseLog, LogFilter};
 pub use event::Event;
 pub use event_param::EventParam;
+pub use special_function::{Fallback, Receive};
+pub use state_mutability::StateMutability;
+pub use tuple_param::TupleParam;
+pub use type_mapper::{TypeMapper, DefaultTypeMapper, default_rust_type};
+pub use utils::{to_checksum_address, from_checksum_address};
+
+/// Items of the standard prelude missing from `core`.
+#[cfg(not(feature = "std"))]
+mod no_std_prelude {
+	pub use alloc::borrow::ToOwned;
+	pub use alloc::boxed::Box;
+	pub use alloc::string::{String, ToString};
+	pub use alloc::vec::Vec;
+}
+
+#[cfg(not(feature = "std"))]
+use no_std_prelude::Vec;
 
 /// ABI address.
 pub type Address = ethereum_types::Address;
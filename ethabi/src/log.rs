@@ -1,4 +1,6 @@
 use {Hash, Token, Bytes, Result, TopicFilter};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Common filtering functions that are available for any event.
 pub trait LogFilter {
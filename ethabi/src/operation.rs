@@ -4,7 +4,7 @@ use serde::{Deserialize, Deserializer};
 use serde::de::{Error as SerdeError};
 use serde_json::Value;
 use serde_json::value::from_value;
-use {Function, Constructor, Event};
+use {Function, Constructor, CustomError, Event, Fallback, Receive};
 
 /// Operation type.
 #[derive(Clone, Debug, PartialEq)]
@@ -15,8 +15,12 @@ pub enum Operation {
 	Function(Function),
 	/// Contract event.
 	Event(Event),
-	/// Fallback, ignored.
-	Fallback,
+	/// Contract custom error.
+	Error(CustomError),
+	/// Contract fallback function.
+	Fallback(Fallback),
+	/// Contract receive function.
+	Receive(Receive),
 }
 
 impl<'a> Deserialize<'a> for Operation {
@@ -44,7 +48,9 @@ impl<'a> Deserialize<'a> for Operation {
 				sanitize_name(&mut e.name);
 				Operation::Event(e)
 			}),
-			"fallback" => Ok(Operation::Fallback),
+			"error" => from_value(v).map(Operation::Error),
+			"fallback" => from_value(v).map(Operation::Fallback),
+			"receive" => from_value(v).map(Operation::Receive),
 			_ => Err(SerdeError::custom("Invalid operation type.")),
 		};
 		result.map_err(|e| D::Error::custom(e.to_string()))
@@ -55,7 +61,7 @@ impl<'a> Deserialize<'a> for Operation {
 mod tests {
 	use serde_json;
 	use super::Operation;
-	use {Function, Param, ParamType};
+	use {Function, Param, ParamType, StateMutability};
 
 	#[test]
 	fn deserialize_operation() {
@@ -77,10 +83,12 @@ mod tests {
 				Param {
 					name: "a".to_owned(),
 					kind: ParamType::Address,
+					internal_type: None,
+					components: vec![],
 				}
 			],
 			outputs: vec![],
-			constant: false,
+			state_mutability: StateMutability::NonPayable,
 		}));
 	}
 
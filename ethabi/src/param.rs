@@ -1,20 +1,73 @@
 //! Function param.
-use ParamType;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tuple_param::AbiType;
+use human_readable;
+use {Error, ParamType, TupleParam};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Function param.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Param {
 	/// Param name.
 	pub name: String,
 	/// Param type.
-	#[serde(rename="type")]
 	pub kind: ParamType,
+	/// Solidity type of the param, e.g. `struct Pool.Key` or `enum State`, if known.
+	pub internal_type: Option<String>,
+	/// Components of the param type if it is a tuple or an array of tuples, keeping their names
+	/// and internal types. Empty for other types and if no component has a name or internal type.
+	pub components: Vec<TupleParam>,
+}
+
+impl Param {
+	/// Parses param from its human readable form, a type followed by an optional data location
+	/// and name, e.g. `uint256 amount`, `bytes32[] calldata hashes` or `(address,uint256) order`.
+	///
+	/// `contract` and `enum` types are parsed to `address` and `uint8` respectively, keeping the
+	/// Solidity type as the internal type, e.g. `contract IERC20 token` or `enum Vault.State[] states`.
+	pub fn from_str_signature(signature: &str) -> Result<Param, Error> {
+		human_readable::parse_param(signature)
+	}
+}
+
+/// Param as found in the ABI json.
+#[derive(Serialize, Deserialize)]
+struct AbiParam {
+	name: String,
+	#[serde(flatten)]
+	kind: AbiType,
+	#[serde(rename = "internalType", default, skip_serializing_if = "Option::is_none")]
+	internal_type: Option<String>,
+}
+
+impl<'a> Deserialize<'a> for Param {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+		let abi = AbiParam::deserialize(deserializer)?;
+		let (kind, components) = abi.kind.deserialize_parts()?;
+		Ok(Param {
+			name: abi.name,
+			kind,
+			internal_type: abi.internal_type,
+			components,
+		})
+	}
+}
+
+impl Serialize for Param {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		AbiParam {
+			name: self.name.clone(),
+			kind: AbiType::new(&self.kind, &self.components),
+			internal_type: self.internal_type.clone(),
+		}.serialize(serializer)
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use serde_json;
-	use {Param, ParamType};
+	use {Param, ParamType, TupleParam};
 
 	#[test]
 	fn param_deserialization() {
@@ -28,6 +81,104 @@ mod tests {
 		assert_eq!(deserialized, Param {
 			name: "foo".to_owned(),
 			kind: ParamType::Address,
+			internal_type: None,
+			components: vec![],
 		});
 	}
+
+	#[test]
+	fn tuple_param_serialization() {
+		let s = r#"{
+			"name": "foo",
+			"type": "tuple[]",
+			"components": [
+				{ "name": "a", "type": "address" },
+				{ "name": "b", "type": "tuple", "components": [{ "name": "c", "type": "bool" }] }
+			]
+		}"#;
+
+		let deserialized: Param = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, Param {
+			name: "foo".to_owned(),
+			kind: "(address,(bool))[]".parse().unwrap(),
+			internal_type: None,
+			components: vec![
+				TupleParam { name: Some("a".to_owned()), kind: ParamType::Address, internal_type: None, components: vec![] },
+				TupleParam {
+					name: Some("b".to_owned()),
+					kind: "(bool)".parse().unwrap(),
+					internal_type: None,
+					components: vec![TupleParam { name: Some("c".to_owned()), kind: ParamType::Bool, internal_type: None, components: vec![] }],
+				},
+			],
+		});
+
+		let serialized = serde_json::to_string(&deserialized).unwrap();
+		assert_eq!(serialized, r#"{"name":"foo","type":"tuple[]","components":[{"name":"a","type":"address"},{"name":"b","type":"tuple","components":[{"name":"c","type":"bool"}]}]}"#);
+		assert_eq!(serde_json::from_str::<Param>(&serialized).unwrap(), deserialized);
+
+		// without components, tuple components are unnamed
+		let param = Param { components: vec![], ..deserialized };
+		let serialized = serde_json::to_string(&param).unwrap();
+		assert_eq!(serialized, r#"{"name":"foo","type":"tuple[]","components":[{"type":"address"},{"type":"tuple","components":[{"type":"bool"}]}]}"#);
+		assert_eq!(serde_json::from_str::<Param>(&serialized).unwrap(), param);
+	}
+
+	#[test]
+	fn internal_type_serialization() {
+		let s = r#"{
+			"internalType": "struct Pool.Key",
+			"name": "key",
+			"type": "tuple",
+			"components": [
+				{ "internalType": "contract IERC20", "name": "token", "type": "address" },
+				{ "internalType": "uint24", "name": "fee", "type": "uint24" }
+			]
+		}"#;
+
+		let deserialized: Param = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.kind, "(address,uint24)".parse().unwrap());
+		assert_eq!(deserialized.internal_type, Some("struct Pool.Key".to_owned()));
+		assert_eq!(deserialized.components[0].internal_type, Some("contract IERC20".to_owned()));
+
+		let serialized = serde_json::to_value(&deserialized).unwrap();
+		assert_eq!(serialized, serde_json::from_str::<serde_json::Value>(s).unwrap());
+		assert_eq!(serde_json::from_value::<Param>(serialized).unwrap(), deserialized);
+	}
+
+	#[test]
+	fn from_str_signature() {
+		fn param(signature: &str) -> (String, String, Option<String>) {
+			let param = Param::from_str_signature(signature).unwrap();
+			(param.name, param.kind.to_string(), param.internal_type)
+		}
+
+		assert_eq!(param("address to"), ("to".to_owned(), "address".to_owned(), None));
+		assert_eq!(param("uint256"), ("".to_owned(), "uint256".to_owned(), None));
+		assert_eq!(param("  uint256   amount "), ("amount".to_owned(), "uint256".to_owned(), None));
+		assert_eq!(param("bytes32[] calldata hashes"), ("hashes".to_owned(), "bytes32[]".to_owned(), None));
+		assert_eq!(param("string memory"), ("".to_owned(), "string".to_owned(), None));
+		assert_eq!(param("address payable owner"), ("owner".to_owned(), "address".to_owned(), None));
+		assert_eq!(param("int8[2][] matrix"), ("matrix".to_owned(), "int8[2][]".to_owned(), None));
+		assert_eq!(param("(address,uint256) order"), ("order".to_owned(), "(address,uint256)".to_owned(), None));
+		assert_eq!(param("tuple(address maker, uint256 amount)[2] orders"), ("orders".to_owned(), "(address,uint256)[2]".to_owned(), None));
+		assert_eq!(
+			param("((bytes32[],(bool,string)[])[3],bytes) nested"),
+			("nested".to_owned(), "((bytes32[],(bool,string)[])[3],bytes)".to_owned(), None)
+		);
+		assert_eq!(param("()"), ("".to_owned(), "()".to_owned(), None));
+		assert_eq!(param("contract IERC20 token"), ("token".to_owned(), "address".to_owned(), Some("contract IERC20".to_owned())));
+		assert_eq!(
+			param("enum Vault.State[] memory states"),
+			("states".to_owned(), "uint8[]".to_owned(), Some("enum Vault.State[]".to_owned()))
+		);
+
+		assert!(Param::from_str_signature("").is_err());
+		assert!(Param::from_str_signature("uint7 amount").is_err());
+		assert!(Param::from_str_signature("address to from").is_err());
+		assert!(Param::from_str_signature("address to,").is_err());
+		assert!(Param::from_str_signature("(address,uint256 order").is_err());
+		assert!(Param::from_str_signature("address indexed to").is_err());
+		assert!(Param::from_str_signature("contract").is_err());
+	}
 }
@@ -1,7 +1,9 @@
-use std::fmt;
+use core::fmt;
 use serde::{Deserialize, Deserializer};
 use serde::de::{Error as SerdeError, Visitor};
 use super::{ParamType, Reader};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 impl<'a> Deserialize<'a> for ParamType {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
@@ -3,8 +3,9 @@
 mod deserialize;
 mod param_type;
 mod reader;
+mod serialize;
 mod writer;
 
-pub use self::param_type::ParamType;
+pub use self::param_type::{ParamType, TupleComponents};
 pub use self::writer::Writer;
 pub use self::reader::Reader;
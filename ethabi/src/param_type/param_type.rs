@@ -1,10 +1,28 @@
 //! Function and event param types.
 
-use std::fmt;
-use super::Writer;
+use core::{fmt, slice};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::str::FromStr;
+use super::{Writer, Reader};
+use Error;
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Components of `ParamType::Tuple`, created with `tuple_components!`.
+#[cfg(not(feature = "smallvec"))]
+pub type TupleComponents = Vec<Box<ParamType>>;
+
+/// Components of `ParamType::Tuple`, created with `tuple_components!`.
+///
+/// Up to 8 components are stored inline, saving an allocation for most tuples. This makes
+/// every `ParamType` larger though, 80 bytes instead of 24 on 64 bit targets, see
+/// `benches/param_type.rs` to compare.
+#[cfg(feature = "smallvec")]
+pub type TupleComponents = ::smallvec::SmallVec<[Box<ParamType>; 8]>;
 
 /// Function and event param types.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ParamType {
 	/// Address.
 	Address,
@@ -24,6 +42,8 @@ pub enum ParamType {
 	FixedBytes(usize),
 	/// Array with fixed size.
 	FixedArray(Box<ParamType>, usize),
+	/// Tuple containing different types
+	Tuple(TupleComponents),
 }
 
 impl fmt::Display for ParamType {
@@ -32,13 +52,227 @@ impl fmt::Display for ParamType {
 	}
 }
 
+/// Scalars are ordered before composites, types of the same kind by their size
+/// and composites by their components.
+impl Ord for ParamType {
+	fn cmp(&self, other: &Self) -> Ordering {
+		match (self, other) {
+			(ParamType::Int(a), ParamType::Int(b)) |
+			(ParamType::Uint(a), ParamType::Uint(b)) |
+			(ParamType::FixedBytes(a), ParamType::FixedBytes(b)) => a.cmp(b),
+			(ParamType::FixedArray(a, a_len), ParamType::FixedArray(b, b_len)) => a.cmp(b).then(a_len.cmp(b_len)),
+			(ParamType::Array(a), ParamType::Array(b)) => a.cmp(b),
+			(ParamType::Tuple(a), ParamType::Tuple(b)) => a.cmp(b),
+			_ => self.rank().cmp(&other.rank()),
+		}
+	}
+}
+
+impl PartialOrd for ParamType {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl FromStr for ParamType {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Reader::read(s)
+	}
+}
+
+impl<'a> TryFrom<&'a str> for ParamType {
+	type Error = Error;
+
+	fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+		Reader::read(s)
+	}
+}
+
+impl TryFrom<String> for ParamType {
+	type Error = Error;
+
+	fn try_from(s: String) -> Result<Self, Self::Error> {
+		Reader::read(&s)
+	}
+}
+
 impl ParamType {
+	/// Returns whether the type is dynamic, i.e. its encoding is placed in the tail
+	/// and referenced from the head by an offset.
+	///
+	/// `bytes`, `string` and `T[]` are always dynamic, `T[k]` and tuples are dynamic
+	/// if any of their components is dynamic, all the other types are static.
+	pub fn is_dynamic(&self) -> bool {
+		match *self {
+			ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+			ParamType::FixedArray(ref param, _) => param.is_dynamic(),
+			ParamType::Tuple(ref params) => params.iter().any(|p| p.is_dynamic()),
+			ParamType::Address | ParamType::Int(_) | ParamType::Uint(_) | ParamType::Bool | ParamType::FixedBytes(_) => false,
+		}
+	}
+
+	/// Returns whether a value of this type can be used where `other` is declared, i.e. it
+	/// converts to `other` without losing information.
+	///
+	/// Unsigned and signed integers widen to integers of the same signedness with at least
+	/// as many bits, unsigned integers also to signed integers with more bits. `bytesN`
+	/// widens to `bytesM` with `M >= N`. Arrays and tuples are compatible if their components
+	/// are pairwise compatible, and fixed size arrays have the same length. Other types are
+	/// only compatible with themselves.
+	pub fn is_compatible_with(&self, other: &ParamType) -> bool {
+		match (self, other) {
+			(ParamType::Uint(from), ParamType::Uint(to)) | (ParamType::Int(from), ParamType::Int(to)) => from <= to,
+			(ParamType::Uint(from), ParamType::Int(to)) => from < to,
+			(ParamType::FixedBytes(from), ParamType::FixedBytes(to)) => from <= to,
+			(ParamType::Array(from), ParamType::Array(to)) => from.is_compatible_with(to),
+			(ParamType::FixedArray(from, from_len), ParamType::FixedArray(to, to_len)) =>
+				from_len == to_len && from.is_compatible_with(to),
+			(ParamType::Tuple(from), ParamType::Tuple(to)) =>
+				from.len() == to.len() && from.iter().zip(to.iter()).all(|(from, to)| from.is_compatible_with(to)),
+			(ParamType::Address, ParamType::Address) | (ParamType::Bool, ParamType::Bool) |
+			(ParamType::Bytes, ParamType::Bytes) | (ParamType::String, ParamType::String) => true,
+			_ => false,
+		}
+	}
+
+	/// Iterates over the direct components of composite types: the element type of arrays
+	/// and the params of tuples. Yields nothing for other types.
+	pub fn iter_components(&self) -> impl Iterator<Item = &ParamType> {
+		let components: &[Box<ParamType>] = match *self {
+			ParamType::Array(ref param) | ParamType::FixedArray(ref param, _) => slice::from_ref(param),
+			ParamType::Tuple(ref params) => params,
+			_ => &[],
+		};
+		components.iter().map(|p| &**p)
+	}
+
+	/// Mutable version of `iter_components`.
+	pub fn iter_components_mut(&mut self) -> impl Iterator<Item = &mut ParamType> {
+		let components: &mut [Box<ParamType>] = match *self {
+			ParamType::Array(ref mut param) | ParamType::FixedArray(ref mut param, _) => slice::from_mut(param),
+			ParamType::Tuple(ref mut params) => params,
+			_ => &mut [],
+		};
+		components.iter_mut().map(|p| &mut **p)
+	}
+
+	/// Consumes the type and returns its direct components, see `iter_components`.
+	pub fn into_components(self) -> Vec<Box<ParamType>> {
+		match self {
+			ParamType::Array(param) | ParamType::FixedArray(param, _) => vec![param],
+			ParamType::Tuple(params) => params.into_iter().collect(),
+			_ => vec![],
+		}
+	}
+
+	/// Returns a copy of the type with redundant single-element tuples replaced by their
+	/// only component, e.g. `((uint256))` becomes `uint256`. Array elements and the
+	/// components of other tuples are flattened recursively.
+	///
+	/// Note that `(address)` and `address` are not encoded the same way when dynamic,
+	/// so this is meant for comparing types only.
+	pub fn flatten_tuple(&self) -> ParamType {
+		match *self {
+			ParamType::Tuple(ref params) if params.len() == 1 => params[0].flatten_tuple(),
+			ParamType::Tuple(ref params) => {
+				ParamType::Tuple(params.iter().map(|p| Box::new(p.flatten_tuple())).collect())
+			},
+			ParamType::Array(ref param) => ParamType::Array(Box::new(param.flatten_tuple())),
+			ParamType::FixedArray(ref param, len) => ParamType::FixedArray(Box::new(param.flatten_tuple()), len),
+			ref other => other.clone(),
+		}
+	}
+
+	/// Position of the type kind in the canonical ordering.
+	fn rank(&self) -> u8 {
+		match *self {
+			ParamType::Address => 0,
+			ParamType::Bool => 1,
+			ParamType::Int(_) => 2,
+			ParamType::Uint(_) => 3,
+			ParamType::FixedBytes(_) => 4,
+			ParamType::Bytes => 5,
+			ParamType::String => 6,
+			ParamType::FixedArray(_, _) => 7,
+			ParamType::Array(_) => 8,
+			ParamType::Tuple(_) => 9,
+		}
+	}
+
+	/// Returns the Solidity declaration of a function param of the type, e.g. `uint256 amount`
+	/// or `address[] memory holders`, for code generation.
+	///
+	/// Reference types, i.e. `bytes`, `string`, arrays and tuples, are declared in `memory`.
+	/// Tuples are written in their canonical form, e.g. `(address,uint256) memory`, since their
+	/// struct names aren't known.
+	pub fn to_solidity_type(&self, name: Option<&str>) -> String {
+		let mut declaration = Writer::write(self);
+		if let ParamType::Bytes | ParamType::String | ParamType::Array(_) | ParamType::FixedArray(..) | ParamType::Tuple(_) = *self {
+			declaration.push_str(" memory");
+		}
+		if let Some(name) = name {
+			declaration.push(' ');
+			declaration.push_str(name);
+		}
+		declaration
+	}
+
+	/// Returns the number of bytes the type contributes to the head of the encoding.
+	///
+	/// Dynamic types are referenced from the head by a 32 bytes offset. Static types are
+	/// encoded in place, so their head size is the size of the whole encoding: 32 bytes for
+	/// every scalar and the sum of the components head sizes for `T[k]` and tuples.
+	pub fn head_size(&self) -> usize {
+		if self.is_dynamic() {
+			return 32;
+		}
+
+		match *self {
+			ParamType::FixedBytes(len) => 32 * len.div_ceil(32),
+			ParamType::FixedArray(ref param, len) => len * param.head_size(),
+			ParamType::Tuple(ref params) => params.iter().map(|p| p.head_size()).sum(),
+			_ => 32,
+		}
+	}
+
+	/// Returns the minimum number of bytes of the encoding of a value of the type, e.g. to
+	/// know how much data to wait for before decoding it from a stream.
+	///
+	/// That's the size of the whole encoding for static types. Dynamic types take their
+	/// 32 bytes head offset, plus the length of empty `bytes`, `string` and `T[]`, or the
+	/// minimum encodings of the components of `T[k]` and tuples.
+	pub fn minimum_encoding_length(&self) -> usize {
+		if !self.is_dynamic() {
+			return self.head_size();
+		}
+
+		let minimum_tail_length = match *self {
+			ParamType::FixedArray(ref param, len) => len * param.minimum_encoding_length(),
+			ParamType::Tuple(ref params) => params.iter().map(|p| p.minimum_encoding_length()).sum(),
+			_ => 32,
+		};
+		32 + minimum_tail_length
+	}
+
+	/// Returns the number of bytes of the encoding of a value of the type if it's bounded,
+	/// i.e. the type is static, `None` for dynamic types.
+	pub fn maximum_encoding_length(&self) -> Option<usize> {
+		if self.is_dynamic() {
+			None
+		} else {
+			Some(self.head_size())
+		}
+	}
+
     /// returns whether a zero length byte slice (`0x`) is
     /// a valid encoded form of this param type
     pub fn is_empty_bytes_valid_encoding(&self) -> bool {
         match self {
             ParamType::FixedBytes(len) => *len == 0,
-            ParamType::FixedArray(_, len) => *len == 0,
+            ParamType::FixedArray(param, len) => *len == 0 || param.is_empty_bytes_valid_encoding(),
+            ParamType::Tuple(params) => params.iter().all(|p| p.is_empty_bytes_valid_encoding()),
             _ => false,
         }
     }
@@ -46,7 +280,9 @@ impl ParamType {
 
 #[cfg(test)]
 mod tests {
-	use ParamType;
+	use std::collections::{BTreeSet, HashSet};
+	use std::convert::TryFrom;
+	use {ParamType, Error, Token, encode};
 
 	#[test]
 	fn test_param_type_display() {
@@ -60,5 +296,345 @@ mod tests {
 		assert_eq!(format!("{}", ParamType::Array(Box::new(ParamType::Bool))), "bool[]".to_owned());
 		assert_eq!(format!("{}", ParamType::FixedArray(Box::new(ParamType::String), 2)), "string[2]".to_owned());
 		assert_eq!(format!("{}", ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Bool))), 2)), "bool[][2]".to_owned());
+		assert_eq!(format!("{}", ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)])), "(address,bool)".to_owned());
+		assert_eq!(format!("{}", ParamType::Array(Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Uint(256))])))), "(address,uint256)[]".to_owned());
+	}
+
+	#[test]
+	fn test_param_type_from_str() {
+		assert_eq!("address".parse::<ParamType>().unwrap(), ParamType::Address);
+		assert_eq!("uint256[]".parse::<ParamType>().unwrap(), ParamType::Array(Box::new(ParamType::Uint(256))));
+		assert!("uint7".parse::<ParamType>().is_err());
+		assert_eq!(
+			"(address,uint256)".parse::<ParamType>().unwrap(),
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Uint(256))])
+		);
+	}
+
+	#[test]
+	fn test_param_type_try_from() {
+		assert_eq!(ParamType::try_from("bytes32").unwrap(), ParamType::FixedBytes(32));
+		assert_eq!(ParamType::try_from("bool[2]".to_owned()).unwrap(), ParamType::FixedArray(Box::new(ParamType::Bool), 2));
+		assert!(ParamType::try_from("foo").is_err());
+
+		fn read_types(types: &[&str]) -> Result<Vec<ParamType>, Error> {
+			let mut result = vec![];
+			for t in types {
+				result.push(ParamType::try_from(*t)?);
+			}
+			Ok(result)
+		}
+
+		assert_eq!(read_types(&["address", "string"]).unwrap(), vec![ParamType::Address, ParamType::String]);
+		assert!(read_types(&["address", "strin"]).is_err());
+	}
+
+	#[test]
+	fn test_param_type_hash() {
+		let mut set = HashSet::new();
+		set.insert(ParamType::Uint(256));
+		set.insert(ParamType::Uint(256));
+		set.insert(ParamType::Uint(8));
+		set.insert(ParamType::Array(Box::new(ParamType::Address)));
+		set.insert(ParamType::Array(Box::new(ParamType::Address)));
+		set.insert(ParamType::Array(Box::new(ParamType::Bool)));
+		set.insert(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]));
+		set.insert(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]));
+		set.insert(ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Address)]));
+
+		assert_eq!(set.len(), 6);
+		assert!(set.contains(&ParamType::Uint(8)));
+		assert!(!set.contains(&ParamType::Int(8)));
+	}
+
+	#[test]
+	fn test_param_type_ord() {
+		let set: BTreeSet<ParamType> = vec![
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Bool)]),
+			ParamType::Array(Box::new(ParamType::Uint(8))),
+			ParamType::FixedArray(Box::new(ParamType::Address), 3),
+			ParamType::FixedArray(Box::new(ParamType::Address), 2),
+			ParamType::String,
+			ParamType::Bytes,
+			ParamType::FixedBytes(32),
+			ParamType::FixedBytes(4),
+			ParamType::Uint(256),
+			ParamType::Uint(8),
+			ParamType::Int(64),
+			ParamType::Bool,
+			ParamType::Address,
+			ParamType::Address,
+		].into_iter().collect();
+
+		assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![
+			ParamType::Address,
+			ParamType::Bool,
+			ParamType::Int(64),
+			ParamType::Uint(8),
+			ParamType::Uint(256),
+			ParamType::FixedBytes(4),
+			ParamType::FixedBytes(32),
+			ParamType::Bytes,
+			ParamType::String,
+			ParamType::FixedArray(Box::new(ParamType::Address), 2),
+			ParamType::FixedArray(Box::new(ParamType::Address), 3),
+			ParamType::Array(Box::new(ParamType::Uint(8))),
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Bool)]),
+		]);
+
+		assert!(ParamType::Array(Box::new(ParamType::Address)) < ParamType::Array(Box::new(ParamType::Bool)));
+		assert!(ParamType::Tuple(tuple_components![Box::new(ParamType::Bool)]) < ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Bool)]));
+	}
+
+	#[test]
+	fn test_iter_components() {
+		assert_eq!(ParamType::Address.iter_components().count(), 0);
+		assert_eq!(ParamType::Bytes.iter_components().count(), 0);
+		assert_eq!(
+			ParamType::Array(Box::new(ParamType::Bool)).iter_components().collect::<Vec<_>>(),
+			vec![&ParamType::Bool]
+		);
+		assert_eq!(
+			ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Bool))), 3).iter_components().collect::<Vec<_>>(),
+			vec![&ParamType::Array(Box::new(ParamType::Bool))]
+		);
+		assert_eq!(
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::String)]).iter_components().collect::<Vec<_>>(),
+			vec![&ParamType::Address, &ParamType::String]
+		);
+		assert_eq!(ParamType::Tuple(tuple_components![]).iter_components().count(), 0);
+	}
+
+	#[test]
+	fn test_iter_components_mut() {
+		fn widen(param: &mut ParamType) {
+			if let ParamType::Uint(ref mut len) = *param {
+				*len = 256;
+			}
+			param.iter_components_mut().for_each(widen);
+		}
+
+		let mut param = ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Uint(8)),
+			Box::new(ParamType::Array(Box::new(ParamType::Uint(32)))),
+			Box::new(ParamType::Int(8)),
+		]);
+		widen(&mut param);
+
+		assert_eq!(param, ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Uint(256)),
+			Box::new(ParamType::Array(Box::new(ParamType::Uint(256)))),
+			Box::new(ParamType::Int(8)),
+		]));
+	}
+
+	#[test]
+	fn test_into_components() {
+		assert!(ParamType::Bool.into_components().is_empty());
+		assert_eq!(ParamType::Array(Box::new(ParamType::Bool)).into_components(), vec![Box::new(ParamType::Bool)]);
+		assert_eq!(ParamType::FixedArray(Box::new(ParamType::Bool), 2).into_components(), vec![Box::new(ParamType::Bool)]);
+
+		let components = ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]).into_components();
+		let reversed = ParamType::Tuple(components.into_iter().rev().collect());
+		assert_eq!(reversed, ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Address)]));
+	}
+
+	#[test]
+	fn test_flatten_tuple() {
+		let nested = ParamType::Tuple(tuple_components![Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Address)]))]);
+		assert_eq!(nested.flatten_tuple(), ParamType::Address);
+
+		let pair = ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]);
+		assert_eq!(pair.flatten_tuple(), pair);
+
+		let inner = ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Uint(256))])),
+			Box::new(ParamType::Array(Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Bytes)])))),
+		]);
+		assert_eq!(inner.flatten_tuple(), ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Uint(256)),
+			Box::new(ParamType::Array(Box::new(ParamType::Bytes))),
+		]));
+
+		assert_eq!(ParamType::Tuple(tuple_components![]).flatten_tuple(), ParamType::Tuple(tuple_components![]));
+	}
+
+	#[test]
+	fn test_to_solidity_type() {
+		let declaration = |kind: &str, name: Option<&str>| kind.parse::<ParamType>().unwrap().to_solidity_type(name);
+
+		assert_eq!(declaration("address", None), "address");
+		assert_eq!(declaration("address", Some("to")), "address to");
+		assert_eq!(declaration("uint256", Some("amount")), "uint256 amount");
+		assert_eq!(declaration("int8", None), "int8");
+		assert_eq!(declaration("bool", Some("approved")), "bool approved");
+		assert_eq!(declaration("bytes32", Some("hash")), "bytes32 hash");
+		assert_eq!(declaration("bytes", None), "bytes memory");
+		assert_eq!(declaration("bytes", Some("data")), "bytes memory data");
+		assert_eq!(declaration("string", Some("name")), "string memory name");
+		assert_eq!(declaration("address[]", None), "address[] memory");
+		assert_eq!(declaration("uint256[]", Some("amounts")), "uint256[] memory amounts");
+		assert_eq!(declaration("bytes32[2][]", Some("proofs")), "bytes32[2][] memory proofs");
+		assert_eq!(declaration("bool[3]", Some("flags")), "bool[3] memory flags");
+		assert_eq!(declaration("(address,uint256)", None), "(address,uint256) memory");
+		assert_eq!(declaration("(address,(bool,string))[]", Some("orders")), "(address,(bool,string))[] memory orders");
+	}
+
+	#[test]
+	fn test_is_compatible_with() {
+		let compatible = |from: &str, to: &str| {
+			let from: ParamType = from.parse().unwrap();
+			from.is_compatible_with(&to.parse().unwrap())
+		};
+
+		assert!(compatible("uint8", "uint8"));
+		assert!(compatible("uint8", "uint256"));
+		assert!(compatible("uint248", "uint256"));
+		assert!(!compatible("uint256", "uint8"));
+		assert!(!compatible("uint16", "uint8"));
+		assert!(compatible("int8", "int256"));
+		assert!(compatible("int256", "int256"));
+		assert!(!compatible("int256", "int128"));
+		assert!(compatible("uint8", "int16"));
+		assert!(compatible("uint248", "int256"));
+		assert!(!compatible("uint8", "int8"));
+		assert!(!compatible("uint256", "int256"));
+		assert!(!compatible("int8", "uint256"));
+		assert!(!compatible("int8", "uint8"));
+
+		assert!(compatible("bytes1", "bytes1"));
+		assert!(compatible("bytes3", "bytes32"));
+		assert!(!compatible("bytes3", "bytes2"));
+		assert!(!compatible("bytes32", "bytes31"));
+		assert!(!compatible("bytes3", "bytes"));
+		assert!(!compatible("bytes", "bytes32"));
+		assert!(!compatible("bytes32", "uint256"));
+		assert!(!compatible("uint160", "address"));
+		assert!(!compatible("address", "uint160"));
+		assert!(!compatible("bool", "uint8"));
+		assert!(!compatible("string", "bytes"));
+		assert!(compatible("address", "address"));
+		assert!(compatible("string", "string"));
+
+		assert!(compatible("uint8[]", "uint256[]"));
+		assert!(!compatible("uint256[]", "uint8[]"));
+		assert!(compatible("bytes4[2]", "bytes32[2]"));
+		assert!(!compatible("bytes4[2]", "bytes32[3]"));
+		assert!(!compatible("uint8[2]", "uint8[]"));
+		assert!(compatible("(uint8,bytes3,string)", "(uint256,bytes32,string)"));
+		assert!(compatible("(uint8,(int8,bool)[])[2]", "(uint16,(int64,bool)[])[2]"));
+		assert!(!compatible("(uint8,(int8,bool)[])[2]", "(uint16,(uint64,bool)[])[2]"));
+		assert!(!compatible("(uint8,bool)", "(uint8,bool,bool)"));
+		assert!(!compatible("(uint256,bool)", "(uint8,bool)"));
+		assert!(compatible("()", "()"));
+	}
+
+	#[test]
+	fn test_is_dynamic() {
+		assert!(!ParamType::Address.is_dynamic());
+		assert!(ParamType::Bytes.is_dynamic());
+		assert!(!ParamType::FixedBytes(32).is_dynamic());
+		assert!(!ParamType::Uint(256).is_dynamic());
+		assert!(!ParamType::Int(64).is_dynamic());
+		assert!(!ParamType::Bool.is_dynamic());
+		assert!(ParamType::String.is_dynamic());
+		assert!(ParamType::Array(Box::new(ParamType::Bool)).is_dynamic());
+		assert!(!ParamType::FixedArray(Box::new(ParamType::Uint(256)), 2).is_dynamic());
+		assert!(ParamType::FixedArray(Box::new(ParamType::String), 2).is_dynamic());
+		assert!(ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Bool))), 2).is_dynamic());
+		assert!(!ParamType::Tuple(tuple_components![]).is_dynamic());
+		assert!(!ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]).is_dynamic());
+		assert!(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bytes)]).is_dynamic());
+		assert!(ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Bool),
+			Box::new(ParamType::FixedArray(Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::String)])), 3)),
+		]).is_dynamic());
+		assert!(!ParamType::FixedArray(Box::new(ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Bool),
+			Box::new(ParamType::FixedArray(Box::new(ParamType::Address), 3)),
+		])), 2).is_dynamic());
+	}
+
+	#[test]
+	fn test_head_size() {
+		assert_eq!(ParamType::Address.head_size(), 32);
+		assert_eq!(ParamType::Bytes.head_size(), 32);
+		assert_eq!(ParamType::FixedBytes(32).head_size(), 32);
+		assert_eq!(ParamType::FixedBytes(3).head_size(), 32);
+		assert_eq!(ParamType::Uint(8).head_size(), 32);
+		assert_eq!(ParamType::Int(256).head_size(), 32);
+		assert_eq!(ParamType::Bool.head_size(), 32);
+		assert_eq!(ParamType::String.head_size(), 32);
+		assert_eq!(ParamType::Array(Box::new(ParamType::Address)).head_size(), 32);
+		assert_eq!(ParamType::FixedArray(Box::new(ParamType::Address), 3).head_size(), 96);
+		assert_eq!(ParamType::FixedArray(Box::new(ParamType::String), 3).head_size(), 32);
+		assert_eq!(ParamType::Tuple(tuple_components![]).head_size(), 0);
+		assert_eq!(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]).head_size(), 64);
+		assert_eq!(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bytes)]).head_size(), 32);
+	}
+
+	#[test]
+	fn test_head_size_of_nested_fixed_arrays_of_tuples() {
+		let static_tuple = ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Address),
+			Box::new(ParamType::FixedArray(Box::new(ParamType::Uint(256)), 2)),
+		]);
+		let dynamic_tuple = ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Address),
+			Box::new(ParamType::Bytes),
+		]);
+
+		assert_eq!(static_tuple.head_size(), 96);
+		assert_eq!(ParamType::FixedArray(Box::new(static_tuple.clone()), 2).head_size(), 192);
+		assert_eq!(ParamType::FixedArray(Box::new(ParamType::FixedArray(Box::new(static_tuple.clone()), 2)), 3).head_size(), 576);
+		assert_eq!(ParamType::FixedArray(Box::new(ParamType::FixedArray(Box::new(dynamic_tuple.clone()), 2)), 3).head_size(), 32);
+		assert_eq!(ParamType::Tuple(tuple_components![
+			Box::new(ParamType::FixedArray(Box::new(static_tuple), 2)),
+			Box::new(ParamType::Bool),
+		]).head_size(), 224);
+		assert_eq!(ParamType::Tuple(tuple_components![
+			Box::new(ParamType::FixedArray(Box::new(dynamic_tuple), 2)),
+			Box::new(ParamType::Bool),
+		]).head_size(), 32);
+	}
+
+	#[test]
+	fn test_encoding_length() {
+		// value of the type with the shortest encoding
+		fn shortest(kind: &ParamType) -> Token {
+			match *kind {
+				ParamType::Address => Token::Address(Default::default()),
+				ParamType::Bytes => Token::Bytes(vec![]),
+				ParamType::Int(_) => Token::Int(0.into()),
+				ParamType::Uint(_) => Token::Uint(0.into()),
+				ParamType::Bool => Token::Bool(false),
+				ParamType::String => Token::String(String::new()),
+				ParamType::Array(_) => Token::Array(vec![]),
+				ParamType::FixedBytes(len) => Token::FixedBytes(vec![0; len]),
+				ParamType::FixedArray(ref kind, len) => Token::FixedArray(vec![shortest(kind); len]),
+				ParamType::Tuple(ref kinds) => Token::Tuple(kinds.iter().map(|kind| shortest(kind)).collect()),
+			}
+		}
+
+		let cases = [
+			("address", 32, Some(32)),
+			("bytes7", 32, Some(32)),
+			("bool[3]", 96, Some(96)),
+			("(uint8,int256,bytes32)[2]", 192, Some(192)),
+			("((address,bool)[2],uint256)", 160, Some(160)),
+			("()", 0, Some(0)),
+			("bytes", 64, None),
+			("string[]", 64, None),
+			("string[2]", 32 + 2 * 64, None),
+			("(address,bytes)", 32 + 32 + 64, None),
+			("(uint256,(bool,string[])[1])", 32 + 64 + 32 + 32 + 64, None),
+		];
+
+		for &(kind, minimum, maximum) in &cases {
+			let kind: ParamType = kind.parse().unwrap();
+			assert_eq!(kind.minimum_encoding_length(), minimum, "{}", kind);
+			assert_eq!(kind.maximum_encoding_length(), maximum, "{}", kind);
+			assert_eq!(encode(&[shortest(&kind)]).len(), minimum, "{}", kind);
+		}
 	}
 }
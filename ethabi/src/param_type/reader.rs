@@ -1,4 +1,7 @@
-use {ParamType, Error, ErrorKind};
+use human_readable::Parser;
+use {ParamType, TupleComponents, Param, Error, ErrorKind};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Used to convert param type represented as a string to rust structure.
 pub struct Reader;
@@ -6,31 +9,95 @@ pub struct Reader;
 impl Reader {
 	/// Converts string to param type.
 	pub fn read(name: &str) -> Result<ParamType, Error> {
+		Reader::read_at(name, 0, name)
+	}
+
+	/// Converts comma separated list of types, like `address,(uint256,bool)[]`, to param types.
+	///
+	/// Types have to be in the canonical form, without whitespace. Empty string is an empty list.
+	pub fn read_tuple(list: &str) -> Result<Vec<ParamType>, Error> {
+		let mut types = vec![];
+		try!(Reader::read_list(list, 0, list, |kind| types.push(kind)));
+		Ok(types)
+	}
+
+	/// Converts comma separated list of named params, like `address to, uint256 amount`, to params.
+	///
+	/// Names are optional, whitespace around types and names is ignored. Empty string is an empty list.
+	pub fn read_params(list: &str) -> Result<Vec<Param>, Error> {
+		let mut parser = Parser::new(list);
+		let params = try!(parser.param_list());
+		try!(parser.finish());
+		Ok(params)
+	}
+
+	/// Converts `list`, found at byte `position` of the `full` type string, to param types,
+	/// passed to `push` in order, so that tuple components go straight to `TupleComponents`.
+	fn read_list<F>(list: &str, position: usize, full: &str, mut push: F) -> Result<(), Error> where F: FnMut(ParamType) {
+		if list.is_empty() {
+			return Ok(());
+		}
+
+		let mut nested = 0isize;
+		let mut last_item = 0;
+		for (i, ch) in list.char_indices() {
+			match ch {
+				'(' | '[' => {
+					nested += 1;
+				},
+				')' | ']' => {
+					nested -= 1;
+					if nested < 0 {
+						return Err(Reader::invalid_name(list, position, full));
+					}
+				},
+				',' if nested == 0 => {
+					push(try!(Reader::read_at(&list[last_item..i], position + last_item, full)));
+					last_item = i + 1;
+				},
+				_ => ()
+			}
+		}
+
+		if nested != 0 {
+			return Err(Reader::invalid_name(list, position, full));
+		}
+
+		push(try!(Reader::read_at(&list[last_item..], position + last_item, full)));
+		Ok(())
+	}
+
+	/// Converts `name`, found at byte `position` of the `full` type string, to param type.
+	fn read_at(name: &str, position: usize, full: &str) -> Result<ParamType, Error> {
 		// check if it is a fixed or dynamic array.
-		if let Some(']') = name.chars().last() {
+		if name.ends_with(']') {
+			// slice by byte offsets of `[` and `]`, which are always char boundaries
+			let open = match name.rfind('[') {
+				Some(open) => open,
+				None => return Err(Reader::invalid_name(name, position, full)),
+			};
+
 			// take number part
-			let num: String = name.chars()
-				.rev()
-				.skip(1)
-				.take_while(|c| *c != '[')
-				.collect::<String>()
-				.chars()
-				.rev()
-				.collect();
-
-			let count = name.chars().count();
+			let num = &name[open + 1..name.len() - 1];
 			if num.is_empty() {
 				// we already know it's a dynamic array!
-				let subtype = try!(Reader::read(&name[..count - 2]));
+				let subtype = try!(Reader::read_at(&name[..open], position, full));
 				return Ok(ParamType::Array(Box::new(subtype)));
 			} else {
 				// it's a fixed array.
-				let len = try!(usize::from_str_radix(&num, 10));
-				let subtype = try!(Reader::read(&name[..count - num.len() - 2]));
+				let len = try!(usize::from_str_radix(num, 10));
+				let subtype = try!(Reader::read_at(&name[..open], position, full));
 				return Ok(ParamType::FixedArray(Box::new(subtype), len));
 			}
 		}
 
+		// check if it is a tuple.
+		if name.starts_with('(') && name.ends_with(')') {
+			let mut components = TupleComponents::new();
+			try!(Reader::read_list(&name[1..name.len() - 1], position + 1, full, |kind| components.push(Box::new(kind))));
+			return Ok(ParamType::Tuple(components));
+		}
+
 		let result = match name {
 			"address" => ParamType::Address,
 			"bytes" => ParamType::Bytes,
@@ -39,29 +106,53 @@ impl Reader {
 			"int" => ParamType::Int(256),
 			"uint" => ParamType::Uint(256),
 			s if s.starts_with("int") => {
-				let len = try!(usize::from_str_radix(&s[3..], 10));
+				let len = try!(usize::from_str_radix(&s[3..], 10).map_err(|_| Reader::invalid_name(name, position, full)));
+				if !Reader::is_valid_int_width(len) {
+					return Err(Reader::invalid_name(name, position, full));
+				}
 				ParamType::Int(len)
 			},
 			s if s.starts_with("uint") => {
-				let len = try!(usize::from_str_radix(&s[4..], 10));
+				let len = try!(usize::from_str_radix(&s[4..], 10).map_err(|_| Reader::invalid_name(name, position, full)));
+				if !Reader::is_valid_int_width(len) {
+					return Err(Reader::invalid_name(name, position, full));
+				}
 				ParamType::Uint(len)
 			},
 			s if s.starts_with("bytes") => {
-				let len = try!(usize::from_str_radix(&s[5..], 10));
+				let len = try!(usize::from_str_radix(&s[5..], 10).map_err(|_| Reader::invalid_name(name, position, full)));
+				if !(1..=32).contains(&len) {
+					return Err(Reader::invalid_name(name, position, full));
+				}
 				ParamType::FixedBytes(len)
 			},
 			_ => {
-				return Err(ErrorKind::InvalidName(name.to_owned()).into());
+				return Err(Reader::invalid_name(name, position, full));
 			}
 		};
 
 		Ok(result)
 	}
+
+	/// Checks that `intN` / `uintN` width is a multiple of 8 in range `8..=256`.
+	fn is_valid_int_width(len: usize) -> bool {
+		len.is_multiple_of(8) && (8..=256).contains(&len)
+	}
+
+	/// Creates an error for invalid `name`. If `name` is only a part of the `full` type string,
+	/// the error points at its position.
+	fn invalid_name(name: &str, position: usize, full: &str) -> Error {
+		if name == full {
+			ErrorKind::InvalidName(name.to_owned()).into()
+		} else {
+			ErrorKind::InvalidNameAt(name.to_owned(), position, full.to_owned()).into()
+		}
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use ParamType;
+	use {ParamType, Param, ErrorKind, TupleParam};
 	use super::Reader;
 
 	#[test]
@@ -75,6 +166,205 @@ mod tests {
 		assert_eq!(Reader::read("uint").unwrap(), ParamType::Uint(256));
 		assert_eq!(Reader::read("int32").unwrap(), ParamType::Int(32));
 		assert_eq!(Reader::read("uint32").unwrap(), ParamType::Uint(32));
+		assert_eq!(Reader::read("int8").unwrap(), ParamType::Int(8));
+		assert_eq!(Reader::read("uint256").unwrap(), ParamType::Uint(256));
+		assert_eq!(Reader::read("bytes1").unwrap(), ParamType::FixedBytes(1));
+	}
+
+	#[test]
+	fn test_read_tuple_param() {
+		assert_eq!(Reader::read("()").unwrap(), ParamType::Tuple(tuple_components![]));
+		assert_eq!(Reader::read("(address)").unwrap(), ParamType::Tuple(tuple_components![Box::new(ParamType::Address)]));
+		assert_eq!(Reader::read("(address,bool)").unwrap(), ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]));
+		assert_eq!(Reader::read("((address,bool),uint256)").unwrap(), ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)])),
+			Box::new(ParamType::Uint(256)),
+		]));
+		assert_eq!(Reader::read("(uint256,(bytes,(bool,string)))").unwrap(), ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Uint(256)),
+			Box::new(ParamType::Tuple(tuple_components![
+				Box::new(ParamType::Bytes),
+				Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::String)])),
+			])),
+		]));
+	}
+
+	#[test]
+	fn test_read_tuple_array_param() {
+		assert_eq!(
+			Reader::read("(address,uint256)[]").unwrap(),
+			ParamType::Array(Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Uint(256))])))
+		);
+		assert_eq!(
+			Reader::read("(address[],bool[2])[3]").unwrap(),
+			ParamType::FixedArray(Box::new(ParamType::Tuple(tuple_components![
+				Box::new(ParamType::Array(Box::new(ParamType::Address))),
+				Box::new(ParamType::FixedArray(Box::new(ParamType::Bool), 2)),
+			])), 3)
+		);
+		assert_eq!(
+			Reader::read("((address,bool)[],uint8)").unwrap(),
+			ParamType::Tuple(tuple_components![
+				Box::new(ParamType::Array(Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)])))),
+				Box::new(ParamType::Uint(8)),
+			])
+		);
+	}
+
+	#[test]
+	fn test_read_invalid_tuple_param() {
+		assert!(Reader::read("(address,)").is_err());
+		assert!(Reader::read("(,address)").is_err());
+		assert!(Reader::read("(address,(bool)").is_err());
+		assert!(Reader::read("(address))(").is_err());
+		assert!(Reader::read("(address,bool))").is_err());
+	}
+
+	#[test]
+	fn test_read_unbalanced_array_param() {
+		assert!(Reader::read("]").is_err());
+		assert!(Reader::read("1]").is_err());
+		assert!(Reader::read("address]").is_err());
+		assert!(Reader::read("(address]").is_err());
+	}
+
+	#[test]
+	fn test_read_multibyte_param() {
+		assert!(Reader::read("é[]").is_err());
+		assert!(Reader::read("address😀[2]").is_err());
+		assert!(Reader::read("👍[]").is_err());
+		assert!(Reader::read("uint256[😀]").is_err());
+		assert!(Reader::read("(bool,🦀)[]").is_err());
+		assert!(Reader::read("[inintЛ][]tЛ][]*][]ۛ]").is_err());
+		assert_eq!(
+			Reader::read("👍👍[]").unwrap_err().to_string(),
+			"Invalid name `👍👍` at position 0 of `👍👍[]`"
+		);
+	}
+
+	#[test]
+	fn test_read_multibyte_tuple_param() {
+		assert_eq!(
+			Reader::read("(address,\u{1F600},bool)").unwrap_err().to_string(),
+			"Invalid name `\u{1F600}` at position 9 of `(address,\u{1F600},bool)`"
+		);
+		assert_eq!(
+			Reader::read("(\u{e9}\u{e9},bool,uint7)").unwrap_err().to_string(),
+			"Invalid name `\u{e9}\u{e9}` at position 1 of `(\u{e9}\u{e9},bool,uint7)`"
+		);
+		match *Reader::read("(bool,\u{1F600},uint7)").unwrap_err().kind() {
+			ErrorKind::InvalidNameAt(ref name, position, _) => {
+				assert_eq!(name, "\u{1F600}");
+				assert_eq!(position, 6);
+			},
+			ref kind => panic!("unexpected error {:?}", kind),
+		}
+		assert!(Reader::read("(\u{1F600}(bool),address)").is_err());
+	}
+
+	#[test]
+	fn test_read_invalid_width_param() {
+		assert!(Reader::read("uint7").is_err());
+		assert!(Reader::read("uint0").is_err());
+		assert!(Reader::read("uint264").is_err());
+		assert!(Reader::read("int300").is_err());
+		assert!(Reader::read("int4").is_err());
+		assert!(Reader::read("bytes0").is_err());
+		assert!(Reader::read("bytes33").is_err());
+		assert!(Reader::read("uint7[]").is_err());
+		assert!(Reader::read("bytes64[2]").is_err());
+
+		match *Reader::read("uint7").unwrap_err().kind() {
+			ErrorKind::InvalidName(ref name) => assert_eq!(name, "uint7"),
+			ref kind => panic!("unexpected error {:?}", kind),
+		}
+	}
+
+	#[test]
+	fn test_read_invalid_nested_param_position() {
+		match *Reader::read("uint7[2][]").unwrap_err().kind() {
+			ErrorKind::InvalidNameAt(ref name, position, ref full) => {
+				assert_eq!(name, "uint7");
+				assert_eq!(position, 0);
+				assert_eq!(full, "uint7[2][]");
+			},
+			ref kind => panic!("unexpected error {:?}", kind),
+		}
+
+		assert_eq!(
+			Reader::read("uintx[]").unwrap_err().to_string(),
+			"Invalid name `uintx` at position 0 of `uintx[]`"
+		);
+
+		assert_eq!(
+			Reader::read("(address,uint7,bool)").unwrap_err().to_string(),
+			"Invalid name `uint7` at position 9 of `(address,uint7,bool)`"
+		);
+
+		match *Reader::read("(bool,(address,bytes33)[])").unwrap_err().kind() {
+			ErrorKind::InvalidNameAt(ref name, position, _) => {
+				assert_eq!(name, "bytes33");
+				assert_eq!(position, 15);
+			},
+			ref kind => panic!("unexpected error {:?}", kind),
+		}
+	}
+
+	#[test]
+	fn test_read_tuple() {
+		assert_eq!(Reader::read_tuple("").unwrap(), vec![]);
+		assert_eq!(Reader::read_tuple("address").unwrap(), vec![ParamType::Address]);
+		assert_eq!(Reader::read_tuple("address,(uint256,(bool,string)[])[2],bytes").unwrap(), vec![
+			ParamType::Address,
+			ParamType::FixedArray(Box::new(ParamType::Tuple(tuple_components![
+				Box::new(ParamType::Uint(256)),
+				Box::new(ParamType::Array(Box::new(ParamType::Tuple(tuple_components![
+					Box::new(ParamType::Bool),
+					Box::new(ParamType::String),
+				])))),
+			])), 2),
+			ParamType::Bytes,
+		]);
+		assert_eq!(Reader::read_tuple("()").unwrap(), vec![ParamType::Tuple(tuple_components![])]);
+
+		assert!(Reader::read_tuple("address,").is_err());
+		assert!(Reader::read_tuple("(address,bool").is_err());
+		assert_eq!(
+			Reader::read_tuple("address,uint7").unwrap_err().to_string(),
+			"Invalid name `uint7` at position 8 of `address,uint7`"
+		);
+	}
+
+	#[test]
+	fn test_read_params() {
+		assert_eq!(Reader::read_params("").unwrap(), vec![]);
+		assert_eq!(Reader::read_params("  ").unwrap(), vec![]);
+		assert_eq!(Reader::read_params("address").unwrap(), vec![
+			Param { name: "".into(), kind: ParamType::Address, internal_type: None, components: vec![] },
+		]);
+		assert_eq!(Reader::read_params("address to, uint256 amount").unwrap(), vec![
+			Param { name: "to".into(), kind: ParamType::Address, internal_type: None, components: vec![] },
+			Param { name: "amount".into(), kind: ParamType::Uint(256), internal_type: None, components: vec![] },
+		]);
+		assert_eq!(Reader::read_params("(uint256 id, bool[] flags)[] items, string memory").unwrap(), vec![
+			Param {
+				name: "items".into(),
+				kind: ParamType::Array(Box::new(ParamType::Tuple(tuple_components![
+					Box::new(ParamType::Uint(256)),
+					Box::new(ParamType::Array(Box::new(ParamType::Bool))),
+				]))),
+				internal_type: None,
+				components: vec![
+					TupleParam { name: Some("id".into()), kind: ParamType::Uint(256), internal_type: None, components: vec![] },
+					TupleParam { name: Some("flags".into()), kind: ParamType::Array(Box::new(ParamType::Bool)), internal_type: None, components: vec![] },
+				],
+			},
+			Param { name: "".into(), kind: ParamType::String, internal_type: None, components: vec![] },
+		]);
+
+		assert!(Reader::read_params("address to,").is_err());
+		assert!(Reader::read_params("address to from").is_err());
+		assert!(Reader::read_params("(address to").is_err());
 	}
 
 	#[test]
@@ -0,0 +1,30 @@
+use serde::{Serialize, Serializer};
+use super::{ParamType, Writer};
+
+impl Serialize for ParamType {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		serializer.serialize_str(&Writer::write(self))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ParamType;
+
+	#[test]
+	fn param_type_serialization() {
+		let types = vec![
+			ParamType::Address,
+			ParamType::FixedBytes(32),
+			ParamType::Int(8),
+			ParamType::Array(Box::new(ParamType::Bool)),
+			ParamType::FixedArray(Box::new(ParamType::String), 2),
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Uint(256)), Box::new(ParamType::Bytes)]),
+		];
+
+		let serialized = serde_json::to_string(&types).unwrap();
+		assert_eq!(serialized, r#"["address","bytes32","int8","bool[]","string[2]","(uint256,bytes)"]"#);
+		assert_eq!(serde_json::from_str::<Vec<ParamType>>(&serialized).unwrap(), types);
+	}
+}
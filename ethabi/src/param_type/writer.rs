@@ -1,10 +1,14 @@
 use ParamType;
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Output formatter for param type.
 pub struct Writer;
 
 impl Writer {
 	/// Returns string which is a formatted represenation of param.
+	///
+	/// Tuples are written in the parenthesized form used by canonical signatures, e.g. `(address,bool)`.
 	pub fn write(param: &ParamType) -> String {
 		match *param {
 			ParamType::Address => "address".to_owned(),
@@ -16,6 +20,14 @@ impl Writer {
 			ParamType::String => "string".to_owned(),
 			ParamType::FixedArray(ref param, len) => format!("{}[{}]", Writer::write(param), len),
 			ParamType::Array(ref param) => format!("{}[]", Writer::write(param)),
+			ParamType::Tuple(ref params) => {
+				let s = params.iter()
+					.map(|t| Writer::write(t))
+					.collect::<Vec<String>>()
+					.join(",");
+
+				format!("({})", s)
+			},
 		}
 	}
 }
@@ -24,6 +36,7 @@ impl Writer {
 mod tests {
 	use ParamType;
 	use super::Writer;
+	use param_type::Reader;
 
 	#[test]
 	fn test_write_param() {
@@ -37,5 +50,49 @@ mod tests {
 		assert_eq!(Writer::write(&ParamType::Array(Box::new(ParamType::Bool))), "bool[]".to_owned());
 		assert_eq!(Writer::write(&ParamType::FixedArray(Box::new(ParamType::String), 2)), "string[2]".to_owned());
 		assert_eq!(Writer::write(&ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Bool))), 2)), "bool[][2]".to_owned());
+		assert_eq!(Writer::write(&ParamType::Array(Box::new(ParamType::FixedArray(Box::new(ParamType::Address), 3)))), "address[3][]".to_owned());
+	}
+
+	#[test]
+	fn test_write_tuple_param() {
+		assert_eq!(Writer::write(&ParamType::Tuple(tuple_components![])), "()".to_owned());
+		assert_eq!(Writer::write(&ParamType::Tuple(tuple_components![Box::new(ParamType::Address)])), "(address)".to_owned());
+		assert_eq!(Writer::write(&ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)])), "(address,bool)".to_owned());
+		assert_eq!(Writer::write(&ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)])),
+			Box::new(ParamType::Uint(256)),
+		])), "((address,bool),uint256)".to_owned());
+		assert_eq!(Writer::write(&ParamType::FixedArray(Box::new(ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Bytes),
+			Box::new(ParamType::Array(Box::new(ParamType::String))),
+		])), 2)), "(bytes,string[])[2]".to_owned());
+	}
+
+	#[test]
+	fn test_write_read_roundtrip() {
+		let params = vec![
+			ParamType::Address,
+			ParamType::Bytes,
+			ParamType::FixedBytes(32),
+			ParamType::Uint(256),
+			ParamType::Int(8),
+			ParamType::Bool,
+			ParamType::String,
+			ParamType::Array(Box::new(ParamType::Bool)),
+			ParamType::FixedArray(Box::new(ParamType::Uint(64)), 3),
+			ParamType::FixedArray(Box::new(ParamType::Array(Box::new(ParamType::Bool))), 2),
+			ParamType::Array(Box::new(ParamType::FixedArray(Box::new(ParamType::Bytes), 5))),
+			ParamType::Tuple(tuple_components![]),
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Bool)]),
+			ParamType::Array(Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Uint(256))]))),
+			ParamType::Tuple(tuple_components![
+				Box::new(ParamType::FixedArray(Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::String)])), 2)),
+				Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Bytes), Box::new(ParamType::Int(32))])),
+			]),
+		];
+
+		for param in params {
+			assert_eq!(Reader::read(&param.to_string()).unwrap(), param);
+		}
 	}
 }
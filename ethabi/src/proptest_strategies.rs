@@ -0,0 +1,180 @@
+//! Proptest strategies generating valid ABI types and tokens.
+//!
+//! Enabled with the `proptest` feature.
+
+use proptest::arbitrary::Arbitrary;
+use proptest::prelude::*;
+use {Address, ParamType, Token, Uint};
+
+/// Default nesting depth of arrays and tuples generated by `any::<ParamType>()`.
+pub const DEFAULT_DEPTH: u32 = 3;
+
+/// Strategy generating elementary types with valid bit widths and byte sizes.
+pub fn elementary_param_type() -> BoxedStrategy<ParamType> {
+	prop_oneof![
+		Just(ParamType::Address),
+		Just(ParamType::Bytes),
+		(1usize..=32).prop_map(ParamType::FixedBytes),
+		(1usize..=32).prop_map(|n| ParamType::Int(n * 8)),
+		(1usize..=32).prop_map(|n| ParamType::Uint(n * 8)),
+		Just(ParamType::Bool),
+		Just(ParamType::String),
+	].boxed()
+}
+
+/// Strategy generating any type, with arrays and tuples nested at most `max_depth` levels deep.
+pub fn param_type(max_depth: u32) -> BoxedStrategy<ParamType> {
+	elementary_param_type().prop_recursive(max_depth, 32, 4, |inner| {
+		prop_oneof![
+			inner.clone().prop_map(|kind| ParamType::Array(Box::new(kind))),
+			(inner.clone(), 1usize..=4).prop_map(|(kind, len)| ParamType::FixedArray(Box::new(kind), len)),
+			prop::collection::vec(inner.prop_map(Box::new), 0..=4)
+				.prop_map(|components| ParamType::Tuple(components.into_iter().collect())),
+		]
+	}).boxed()
+}
+
+impl Arbitrary for ParamType {
+	type Parameters = u32;
+	type Strategy = BoxedStrategy<ParamType>;
+
+	/// Parameter is the maximum nesting depth, `DEFAULT_DEPTH` unless specified.
+	fn arbitrary_with(max_depth: u32) -> Self::Strategy {
+		param_type(if max_depth == 0 { DEFAULT_DEPTH } else { max_depth })
+	}
+}
+
+/// Strategy generating 256 bit words with only the lowest `bits` bits set.
+fn uint(bits: usize) -> BoxedStrategy<Uint> {
+	prop::array::uniform32(any::<u8>())
+		.prop_map(move |bytes| Uint::from_big_endian(&bytes) >> (256 - bits))
+		.boxed()
+}
+
+/// Strategy generating two's complement words representable in `bits` bits.
+fn int(bits: usize) -> BoxedStrategy<Uint> {
+	uint(bits).prop_map(move |value| if bits < 256 && value.bit(bits - 1) {
+		value | (!Uint::zero() << bits)
+	} else {
+		value
+	}).boxed()
+}
+
+/// Strategy generating tokens which pass `Token::validate(param_type)`.
+///
+/// Dynamic arrays get up to 4 elements.
+pub fn token_for_type(param_type: &ParamType) -> BoxedStrategy<Token> {
+	match *param_type {
+		ParamType::Address => prop::array::uniform20(any::<u8>())
+			.prop_map(|bytes| Token::Address(Address::from_slice(&bytes)))
+			.boxed(),
+		ParamType::Bytes => prop::collection::vec(any::<u8>(), 0..=64).prop_map(Token::Bytes).boxed(),
+		ParamType::FixedBytes(size) => prop::collection::vec(any::<u8>(), size).prop_map(Token::FixedBytes).boxed(),
+		ParamType::Int(size) => int(size).prop_map(Token::Int).boxed(),
+		ParamType::Uint(size) => uint(size).prop_map(Token::Uint).boxed(),
+		ParamType::Bool => any::<bool>().prop_map(Token::Bool).boxed(),
+		ParamType::String => any::<String>().prop_map(Token::String).boxed(),
+		ParamType::Array(ref kind) => prop::collection::vec(token_for_type(kind), 0..=4).prop_map(Token::Array).boxed(),
+		ParamType::FixedArray(ref kind, len) => {
+			prop::collection::vec(token_for_type(kind), len).prop_map(Token::FixedArray).boxed()
+		},
+		ParamType::Tuple(ref kinds) => {
+			let tokens: Vec<_> = kinds.iter().map(|kind| token_for_type(kind)).collect();
+			tokens.prop_map(Token::Tuple).boxed()
+		},
+	}
+}
+
+/// Strategy generating a type, nested at most `max_depth` levels deep, along with a token of that type.
+pub fn param_type_and_token(max_depth: u32) -> BoxedStrategy<(ParamType, Token)> {
+	param_type(max_depth).prop_flat_map(|kind| {
+		let token = token_for_type(&kind);
+		(Just(kind), token)
+	}).boxed()
+}
+
+impl Arbitrary for Token {
+	type Parameters = u32;
+	type Strategy = BoxedStrategy<Token>;
+
+	/// Parameter is the maximum nesting depth of the token type, `DEFAULT_DEPTH` unless specified.
+	fn arbitrary_with(max_depth: u32) -> Self::Strategy {
+		param_type_and_token(if max_depth == 0 { DEFAULT_DEPTH } else { max_depth })
+			.prop_map(|(_, token)| token)
+			.boxed()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use proptest::prelude::*;
+	use param_type::Reader;
+	use {decode, encode, encoded_size, ParamType, Token};
+	use super::{elementary_param_type, param_type, param_type_and_token, token_for_type};
+
+	fn depth(kind: &ParamType) -> u32 {
+		match *kind {
+			ParamType::Array(ref kind) | ParamType::FixedArray(ref kind, _) => 1 + depth(kind),
+			ParamType::Tuple(ref kinds) => 1 + kinds.iter().map(|k| depth(k)).max().unwrap_or(0),
+			_ => 0,
+		}
+	}
+
+	fn is_valid(kind: &ParamType) -> bool {
+		match *kind {
+			ParamType::FixedBytes(size) => (1..=32).contains(&size),
+			ParamType::Int(size) | ParamType::Uint(size) => (8..=256).contains(&size) && size % 8 == 0,
+			ParamType::Array(ref kind) => is_valid(kind),
+			ParamType::FixedArray(ref kind, len) => len > 0 && is_valid(kind),
+			ParamType::Tuple(ref kinds) => kinds.iter().all(|k| is_valid(k)),
+			_ => true,
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn generated_types_are_valid(kind in any::<ParamType>()) {
+			prop_assert!(is_valid(&kind));
+		}
+
+		#[test]
+		fn depth_is_capped(kind in param_type(2)) {
+			prop_assert!(depth(&kind) <= 2);
+		}
+
+		#[test]
+		fn generated_types_round_trip_through_reader(kind in param_type(2)) {
+			prop_assert_eq!(Reader::read(&format!("{}", kind)).unwrap(), kind);
+		}
+
+		#[test]
+		fn generated_tokens_are_valid((kind, token) in param_type_and_token(3)) {
+			prop_assert!(token.validate(&kind).is_ok());
+			prop_assert!(token.type_check(&kind));
+		}
+
+		#[test]
+		fn arbitrary_tokens_have_predicted_encoded_size(token in any::<Token>()) {
+			let tokens = [token];
+			prop_assert_eq!(encode(&tokens).len(), encoded_size(&tokens));
+		}
+
+		#[test]
+		fn encoding_length_is_within_bounds((kind, token) in param_type_and_token(3)) {
+			let encoded_len = encode(&[token]).len();
+			prop_assert!(encoded_len >= kind.minimum_encoding_length());
+			if let Some(maximum) = kind.maximum_encoding_length() {
+				prop_assert_eq!(encoded_len, maximum);
+			}
+		}
+
+		#[test]
+		fn elementary_tokens_round_trip(
+			(kind, token) in elementary_param_type().prop_flat_map(|kind| (Just(kind.clone()), token_for_type(&kind)))
+		) {
+			let tokens = vec![token];
+			let encoded = encode(&tokens);
+			prop_assert_eq!(decode(&[kind], &encoded).unwrap(), tokens);
+		}
+	}
+}
@@ -0,0 +1,159 @@
+//! Revert data decoding.
+
+use core::fmt;
+use {decode_function_input, ErrorKind, ParamType, Result, Token, Uint};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Selector of `Error(string)`, used by `require` and `revert` with a message.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of `Panic(uint256)`, used by failing assertions and runtime checks.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Reason of a reverted call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RevertReason {
+	/// Reverted without data, e.g. with `revert()` or `require(condition)`.
+	Empty,
+	/// Reverted with `Error(string)`, e.g. with `require(condition, "message")`.
+	Message(String),
+	/// Reverted with `Panic(uint256)`, e.g. failed `assert` or arithmetic overflow.
+	Panic(Uint),
+	/// Reverted with a custom error, see `CustomError` and `Contract::error_by_selector`.
+	Custom {
+		/// Selector of the error.
+		selector: [u8; 4],
+		/// Revert data, including the selector.
+		data: Vec<u8>,
+	},
+}
+
+impl RevertReason {
+	/// Describes the panic code, for the codes known to solidity 0.8.
+	pub fn panic_description(code: Uint) -> Option<&'static str> {
+		if code > Uint::from(0xff) {
+			return None;
+		}
+
+		let description = match code.low_u64() {
+			0x00 => "generic compiler inserted panic",
+			0x01 => "assertion failed",
+			0x11 => "arithmetic overflow or underflow",
+			0x12 => "division or modulo by zero",
+			0x21 => "invalid enum value",
+			0x22 => "incorrectly encoded storage byte array",
+			0x31 => "pop on empty array",
+			0x32 => "array index out of bounds",
+			0x41 => "too much memory allocated",
+			0x51 => "call to zero initialized internal function",
+			_ => return None,
+		};
+
+		Some(description)
+	}
+}
+
+impl fmt::Display for RevertReason {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			RevertReason::Empty => write!(f, "reverted"),
+			RevertReason::Message(ref message) => write!(f, "reverted: {}", message),
+			RevertReason::Panic(code) => match RevertReason::panic_description(code) {
+				Some(description) => write!(f, "panic: {} (0x{:02x})", description, code.low_u64()),
+				None => write!(f, "panic: unknown code 0x{:x}", code),
+			},
+			RevertReason::Custom { ref selector, .. } => {
+				write!(f, "reverted with custom error 0x{:02x}{:02x}{:02x}{:02x}", selector[0], selector[1], selector[2], selector[3])
+			},
+		}
+	}
+}
+
+/// Decodes the data returned by a reverted call.
+///
+/// `Error(string)` and `Panic(uint256)` are decoded, data starting with any other selector
+/// is returned as `RevertReason::Custom`. Fails if the data is too short to hold a selector,
+/// or if `Error(string)` or `Panic(uint256)` are incorrectly encoded.
+pub fn decode_revert(data: &[u8]) -> Result<RevertReason> {
+	if data.is_empty() {
+		return Ok(RevertReason::Empty);
+	}
+
+	if data.len() < 4 {
+		return Err(ErrorKind::InvalidData.into());
+	}
+
+	let mut selector = [0u8; 4];
+	selector.copy_from_slice(&data[..4]);
+
+	let reason = match selector {
+		ERROR_SELECTOR => match decode_function_input(selector, data, &[ParamType::String])?.pop() {
+			Some(Token::String(message)) => RevertReason::Message(message),
+			_ => unreachable!("decoded single string param; qed"),
+		},
+		PANIC_SELECTOR => match decode_function_input(selector, data, &[ParamType::Uint(256)])?.pop() {
+			Some(Token::Uint(code)) => RevertReason::Panic(code),
+			_ => unreachable!("decoded single uint param; qed"),
+		},
+		_ => RevertReason::Custom { selector, data: data.to_vec() },
+	};
+
+	Ok(reason)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{decode_revert, RevertReason};
+
+	#[test]
+	fn test_empty() {
+		assert_eq!(decode_revert(&[]).unwrap(), RevertReason::Empty);
+		assert!(decode_revert(&[0x08, 0xc3]).is_err());
+	}
+
+	#[test]
+	fn test_message() {
+		let data = hex!("
+			08c379a0
+			0000000000000000000000000000000000000000000000000000000000000020
+			000000000000000000000000000000000000000000000000000000000000001a
+			4e6f7420656e6f7567682045746865722070726f76696465642e000000000000
+		");
+		let reason = decode_revert(&data).unwrap();
+		assert_eq!(reason, RevertReason::Message("Not enough Ether provided.".to_owned()));
+		assert_eq!(format!("{}", reason), "reverted: Not enough Ether provided.");
+
+		assert!(decode_revert(&data[..36]).is_err());
+	}
+
+	#[test]
+	fn test_panic() {
+		let assert = hex!("4e487b710000000000000000000000000000000000000000000000000000000000000001");
+		let reason = decode_revert(&assert).unwrap();
+		assert_eq!(reason, RevertReason::Panic(1.into()));
+		assert_eq!(format!("{}", reason), "panic: assertion failed (0x01)");
+
+		let overflow = hex!("4e487b710000000000000000000000000000000000000000000000000000000000000011");
+		let reason = decode_revert(&overflow).unwrap();
+		assert_eq!(reason, RevertReason::Panic(0x11.into()));
+		assert_eq!(format!("{}", reason), "panic: arithmetic overflow or underflow (0x11)");
+
+		assert_eq!(RevertReason::panic_description(0x12.into()), Some("division or modulo by zero"));
+		assert_eq!(RevertReason::panic_description(0x32.into()), Some("array index out of bounds"));
+		assert_eq!(RevertReason::panic_description(0x02.into()), None);
+		assert_eq!(RevertReason::panic_description(0x111.into()), None);
+		assert_eq!(format!("{}", RevertReason::Panic(0x99.into())), "panic: unknown code 0x99");
+	}
+
+	#[test]
+	fn test_custom() {
+		let data = hex!("
+			cf479181
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000002
+		");
+		let reason = decode_revert(&data).unwrap();
+		assert_eq!(reason, RevertReason::Custom { selector: hex!("cf479181"), data: data.to_vec() });
+		assert_eq!(format!("{}", reason), "reverted with custom error 0xcf479181");
+	}
+}
@@ -0,0 +1,198 @@
+//! Registry of function selectors, to tell which function unknown call data calls.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+use hex::{FromHex, ToHex};
+use serde_json;
+use {Contract, Function, Result, ResultExt};
+
+/// Maps 4 byte function selectors to canonical function signatures, e.g. `a9059cbb`
+/// to `transfer(address,uint256)`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectorRegistry {
+	signatures: HashMap<[u8; 4], String>,
+}
+
+impl SelectorRegistry {
+	/// Creates an empty registry.
+	pub fn new() -> Self {
+		SelectorRegistry::default()
+	}
+
+	/// Creates a registry of the functions of the contract.
+	///
+	/// Solidity rejects contracts with colliding selectors. For such ABIs the first of the
+	/// colliding functions is registered.
+	pub fn from_contract(contract: &Contract) -> Self {
+		let mut registry = SelectorRegistry::new();
+		for function in contract.functions() {
+			let _ = registry.register_function(function);
+		}
+		registry
+	}
+
+	/// Loads a registry from json file mapping selectors to signatures, in the format of the
+	/// 4byte database dumps, e.g. `{"a9059cbb": "transfer(address,uint256)"}`.
+	///
+	/// Selectors may be `0x` prefixed. Fails if a selector doesn't match its signature, or
+	/// if the file maps two signatures to the same selector.
+	pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+		let path = path.as_ref();
+		let file = File::open(path).chain_err(|| format!("Cannot open {}", path.display()))?;
+		let entries = serde_json::from_reader(BufReader::new(file))
+			.chain_err(|| format!("Cannot parse {}", path.display()))?;
+		SelectorRegistry::from_entries(entries)
+	}
+
+	/// Loads a registry from json mapping selectors to signatures, like `from_json_file`.
+	pub fn from_json_reader<R: io::Read>(reader: R) -> Result<Self> {
+		let entries = serde_json::from_reader(reader).chain_err(|| "Cannot parse selectors json")?;
+		SelectorRegistry::from_entries(entries)
+	}
+
+	fn from_entries(entries: HashMap<String, String>) -> Result<Self> {
+		let mut registry = SelectorRegistry::new();
+		for (selector, signature) in entries {
+			let hex = selector.strip_prefix("0x").unwrap_or(&selector);
+			let bytes: Vec<u8> = hex.from_hex().chain_err(|| format!("Invalid selector `{}`", selector))?;
+			if bytes.len() != 4 {
+				bail!("Invalid selector `{}`", selector);
+			}
+			if registry.register(&signature)?[..] != bytes[..] {
+				bail!("Selector `{}` does not match `{}`", selector, signature);
+			}
+		}
+		Ok(registry)
+	}
+
+	/// Registers the signature and returns its selector.
+	///
+	/// The signature may be human readable, like `transfer(address to, uint256 amount)`, it's
+	/// stored in its canonical form. Fails if another signature is registered with the same
+	/// selector.
+	pub fn register(&mut self, signature: &str) -> Result<[u8; 4]> {
+		let function = Function::from_str_signature(signature)?;
+		self.register_function(&function)
+	}
+
+	/// Registers all functions of the contract, fails on the first selector collision.
+	pub fn register_contract(&mut self, contract: &Contract) -> Result<()> {
+		for function in contract.functions() {
+			self.register_function(function)?;
+		}
+		Ok(())
+	}
+
+	fn register_function(&mut self, function: &Function) -> Result<[u8; 4]> {
+		let selector = function.selector();
+		let signature = function.canonical_signature();
+		if let Some(registered) = self.signatures.get(&selector) {
+			if *registered != signature {
+				bail!("Selector 0x{} of `{}` collides with `{}`", selector.to_hex::<String>(), signature, registered);
+			}
+		}
+		self.signatures.insert(selector, signature);
+		Ok(selector)
+	}
+
+	/// Returns the canonical signature registered with the selector.
+	pub fn lookup(&self, selector: &[u8; 4]) -> Option<&str> {
+		self.signatures.get(selector).map(String::as_str)
+	}
+
+	/// Returns the number of registered signatures.
+	pub fn len(&self) -> usize {
+		self.signatures.len()
+	}
+
+	/// Returns true if no signature is registered.
+	pub fn is_empty(&self) -> bool {
+		self.signatures.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{env, fs, process};
+	use std::io::Cursor;
+	use std::path::PathBuf;
+	use super::SelectorRegistry;
+	use Contract;
+
+	#[test]
+	fn erc20_selectors() {
+		let contract = Contract::load(include_str!("../../res/eip20.abi").as_bytes()).unwrap();
+		let registry = SelectorRegistry::from_contract(&contract);
+		assert_eq!(registry.len(), contract.functions().count());
+		for function in contract.functions() {
+			assert_eq!(registry.lookup(&function.selector()), Some(&function.canonical_signature()[..]));
+		}
+		assert_eq!(registry.lookup(&[0xa9, 0x05, 0x9c, 0xbb]), Some("transfer(address,uint256)"));
+		assert_eq!(registry.lookup(&[0, 0, 0, 0]), None);
+
+		let mut other = SelectorRegistry::new();
+		other.register_contract(&contract).unwrap();
+		assert_eq!(other, registry);
+	}
+
+	#[test]
+	fn register() {
+		let mut registry = SelectorRegistry::new();
+		assert_eq!(registry.register("transfer(address to, uint256 amount)").unwrap(), [0xa9, 0x05, 0x9c, 0xbb]);
+		assert_eq!(registry.lookup(&[0xa9, 0x05, 0x9c, 0xbb]), Some("transfer(address,uint256)"));
+		// registering the same function again is fine
+		assert!(registry.register("transfer(address,uint256)").is_ok());
+		assert!(registry.register("transfer(").is_err());
+	}
+
+	#[test]
+	fn register_collision() {
+		// well known collision of `transfer(address,uint256)`
+		let mut registry = SelectorRegistry::new();
+		registry.register("transfer(address,uint256)").unwrap();
+		let error = registry.register("many_msg_babbage(bytes1)").unwrap_err();
+		assert_eq!(
+			error.to_string(),
+			"Selector 0xa9059cbb of `many_msg_babbage(bytes1)` collides with `transfer(address,uint256)`"
+		);
+	}
+
+	#[test]
+	fn from_json_reader() {
+		let json = r#"{"0xa9059cbb": "transfer(address,uint256)", "70a08231": "balanceOf(address)"}"#;
+		let registry = SelectorRegistry::from_json_reader(Cursor::new(json)).unwrap();
+		assert_eq!(registry.len(), 2);
+		assert_eq!(registry.lookup(&[0x70, 0xa0, 0x82, 0x31]), Some("balanceOf(address)"));
+
+		let json = r#"{"0xa9059cbc": "transfer(address,uint256)"}"#;
+		let error = SelectorRegistry::from_json_reader(Cursor::new(json)).unwrap_err();
+		assert_eq!(error.to_string(), "Selector `0xa9059cbc` does not match `transfer(address,uint256)`");
+		assert!(SelectorRegistry::from_json_reader(Cursor::new(r#"{"0xa9059cbb": "#)).is_err());
+	}
+
+	/// Temporary file, removed when dropped.
+	struct TempFile(PathBuf);
+
+	impl Drop for TempFile {
+		fn drop(&mut self) {
+			let _ = fs::remove_file(&self.0);
+		}
+	}
+
+	#[test]
+	fn from_json_file() {
+		let file = TempFile(env::temp_dir().join(format!("ethabi_selector_registry_from_json_file_{}.json", process::id())));
+		fs::write(&file.0, r#"{"a9059cbb": "transfer(address,uint256)"}"#).unwrap();
+		let registry = SelectorRegistry::from_json_file(&file.0).unwrap();
+		assert_eq!(registry.lookup(&[0xa9, 0x05, 0x9c, 0xbb]), Some("transfer(address,uint256)"));
+
+		fs::write(&file.0, r#"{"0xa9059cbc": "transfer(address,uint256)"}"#).unwrap();
+		let error = SelectorRegistry::from_json_file(&file.0).unwrap_err();
+		assert_eq!(error.to_string(), "Selector `0xa9059cbc` does not match `transfer(address,uint256)`");
+
+		let missing = env::temp_dir().join(format!("ethabi_selector_registry_missing_{}.json", process::id()));
+		assert!(SelectorRegistry::from_json_file(&missing).is_err());
+	}
+}
@@ -1,6 +1,8 @@
 use tiny_keccak::Keccak;
 use param_type::{Writer, ParamType};
 use Hash;
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 pub fn short_signature(name: &str, params: &[ParamType]) -> [u8; 4] {
 	let mut result = [0u8; 4];
@@ -0,0 +1,95 @@
+//! Fallback and receive functions.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use StateMutability;
+
+/// Contract fallback function, called with data not matching any function selector.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Fallback {
+	/// Fallback state mutability, `payable` or `nonpayable`.
+	pub state_mutability: StateMutability,
+}
+
+/// Contract receive function, called by plain ether transfers without data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Receive {
+	/// Receive state mutability, always `payable` in valid contracts.
+	pub state_mutability: StateMutability,
+}
+
+/// Fallback or receive function as found in the ABI json, with both the current and legacy
+/// mutability fields. Neither has a name nor params.
+#[derive(Serialize, Deserialize)]
+struct AbiSpecialFunction {
+	#[serde(rename = "stateMutability", default, skip_serializing_if = "Option::is_none")]
+	state_mutability: Option<StateMutability>,
+	#[serde(default, skip_serializing)]
+	payable: bool,
+}
+
+impl AbiSpecialFunction {
+	fn new(state_mutability: StateMutability) -> Self {
+		AbiSpecialFunction { state_mutability: Some(state_mutability), payable: false }
+	}
+
+	/// `stateMutability` supersedes `payable` since solidity 0.4.16, solidity 0.5 still writes
+	/// only `payable` for fallback functions.
+	fn state_mutability(&self) -> StateMutability {
+		match self.state_mutability {
+			Some(state_mutability) => state_mutability,
+			None if self.payable => StateMutability::Payable,
+			None => StateMutability::NonPayable,
+		}
+	}
+}
+
+impl<'a> Deserialize<'a> for Fallback {
+	fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: Deserializer<'a> {
+		let abi = AbiSpecialFunction::deserialize(deserializer)?;
+		Ok(Fallback { state_mutability: abi.state_mutability() })
+	}
+}
+
+impl Serialize for Fallback {
+	fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: Serializer {
+		AbiSpecialFunction::new(self.state_mutability).serialize(serializer)
+	}
+}
+
+impl<'a> Deserialize<'a> for Receive {
+	fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: Deserializer<'a> {
+		let abi = AbiSpecialFunction::deserialize(deserializer)?;
+		Ok(Receive { state_mutability: abi.state_mutability() })
+	}
+}
+
+impl Serialize for Receive {
+	fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: Serializer {
+		AbiSpecialFunction::new(self.state_mutability).serialize(serializer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use {Fallback, Receive, StateMutability};
+
+	#[test]
+	fn deserialize_fallback_and_receive() {
+		let fallback: Fallback = serde_json::from_str(r#"{ "type": "fallback", "stateMutability": "payable" }"#).unwrap();
+		assert_eq!(fallback, Fallback { state_mutability: StateMutability::Payable });
+		let receive: Receive = serde_json::from_str(r#"{ "type": "receive", "stateMutability": "payable" }"#).unwrap();
+		assert_eq!(receive, Receive { state_mutability: StateMutability::Payable });
+
+		// solidity 0.5 fallback functions
+		let fallback: Fallback = serde_json::from_str(r#"{ "type": "fallback", "payable": true }"#).unwrap();
+		assert_eq!(fallback.state_mutability, StateMutability::Payable);
+		let fallback: Fallback = serde_json::from_str(r#"{ "type": "fallback", "payable": false, "constant": false }"#).unwrap();
+		assert_eq!(fallback.state_mutability, StateMutability::NonPayable);
+		let fallback: Fallback = serde_json::from_str(r#"{ "payable": true, "stateMutability": "nonpayable" }"#).unwrap();
+		assert_eq!(fallback.state_mutability, StateMutability::NonPayable);
+
+		assert_eq!(serde_json::to_string(&Fallback { state_mutability: StateMutability::Payable }).unwrap(), r#"{"stateMutability":"payable"}"#);
+		assert_eq!(serde_json::to_string(&Receive::default()).unwrap(), r#"{"stateMutability":"nonpayable"}"#);
+	}
+}
@@ -0,0 +1,67 @@
+//! Function state mutability.
+
+/// Whether a function reads or modifies the blockchain state and accepts ether.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StateMutability {
+	/// Doesn't read nor modify the state.
+	Pure,
+	/// Reads, but doesn't modify the state.
+	View,
+	/// Modifies the state and rejects ether.
+	NonPayable,
+	/// Modifies the state and accepts ether.
+	Payable,
+}
+
+impl Default for StateMutability {
+	fn default() -> Self {
+		StateMutability::NonPayable
+	}
+}
+
+impl StateMutability {
+	/// Returns true for `view` functions.
+	pub fn is_view(&self) -> bool {
+		*self == StateMutability::View
+	}
+
+	/// Returns true for `pure` functions.
+	pub fn is_pure(&self) -> bool {
+		*self == StateMutability::Pure
+	}
+
+	/// Returns true for `payable` functions.
+	pub fn is_payable(&self) -> bool {
+		*self == StateMutability::Payable
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use StateMutability;
+
+	#[test]
+	fn deserialize_state_mutability() {
+		let s = r#"["pure", "view", "nonpayable", "payable"]"#;
+		let deserialized: Vec<StateMutability> = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, vec![
+			StateMutability::Pure,
+			StateMutability::View,
+			StateMutability::NonPayable,
+			StateMutability::Payable,
+		]);
+		assert!(serde_json::from_str::<StateMutability>(r#""constant""#).is_err());
+	}
+
+	#[test]
+	fn state_mutability_helpers() {
+		assert!(StateMutability::View.is_view());
+		assert!(StateMutability::Pure.is_pure());
+		assert!(StateMutability::Payable.is_payable());
+		assert!(!StateMutability::NonPayable.is_view());
+		assert!(!StateMutability::NonPayable.is_pure());
+		assert!(!StateMutability::NonPayable.is_payable());
+	}
+}
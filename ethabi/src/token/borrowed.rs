@@ -0,0 +1,61 @@
+//! Ethereum ABI params borrowing their data from the decoded input.
+
+use {Address, Uint, Token};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Ethereum ABI param like `Token`, with bytes, fixed bytes and strings borrowed from the data
+/// they were decoded from.
+///
+/// Returned by `decode_borrowed`, which doesn't copy them.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BorrowedToken<'a> {
+	/// Address.
+	Address(Address),
+	/// Bytes with known size.
+	FixedBytes(&'a [u8]),
+	/// Bytes of unknown size.
+	Bytes(&'a [u8]),
+	/// Signed integer.
+	Int(Uint),
+	/// Unsigned integer.
+	Uint(Uint),
+	/// Boolean value.
+	Bool(bool),
+	/// String.
+	String(&'a str),
+	/// Array with known size.
+	FixedArray(Vec<BorrowedToken<'a>>),
+	/// Array of params with unknown size.
+	Array(Vec<BorrowedToken<'a>>),
+	/// Tuple of params of variable types.
+	Tuple(Vec<BorrowedToken<'a>>),
+}
+
+impl<'a> BorrowedToken<'a> {
+	/// Converts to `Token`, copying borrowed bytes and strings.
+	pub fn into_owned(self) -> Token {
+		match self {
+			BorrowedToken::Address(address) => Token::Address(address),
+			BorrowedToken::FixedBytes(bytes) => Token::FixedBytes(bytes.to_vec()),
+			BorrowedToken::Bytes(bytes) => Token::Bytes(bytes.to_vec()),
+			BorrowedToken::Int(int) => Token::Int(int),
+			BorrowedToken::Uint(uint) => Token::Uint(uint),
+			BorrowedToken::Bool(b) => Token::Bool(b),
+			BorrowedToken::String(s) => Token::String(s.to_owned()),
+			BorrowedToken::FixedArray(tokens) => Token::FixedArray(into_owned(tokens)),
+			BorrowedToken::Array(tokens) => Token::Array(into_owned(tokens)),
+			BorrowedToken::Tuple(tokens) => Token::Tuple(into_owned(tokens)),
+		}
+	}
+}
+
+fn into_owned(tokens: Vec<BorrowedToken>) -> Vec<Token> {
+	tokens.into_iter().map(BorrowedToken::into_owned).collect()
+}
+
+impl<'a> From<BorrowedToken<'a>> for Token {
+	fn from(token: BorrowedToken<'a>) -> Self {
+		token.into_owned()
+	}
+}
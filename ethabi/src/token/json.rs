@@ -0,0 +1,525 @@
+//! Conversion of tokens to and from JSON values, as used by JSON-RPC clients.
+
+use core::result;
+use hex::{FromHex, ToHex};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, DeserializeSeed};
+use serde::ser;
+use serde_json::Value;
+use {Token, ParamType, Param, TupleParam, Address, Uint, Result, ResultExt, to_checksum_address, from_checksum_address};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+impl Token {
+	/// Converts the token of the given type to JSON.
+	///
+	/// Addresses are written with their EIP-55 checksum, bytes and fixed bytes as `0x` prefixed
+	/// hex strings, unsigned integers as `0x` prefixed hex quantities, e.g. `"0x3e8"`, and signed
+	/// integers as the quantity of their 256 bit two's complement, like `TokenWithType`.
+	/// Arrays and tuples are written as JSON arrays, use `to_json_value_named` to write tuples
+	/// as objects. Tokens not matching the type are written according to their own type.
+	pub fn to_json_value(&self, param_type: &ParamType) -> Value {
+		self.to_json(param_type, &[])
+	}
+
+	/// Converts the token of the param type to JSON like `to_json_value`, writing tuples as
+	/// JSON objects keyed by the names of their components. Unnamed components are keyed
+	/// by their index, e.g. `"0"`.
+	pub fn to_json_value_named(&self, param: &Param) -> Value {
+		self.to_json(&param.kind, &param.components)
+	}
+
+	fn to_json(&self, param_type: &ParamType, components: &[TupleParam]) -> Value {
+		match (self, param_type) {
+			(Token::Int(int), ParamType::Int(_)) | (Token::Uint(int), ParamType::Int(_)) |
+			(Token::Int(int), ParamType::Uint(_)) | (Token::Uint(int), ParamType::Uint(_)) => {
+				Value::String(quantity(int))
+			},
+			(Token::Address(address), ParamType::Address) => {
+				Value::String(to_checksum_address(address.as_fixed_bytes()))
+			},
+			(Token::Bytes(bytes), ParamType::Bytes) | (Token::FixedBytes(bytes), ParamType::FixedBytes(_)) => {
+				Value::String(format!("0x{}", bytes.to_hex::<String>()))
+			},
+			(Token::Bool(b), ParamType::Bool) => Value::Bool(*b),
+			(Token::String(s), ParamType::String) => Value::String(s.clone()),
+			(Token::Array(tokens), ParamType::Array(kind)) |
+			(Token::FixedArray(tokens), ParamType::FixedArray(kind, _)) => {
+				Value::Array(tokens.iter().map(|token| token.to_json(kind, components)).collect())
+			},
+			(Token::Tuple(tokens), ParamType::Tuple(kinds)) if tokens.len() == kinds.len() => {
+				if components.len() == kinds.len() && !components.is_empty() {
+					Value::Object(tokens.iter().zip(kinds.iter()).zip(components).enumerate()
+						.map(|(i, ((token, kind), component))| (component_key(component, i), token.to_json(kind, &component.components)))
+						.collect())
+				} else {
+					Value::Array(tokens.iter().zip(kinds.iter()).map(|(token, kind)| token.to_json(kind, &[])).collect())
+				}
+			},
+			_ => self.to_json(&self.type_of(), &[]),
+		}
+	}
+
+	/// Converts JSON in the format of `to_json_value` to a token of the given type.
+	///
+	/// Integers may also be given as JSON numbers, and negative integers as `-` prefixed
+	/// quantities, e.g. `"-0x3e8"`. Fails if the value doesn't match the type, including
+	/// integers not fitting their bit width and mixed case addresses with an invalid checksum.
+	pub fn from_json_value(value: Value, param_type: &ParamType) -> Result<Token> {
+		let token = Token::from_json_value_unchecked(value, param_type, &[])?;
+		token.validate(param_type)?;
+		Ok(token)
+	}
+
+	/// Converts JSON in the format of `to_json_value_named` to a token of the param type.
+	/// Tuples may also be given as arrays, see `from_json_value`.
+	pub fn from_json_value_named(value: Value, param: &Param) -> Result<Token> {
+		let token = Token::from_json_value_unchecked(value, &param.kind, &param.components)?;
+		token.validate(&param.kind)?;
+		Ok(token)
+	}
+
+	fn from_json_value_unchecked(value: Value, param_type: &ParamType, components: &[TupleParam]) -> Result<Token> {
+		let token = match (value, param_type) {
+			(Value::String(ref s), ParamType::Address) => Token::Address(from_checksum_address(s)?.into()),
+			(Value::String(ref s), ParamType::Bytes) => Token::Bytes(hex_bytes(s)?),
+			(Value::String(ref s), ParamType::FixedBytes(len)) => {
+				let bytes = hex_bytes(s)?;
+				if bytes.len() != *len {
+					bail!("Expected {} bytes, got `{}`", len, s);
+				}
+				Token::FixedBytes(bytes)
+			},
+			(Value::String(ref s), ParamType::Int(_)) => match s.strip_prefix('-') {
+				Some(magnitude) => Token::Int((!parse_quantity(magnitude)?).overflowing_add(1.into()).0),
+				None => Token::Int(parse_quantity(s)?),
+			},
+			(Value::String(ref s), ParamType::Uint(_)) => Token::Uint(parse_quantity(s)?),
+			(Value::Number(ref n), ParamType::Int(_)) => match (n.as_u64(), n.as_i64()) {
+				(Some(n), _) => Token::Int(n.into()),
+				(None, Some(n)) => Token::Int((!Uint::from(n.unsigned_abs())).overflowing_add(1.into()).0),
+				_ => bail!("Invalid integer `{}`", n),
+			},
+			(Value::Number(ref n), ParamType::Uint(_)) => match n.as_u64() {
+				Some(n) => Token::Uint(n.into()),
+				None => bail!("Invalid unsigned integer `{}`", n),
+			},
+			(Value::Bool(b), ParamType::Bool) => Token::Bool(b),
+			(Value::String(s), ParamType::String) => Token::String(s),
+			(Value::Array(values), ParamType::Array(kind)) => {
+				Token::Array(Token::from_json_values(values, |_| (kind, components))?)
+			},
+			(Value::Array(values), ParamType::FixedArray(kind, len)) if values.len() == *len => {
+				Token::FixedArray(Token::from_json_values(values, |_| (kind, components))?)
+			},
+			(Value::Array(values), ParamType::Tuple(kinds)) if values.len() == kinds.len() => {
+				Token::Tuple(Token::from_json_values(values, |i| (&kinds[i], &[]))?)
+			},
+			(Value::Object(mut values), ParamType::Tuple(kinds)) if components.len() == kinds.len() && values.len() == kinds.len() => {
+				let values = components.iter().enumerate()
+					.map(|(i, component)| {
+						let key = component_key(component, i);
+						values.remove(&key).ok_or_else(|| format!("Missing tuple component `{}`", key).into())
+					})
+					.collect::<Result<Vec<Value>>>()?;
+				Token::Tuple(Token::from_json_values(values, |i| (&kinds[i], &components[i].components))?)
+			},
+			(value, _) => bail!("Expected {}, got `{}`", param_type, value),
+		};
+
+		Ok(token)
+	}
+
+	/// Interprets a `0x` prefixed hex value of the given type, e.g. a storage slot returned by
+	/// `eth_getStorageAt`.
+	///
+	/// Integers and booleans are read as big endian words of up to 32 bytes, signed integers
+	/// shorter than 32 bytes are sign extended from their top bit, so `0xff` is -1. Addresses are
+	/// taken from the last 20 bytes, bytes, fixed bytes and strings are the raw bytes. Arrays
+	/// and tuples aren't supported, use `decode_from_hex` for them.
+	pub fn from_hex_string(s: &str, param_type: &ParamType) -> Result<Token> {
+		let bytes = hex_bytes(s)?;
+		let word = || -> Result<Uint> {
+			if bytes.len() > 32 {
+				bail!("Expected at most 32 bytes, got `{}`", s);
+			}
+			Ok(Uint::from(&bytes[..]))
+		};
+
+		let token = match param_type {
+			ParamType::Uint(_) => Token::Uint(word()?),
+			ParamType::Int(_) => match word()? {
+				value if !bytes.is_empty() && bytes.len() < 32 && bytes[0] & 0x80 != 0 => {
+					Token::Int(value | (!Uint::zero() << (bytes.len() * 8)))
+				},
+				value => Token::Int(value),
+			},
+			ParamType::Bool => match word()? {
+				value if value.is_zero() => Token::Bool(false),
+				value if value == Uint::one() => Token::Bool(true),
+				_ => bail!("Invalid boolean `{}`", s),
+			},
+			ParamType::Address => {
+				if bytes.len() < 20 || bytes.len() > 32 {
+					bail!("Expected 20 to 32 bytes, got `{}`", s);
+				}
+				Token::Address(Address::from_slice(&bytes[bytes.len() - 20..]))
+			},
+			ParamType::Bytes => Token::Bytes(bytes),
+			ParamType::FixedBytes(len) => {
+				if bytes.len() != *len {
+					bail!("Expected {} bytes, got `{}`", len, s);
+				}
+				Token::FixedBytes(bytes)
+			},
+			ParamType::String => Token::String(String::from_utf8(bytes).chain_err(|| format!("Invalid string `{}`", s))?),
+			ParamType::Array(_) | ParamType::FixedArray(..) | ParamType::Tuple(_) => {
+				bail!("Cannot read {} from hex, decode it instead", param_type)
+			},
+		};
+
+		token.validate(param_type)?;
+		Ok(token)
+	}
+
+	fn from_json_values<'a, F>(values: Vec<Value>, kind: F) -> Result<Vec<Token>>
+		where F: Fn(usize) -> (&'a ParamType, &'a [TupleParam])
+	{
+		values.into_iter()
+			.enumerate()
+			.map(|(i, value)| {
+				let (kind, components) = kind(i);
+				Token::from_json_value_unchecked(value, kind, components).chain_err(|| format!("Invalid element {}", i))
+			})
+			.collect()
+	}
+}
+
+/// Token along with its type, serialized in the format of JSON-RPC.
+///
+/// Unsigned integers are serialized as `0x` prefixed hex quantities, signed integers as the
+/// quantity of their 256 bit two's complement, addresses as `0x` prefixed hex with the EIP-55
+/// checksum, bytes and fixed bytes as `0x` prefixed hex, booleans and strings as themselves,
+/// and arrays and tuples as JSON arrays. Use `TokenSeed` for the inverse.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenWithType<'a>(pub &'a Token, pub &'a ParamType);
+
+impl<'a> Serialize for TokenWithType<'a> {
+	fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error> where S: Serializer {
+		match (self.0, self.1) {
+			(Token::Int(int), ParamType::Int(_)) | (Token::Uint(int), ParamType::Uint(_)) => {
+				serializer.serialize_str(&quantity(int))
+			},
+			(Token::Address(address), ParamType::Address) => serializer.serialize_str(&to_checksum_address(address.as_fixed_bytes())),
+			(Token::Bytes(bytes), ParamType::Bytes) | (Token::FixedBytes(bytes), ParamType::FixedBytes(_)) => {
+				serializer.serialize_str(&format!("0x{}", bytes.to_hex::<String>()))
+			},
+			(Token::Bool(b), ParamType::Bool) => serializer.serialize_bool(*b),
+			(Token::String(s), ParamType::String) => serializer.serialize_str(s),
+			(Token::Array(tokens), ParamType::Array(kind)) |
+			(Token::FixedArray(tokens), ParamType::FixedArray(kind, _)) => {
+				serializer.collect_seq(tokens.iter().map(|token| TokenWithType(token, kind)))
+			},
+			(Token::Tuple(tokens), ParamType::Tuple(kinds)) if tokens.len() == kinds.len() => {
+				serializer.collect_seq(tokens.iter().zip(kinds.iter()).map(|(token, kind)| TokenWithType(token, kind)))
+			},
+			(token, kind) => Err(ser::Error::custom(format!("Token `{}` doesn't match type {}", token, kind))),
+		}
+	}
+}
+
+/// Deserializes a token of the given type from JSON, in the format of `TokenWithType`
+/// or `Token::to_json_value`, see `Token::from_json_value`.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenSeed<'a>(pub &'a ParamType);
+
+impl<'a, 'de> DeserializeSeed<'de> for TokenSeed<'a> {
+	type Value = Token;
+
+	fn deserialize<D>(self, deserializer: D) -> result::Result<Token, D::Error> where D: Deserializer<'de> {
+		let value = Value::deserialize(deserializer)?;
+		Token::from_json_value(value, self.0).map_err(|e| de::Error::custom(format!("{}", e)))
+	}
+}
+
+/// Key of the tuple component in JSON objects, its name or its index if it's unnamed.
+fn component_key(component: &TupleParam, index: usize) -> String {
+	match component.name {
+		Some(ref name) if !name.is_empty() => name.clone(),
+		_ => index.to_string(),
+	}
+}
+
+/// Formats the number as `0x` prefixed hex without leading zeros.
+fn quantity(value: &Uint) -> String {
+	format!("{:#x}", value)
+}
+
+fn parse_quantity(s: &str) -> Result<Uint> {
+	match s.strip_prefix("0x") {
+		Some(hex) if !hex.is_empty() && hex.len() <= 64 => {
+			let padded = format!("{:0>64}", hex);
+			let bytes: Vec<u8> = padded.from_hex().chain_err(|| format!("Invalid hex quantity `{}`", s))?;
+			Ok(Uint::from(&bytes[..]))
+		},
+		_ => bail!("Invalid hex quantity `{}`", s),
+	}
+}
+
+fn hex_bytes(s: &str) -> Result<Vec<u8>> {
+	match s.strip_prefix("0x") {
+		Some(hex) => Ok(hex.from_hex()?),
+		None => bail!("Expected 0x prefixed hex, got `{}`", s),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde::de::DeserializeSeed;
+	use serde_json::{self, Value};
+	use {Token, TokenWithType, TokenSeed, ParamType, Param, decode, decode_from_hex};
+
+	fn json(s: &str) -> Value {
+		serde_json::from_str(s).unwrap()
+	}
+
+	#[test]
+	fn usdc_symbol_and_decimals() {
+		// eth_call responses of `symbol()` and `decimals()` of USDC, 0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48
+		let symbol = json(concat!(
+			r#"{"jsonrpc": "2.0", "id": 1, "result": "0x"#,
+			"0000000000000000000000000000000000000000000000000000000000000020",
+			"0000000000000000000000000000000000000000000000000000000000000004",
+			"5553444300000000000000000000000000000000000000000000000000000000",
+			r#""}"#
+		));
+		let decimals = json(r#"{"jsonrpc": "2.0", "id": 2, "result": "0x0000000000000000000000000000000000000000000000000000000000000006"}"#);
+
+		let tokens = decode_from_hex(&[ParamType::String], symbol["result"].as_str().unwrap()).unwrap();
+		assert_eq!(tokens[0].to_json_value(&ParamType::String), json(r#""USDC""#));
+
+		let tokens = decode_from_hex(&[ParamType::Uint(8)], decimals["result"].as_str().unwrap()).unwrap();
+		assert_eq!(tokens[0].to_json_value(&ParamType::Uint(8)), json(r#""0x6""#));
+		assert_eq!(Token::from_json_value(json(r#""0x6""#), &ParamType::Uint(8)).unwrap(), tokens[0]);
+	}
+
+	#[test]
+	fn round_trip() {
+		let kind: ParamType = "(address,int256,uint256,bytes,bytes2,bool[2],string[])".parse().unwrap();
+		let value = json(r#"[
+			"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+			"-0x3e8",
+			"0x0",
+			"0x",
+			"0x1234",
+			[true, false],
+			["a", "b"]
+		]"#);
+
+		let token = Token::from_json_value(value.clone(), &kind).unwrap();
+		match token {
+			Token::Tuple(ref tokens) => {
+				assert_eq!(tokens[1], Token::Int(!::Uint::from(999)));
+				assert_eq!(tokens[3], Token::Bytes(vec![]));
+			},
+			_ => panic!("expected tuple"),
+		}
+		// negative integers are written as two's complement, like `TokenWithType` does
+		let mut written = value.clone();
+		written[1] = json(r#""0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc18""#);
+		assert_eq!(token.to_json_value(&kind), written);
+		assert_eq!(token.to_json_value(&kind), serde_json::to_value(TokenWithType(&token, &kind)).unwrap());
+		assert_eq!(Token::from_json_value(written, &kind).unwrap(), token);
+	}
+
+	#[test]
+	fn named_tuples() {
+		// `params` of Uniswap V3 `SwapRouter.exactInputSingle`, with an array of unnamed tuples added
+		let param: Param = serde_json::from_str(r#"{
+			"name": "params",
+			"type": "tuple",
+			"components": [
+				{ "name": "tokenIn", "type": "address" },
+				{ "name": "tokenOut", "type": "address" },
+				{ "name": "fee", "type": "uint24" },
+				{ "name": "amountIn", "type": "uint256" },
+				{ "name": "hops", "type": "tuple[]", "components": [
+					{ "name": "", "type": "int24" },
+					{ "name": "", "type": "bool" }
+				]}
+			]
+		}"#).unwrap();
+		let value = json(r#"{
+			"tokenIn": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+			"tokenOut": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+			"fee": "0x1f4",
+			"amountIn": "0x5f5e100",
+			"hops": [{ "0": "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffcf2c0", "1": true }]
+		}"#);
+
+		let token = Token::from_json_value_named(value.clone(), &param).unwrap();
+		match token {
+			Token::Tuple(ref tokens) => {
+				assert_eq!(tokens[2], Token::Uint(500.into()));
+				assert_eq!(tokens[4], Token::Array(vec![Token::Tuple(vec![Token::from(-200_000i64), Token::Bool(true)])]));
+			},
+			_ => panic!("expected tuple"),
+		}
+		assert_eq!(token.to_json_value_named(&param), value);
+
+		// without the components tuples are arrays, and may be given as arrays to the named variant too
+		let array = token.to_json_value(&param.kind);
+		assert_eq!(array[4], json(r#"[["0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffcf2c0", true]]"#));
+		assert_eq!(Token::from_json_value_named(array, &param).unwrap(), token);
+
+		let mut missing = value.clone();
+		missing.as_object_mut().unwrap().remove("fee");
+		assert!(Token::from_json_value_named(missing, &param).is_err());
+		assert!(Token::from_json_value(value, &param.kind).is_err());
+	}
+
+	#[test]
+	fn numbers() {
+		assert_eq!(Token::from_json_value(json("1000"), &ParamType::Uint(256)).unwrap(), Token::Uint(1000.into()));
+		assert_eq!(Token::from_json_value(json("-1"), &ParamType::Int(8)).unwrap(), Token::Int(!::Uint::zero()));
+		assert!(Token::from_json_value(json("-1"), &ParamType::Uint(256)).is_err());
+		assert!(Token::from_json_value(json(r#""0x100""#), &ParamType::Uint(8)).is_err());
+		assert!(Token::from_json_value(json(r#""1000""#), &ParamType::Uint(256)).is_err());
+	}
+
+	#[test]
+	fn mismatched_values() {
+		assert!(Token::from_json_value(json(r#""0x1234""#), &ParamType::Address).is_err());
+		assert!(Token::from_json_value(json(r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD""#), &ParamType::Address).is_err());
+		assert!(Token::from_json_value(json(r#""0x12""#), &ParamType::FixedBytes(2)).is_err());
+		assert!(Token::from_json_value(json("[true]"), &ParamType::FixedArray(Box::new(ParamType::Bool), 2)).is_err());
+		assert!(Token::from_json_value(json("true"), &ParamType::String).is_err());
+		assert_eq!(
+			Token::from_json_value(json("[1, true]"), &"uint8[]".parse().unwrap()).unwrap_err().to_string(),
+			"Invalid element 1"
+		);
+	}
+
+	#[test]
+	fn eip55_checksums() {
+		for address in &[
+			"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+			"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+			"0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+			"0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+			"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+		] {
+			let token = Token::from_json_value(Value::String(address.to_lowercase()), &ParamType::Address).unwrap();
+			assert_eq!(serde_json::to_value(TokenWithType(&token, &ParamType::Address)).unwrap(), json(&format!("\"{}\"", address)));
+		}
+	}
+
+	#[test]
+	fn serialize_uniswap_v2_swap_log() {
+		// data of a `Swap(address indexed sender, uint amount0In, uint amount1In, uint amount0Out,
+		// uint amount1Out, address indexed to)` log of the USDC/WETH pair, returned by eth_getLogs
+		let data = json(concat!(
+			r#"{"data": "0x"#,
+			"0000000000000000000000000000000000000000000000000000000077359400",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			"00000000000000000000000000000000000000000000000006f05b59d3b20000",
+			r#""}"#
+		));
+		let kinds = vec![ParamType::Uint(256); 4];
+		let tokens = decode_from_hex(&kinds, data["data"].as_str().unwrap()).unwrap();
+		let kind = ParamType::Tuple(kinds.iter().cloned().map(Box::new).collect());
+		let token = Token::Tuple(tokens);
+
+		let serialized = serde_json::to_value(TokenWithType(&token, &kind)).unwrap();
+		assert_eq!(serialized, json(r#"["0x77359400", "0x0", "0x0", "0x6f05b59d3b20000"]"#));
+		assert_eq!(TokenSeed(&kind).deserialize(serialized).unwrap(), token);
+	}
+
+	#[test]
+	fn serialize_and_deserialize() {
+		let kind: ParamType = "(address,int8,int256,bytes,bytes4,bool,string,uint16[2])".parse().unwrap();
+		let encoded = hex!("
+			000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa96045
+			ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff
+			0000000000000000000000000000000000000000000000000000000000000539
+			0000000000000000000000000000000000000000000000000000000000000140
+			a9059cbb00000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000180
+			0000000000000000000000000000000000000000000000000000000000000001
+			00000000000000000000000000000000000000000000000000000000000000ff
+			0000000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000002
+			beef000000000000000000000000000000000000000000000000000000000000
+			0000000000000000000000000000000000000000000000000000000000000003
+			6574680000000000000000000000000000000000000000000000000000000000
+		");
+		let kinds = match kind {
+			ParamType::Tuple(ref kinds) => kinds.iter().map(|k| (**k).clone()).collect::<Vec<_>>(),
+			_ => unreachable!(),
+		};
+		let token = Token::Tuple(decode(&kinds, &encoded).unwrap());
+
+		let serialized = serde_json::to_string(&TokenWithType(&token, &kind)).unwrap();
+		assert_eq!(serialized, concat!(
+			r#"["0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045","#,
+			r#""0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff","#,
+			r#""0x539","0xbeef","0xa9059cbb",true,"eth",["0x1","0xff"]]"#
+		));
+
+		let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+		assert_eq!(TokenSeed(&kind).deserialize(&mut deserializer).unwrap(), token);
+
+		assert!(serde_json::to_value(TokenWithType(&Token::Bool(true), &ParamType::Uint(8))).is_err());
+		assert!(serde_json::to_value(TokenWithType(&token, &"(address,int8)".parse().unwrap())).is_err());
+		assert!(TokenSeed(&ParamType::Int(8)).deserialize(json(r#""0x100""#)).is_err());
+	}
+
+	#[test]
+	fn storage_slots_from_hex() {
+		// eth_getStorageAt responses of slots holding a uint256 balance, an int24 tick,
+		// an address packed after a bool, a bool and a bytes32 hash
+		let from_hex = |s: &str, kind: ParamType| Token::from_hex_string(s, &kind);
+		assert_eq!(
+			from_hex("0x00000000000000000000000000000000000000000000000000000000000f4240", ParamType::Uint(256)).unwrap(),
+			Token::from(1_000_000u64)
+		);
+		assert_eq!(
+			from_hex("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffcf2c0", ParamType::Int(24)).unwrap(),
+			Token::from(-200_000i64)
+		);
+		assert_eq!(
+			from_hex("0x00000000000000000000015aaeb6053f3e94c9b9a09f33669435e7ef1beaed", ParamType::Address).unwrap(),
+			Token::Address(hex!("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").into())
+		);
+		assert_eq!(
+			from_hex("0x0000000000000000000000000000000000000000000000000000000000000001", ParamType::Bool).unwrap(),
+			Token::Bool(true)
+		);
+		assert_eq!(
+			from_hex("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470", ParamType::FixedBytes(32)).unwrap(),
+			Token::FixedBytes(hex!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").to_vec())
+		);
+
+		assert_eq!(from_hex("0x68656c6c6f", ParamType::Bytes).unwrap(), Token::Bytes(b"hello".to_vec()));
+		assert_eq!(from_hex("0x68656c6c6f", ParamType::String).unwrap(), Token::String("hello".to_owned()));
+		assert_eq!(from_hex("0x00ff", ParamType::Uint(8)).unwrap(), Token::from(255u64));
+		assert_eq!(from_hex("0xff", ParamType::Int(8)).unwrap(), Token::from(-1i64));
+		assert_eq!(from_hex("0x80", ParamType::Int(8)).unwrap(), Token::from(-128i64));
+		assert_eq!(from_hex("0x7f", ParamType::Int(8)).unwrap(), Token::from(127i64));
+		assert_eq!(from_hex("0xfcf2c0", ParamType::Int(24)).unwrap(), Token::from(-200_000i64));
+		assert_eq!(from_hex("0x", ParamType::Int(8)).unwrap(), Token::from(0i64));
+
+		assert!(from_hex("0x0100", ParamType::Uint(8)).is_err());
+		// `0x00ff` is 255, which doesn't fit in `int8`
+		assert!(from_hex("0x00ff", ParamType::Int(8)).is_err());
+		assert!(from_hex("0x0000000000000000000000000000000000000000000000000000000000000002", ParamType::Bool).is_err());
+		assert!(from_hex("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea", ParamType::Address).is_err());
+		assert!(from_hex("0x68656c6c6f", ParamType::FixedBytes(4)).is_err());
+		assert!(from_hex("0xff", ParamType::String).is_err());
+		assert!(from_hex(&format!("0x{}", "00".repeat(33)), ParamType::Uint(256)).is_err());
+		assert!(from_hex("f4240", ParamType::Uint(256)).is_err());
+		assert!(from_hex("0x01", ParamType::Array(Box::new(ParamType::Bool))).is_err());
+	}
+}
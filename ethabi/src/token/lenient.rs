@@ -1,6 +1,8 @@
 use token::{Tokenizer, StrictTokenizer};
 use util::{pad_u32, pad_i32};
 use errors::Error;
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Tries to parse string as a token. Does not require string to clearly represent the value.
 pub struct LenientTokenizer;
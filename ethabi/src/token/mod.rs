@@ -1,13 +1,19 @@
 //! ABI param and parsing for it.
 
+mod borrowed;
+mod json;
 mod lenient;
 mod strict;
 mod token;
 
 use {ParamType, Error, ErrorKind, ResultExt};
+pub use self::borrowed::BorrowedToken;
+pub use self::json::{TokenWithType, TokenSeed};
 pub use self::lenient::LenientTokenizer;
 pub use self::strict::StrictTokenizer;
 pub use self::token::Token;
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// This trait should be used to parse string values as tokens.
 pub trait Tokenizer {
@@ -23,9 +29,66 @@ pub trait Tokenizer {
 			ParamType::Int(_) => Self::tokenize_int(value).map(Into::into).map(Token::Int),
 			ParamType::Array(ref p) => Self::tokenize_array(value, p).map(Token::Array),
 			ParamType::FixedArray(ref p, len) => Self::tokenize_fixed_array(value, p, len).map(Token::FixedArray),
+			ParamType::Tuple(ref p) => Self::tokenize_struct(value, p).map(Token::Tuple),
 		}.chain_err(|| format!("Cannot parse {}", param))
 	}
 
+	/// Tries to parse a value as a vector of tokens of different types, e.g. `(1,true,"foo")`.
+	fn tokenize_struct(value: &str, param: &[Box<ParamType>]) -> Result<Vec<Token>, Error> {
+		if !value.starts_with('(') || !value.ends_with(')') {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		if value.chars().count() == 2 {
+			return match param.is_empty() {
+				true => Ok(vec![]),
+				false => Err(ErrorKind::InvalidData.into()),
+			};
+		}
+
+		let mut result = vec![];
+		let mut params = param.iter();
+		let mut nested = 0isize;
+		let mut ignore = false;
+		let mut last_item = 1;
+		for (i, ch) in value.char_indices() {
+			match ch {
+				'(' | '[' if !ignore => {
+					nested += 1;
+				},
+				')' | ']' if !ignore => {
+					nested -= 1;
+					if nested < 0 {
+						return Err(ErrorKind::InvalidData.into());
+					} else if nested == 0 {
+						let sub = &value[last_item..i];
+						let param = try!(params.next().ok_or(ErrorKind::InvalidData));
+						let token = try!(Self::tokenize(param, sub));
+						result.push(token);
+						last_item = i + 1;
+					}
+				},
+				'"' => {
+					ignore = !ignore;
+				},
+				',' if nested == 1 && !ignore => {
+					let sub = &value[last_item..i];
+					let param = try!(params.next().ok_or(ErrorKind::InvalidData));
+					let token = try!(Self::tokenize(param, sub));
+					result.push(token);
+					last_item = i + 1;
+				},
+				_ => ()
+			}
+		}
+
+		if ignore || params.next().is_some() {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		Ok(result)
+	}
+
 	/// Tries to parse a value as a vector of tokens of fixed size.
 	fn tokenize_fixed_array(value: &str, param: &ParamType, len: usize) -> Result<Vec<Token>, Error> {
 		let result = try!(Self::tokenize_array(value, param));
@@ -51,10 +114,13 @@ pub trait Tokenizer {
 		let mut last_item = 1;
 		for (i, ch) in value.chars().enumerate() {
 			match ch {
-				'[' if ignore == false => {
+				'[' | '(' if !ignore => {
 					nested += 1;
 				},
-				']' if ignore == false => {
+				')' if !ignore => {
+					nested -= 1;
+				},
+				']' if !ignore => {
 					nested -= 1;
 					if nested < 0 {
 						return Err(ErrorKind::InvalidData.into());
@@ -68,7 +134,7 @@ pub trait Tokenizer {
 				'"' => {
 					ignore = !ignore;
 				},
-				',' if nested == 1 && ignore == false => {
+				',' if nested == 1 && !ignore => {
 					let sub = &value[last_item..i];
 					let token = try!(Self::tokenize(param, sub));
 					result.push(token);
@@ -118,4 +184,14 @@ mod test {
 		assert!(LenientTokenizer::tokenize_array("[1,\"0\",false]", &ParamType::Bool).is_err());
 		assert!(LenientTokenizer::tokenize_array("[1,0]", &ParamType::Bool).is_ok());
 	}
+
+	#[test]
+	fn tuple_with_wrong_number_of_items_must_error() {
+		let params = vec![Box::new(ParamType::Bool), Box::new(ParamType::Bool)];
+		assert!(LenientTokenizer::tokenize_struct("(1)", &params).is_err());
+		assert!(LenientTokenizer::tokenize_struct("(1,0,1)", &params).is_err());
+		assert!(LenientTokenizer::tokenize_struct("()", &params).is_err());
+		assert!(LenientTokenizer::tokenize_struct("[1,0]", &params).is_err());
+		assert!(LenientTokenizer::tokenize_struct("(1,0)", &params).is_ok());
+	}
 }
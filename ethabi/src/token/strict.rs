@@ -1,6 +1,8 @@
 use hex::FromHex;
 use token::Tokenizer;
 use errors::{Error, ErrorKind};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Tries to parse string as a token. Require string to clearly represent the value.
 pub struct StrictTokenizer;
@@ -157,5 +159,37 @@ mod tests {
 			])
 		);
 	}
-}
 
+	#[test]
+	fn tokenize_tuple() {
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::String)]), "(true,gavofyork)").unwrap(),
+			Token::Tuple(vec![Token::Bool(true), Token::String("gavofyork".to_owned())])
+		);
+	}
+
+	#[test]
+	fn tokenize_array_of_tuples() {
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Array(Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Bool)]))), "[(true,0),(false,1)]").unwrap(),
+			Token::Array(vec![
+				Token::Tuple(vec![Token::Bool(true), Token::Bool(false)]),
+				Token::Tuple(vec![Token::Bool(false), Token::Bool(true)]),
+			])
+		);
+	}
+
+	#[test]
+	fn tokenize_tuple_of_arrays() {
+		assert_eq!(
+			StrictTokenizer::tokenize(&ParamType::Tuple(tuple_components![
+				Box::new(ParamType::Array(Box::new(ParamType::Bool))),
+				Box::new(ParamType::Tuple(tuple_components![Box::new(ParamType::Bool)])),
+			]), "([true,0],(1))").unwrap(),
+			Token::Tuple(vec![
+				Token::Array(vec![Token::Bool(true), Token::Bool(false)]),
+				Token::Tuple(vec![Token::Bool(true)]),
+			])
+		);
+	}
+}
@@ -1,7 +1,9 @@
 //! Ethereum ABI params.
-use std::fmt;
+use core::{fmt, slice};
 use hex::ToHex;
-use {ParamType, Address, FixedBytes, Bytes, Uint};
+use {ParamType, Address, FixedBytes, Bytes, Uint, Error, ErrorKind, Result, encode, decode, to_checksum_address};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
 
 /// Ethereum ABI params.
 #[derive(Debug, PartialEq, Clone)]
@@ -50,28 +52,117 @@ pub enum Token {
 	///
 	/// solidity name eg. int[], bool[], address[5][]
 	Array(Vec<Token>),
+	/// Tuple of params of variable types.
+	///
+	/// solidity name: tuple
+	/// Encoded as the concatenation of its params. Dynamic when any of its params is dynamic.
+	Tuple(Vec<Token>),
 }
 
+/// Human readable representation of the value, e.g. `(0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed, [1, -2], "abc")`.
+///
+/// Unsigned and signed integers are rendered in decimal, addresses with their EIP-55 checksum,
+/// bytes as `0x` prefixed hex and strings quoted. Fixed bytes carry their type tag, e.g.
+/// `bytes2(0x1234)`, to tell them apart from dynamic bytes.
 impl fmt::Display for Token {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match *self {
 			Token::Bool(b) => write!(f, "{}", b),
-			Token::String(ref s) => write!(f, "{}", s),
-			Token::Address(ref a) => write!(f, "{:x}", a),
-			Token::Bytes(ref bytes) | Token::FixedBytes(ref bytes) => write!(f, "{}", bytes.to_hex::<String>()),
-			Token::Uint(ref i) | Token::Int(ref i) => write!(f, "{:x}", i),
+			Token::String(ref s) => write!(f, "{:?}", s),
+			Token::Address(ref a) => write!(f, "{}", to_checksum_address(a.as_fixed_bytes())),
+			Token::Bytes(ref bytes) => write!(f, "0x{}", bytes.to_hex::<String>()),
+			Token::FixedBytes(ref bytes) => write!(f, "bytes{}(0x{})", bytes.len(), bytes.to_hex::<String>()),
+			Token::Uint(ref i) => write!(f, "{}", i),
+			Token::Int(ref i) => if i.bit(255) {
+				write!(f, "-{}", (!*i).overflowing_add(1.into()).0)
+			} else {
+				write!(f, "{}", i)
+			},
 			Token::Array(ref arr) | Token::FixedArray(ref arr) => {
 				let s = arr.iter()
 					.map(|ref t| format!("{}", t))
 					.collect::<Vec<String>>()
-					.join(",");
+					.join(", ");
 
 				write!(f, "[{}]", s)
+			},
+			Token::Tuple(ref s) => {
+				let s = s.iter()
+					.map(|ref t| format!("{}", t))
+					.collect::<Vec<String>>()
+					.join(", ");
+
+				write!(f, "({})", s)
 			}
 		}
 	}
 }
 
+impl From<bool> for Token {
+	fn from(value: bool) -> Self {
+		Token::Bool(value)
+	}
+}
+
+/// Integers are converted to `Token::Uint`, widened to 256 bits.
+impl From<u64> for Token {
+	fn from(value: u64) -> Self {
+		Token::Uint(value.into())
+	}
+}
+
+/// Integers are converted to `Token::Uint`, widened to 256 bits.
+impl From<u128> for Token {
+	fn from(value: u128) -> Self {
+		Token::Uint(value.into())
+	}
+}
+
+/// Signed integers are converted to `Token::Int`, in 256 bit two's complement.
+impl From<i64> for Token {
+	fn from(value: i64) -> Self {
+		Token::from(value as i128)
+	}
+}
+
+/// Signed integers are converted to `Token::Int`, in 256 bit two's complement.
+impl From<i128> for Token {
+	fn from(value: i128) -> Self {
+		let magnitude = Uint::from(value.unsigned_abs());
+		if value < 0 {
+			Token::Int((!magnitude).overflowing_add(1.into()).0)
+		} else {
+			Token::Int(magnitude)
+		}
+	}
+}
+
+impl From<[u8; 20]> for Token {
+	fn from(value: [u8; 20]) -> Self {
+		Token::Address(value.into())
+	}
+}
+
+/// Byte vectors are converted to dynamic `Token::Bytes`. Use `Token::FixedBytes` explicitly
+/// for `bytesN` values.
+impl From<Vec<u8>> for Token {
+	fn from(value: Vec<u8>) -> Self {
+		Token::Bytes(value)
+	}
+}
+
+impl From<String> for Token {
+	fn from(value: String) -> Self {
+		Token::String(value)
+	}
+}
+
+impl<'a> From<&'a str> for Token {
+	fn from(value: &'a str) -> Self {
+		Token::String(value.to_owned())
+	}
+}
+
 impl Token {
 	/// Check whether the type of the token matches the given parameter type.
 	///
@@ -113,6 +204,12 @@ impl Token {
 				} else {
 					false
 				},
+			Token::Tuple(ref tokens) =>
+				if let ParamType::Tuple(ref param_type) = *param_type {
+					param_type.len() == tokens.len() && tokens.iter().zip(param_type).all(|(t, p)| t.type_check(p))
+				} else {
+					false
+				},
 		}
 	}
 
@@ -188,6 +285,400 @@ impl Token {
 		}
 	}
 
+	/// Converts token to...
+	pub fn to_tuple(self) -> Option<Vec<Token>> {
+		match self {
+			Token::Tuple(tuple) => Some(tuple),
+			_ => None,
+		}
+	}
+
+	/// Converts token to address, failing with `InvalidTokenType` on any other variant.
+	pub fn into_address(self) -> Result<Address> {
+		match self {
+			Token::Address(address) => Ok(address),
+			other => Err(ErrorKind::InvalidTokenType("address", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to fixed bytes, failing with `InvalidTokenType` on any other variant.
+	pub fn into_fixed_bytes(self) -> Result<FixedBytes> {
+		match self {
+			Token::FixedBytes(bytes) => Ok(bytes),
+			other => Err(ErrorKind::InvalidTokenType("fixed_bytes", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to bytes, failing with `InvalidTokenType` on any other variant.
+	pub fn into_bytes(self) -> Result<Bytes> {
+		match self {
+			Token::Bytes(bytes) => Ok(bytes),
+			other => Err(ErrorKind::InvalidTokenType("bytes", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to int, failing with `InvalidTokenType` on any other variant.
+	pub fn into_int(self) -> Result<Uint> {
+		match self {
+			Token::Int(int) => Ok(int),
+			other => Err(ErrorKind::InvalidTokenType("int", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to uint, failing with `InvalidTokenType` on any other variant.
+	pub fn into_uint(self) -> Result<Uint> {
+		match self {
+			Token::Uint(uint) => Ok(uint),
+			other => Err(ErrorKind::InvalidTokenType("uint", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to bool, failing with `InvalidTokenType` on any other variant.
+	pub fn into_bool(self) -> Result<bool> {
+		match self {
+			Token::Bool(b) => Ok(b),
+			other => Err(ErrorKind::InvalidTokenType("bool", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to string, failing with `InvalidTokenType` on any other variant.
+	pub fn into_string(self) -> Result<String> {
+		match self {
+			Token::String(s) => Ok(s),
+			other => Err(ErrorKind::InvalidTokenType("string", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to fixed array, failing with `InvalidTokenType` on any other variant.
+	pub fn into_fixed_array(self) -> Result<Vec<Token>> {
+		match self {
+			Token::FixedArray(arr) => Ok(arr),
+			other => Err(ErrorKind::InvalidTokenType("fixed_array", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to array, failing with `InvalidTokenType` on any other variant.
+	pub fn into_array(self) -> Result<Vec<Token>> {
+		match self {
+			Token::Array(arr) => Ok(arr),
+			other => Err(ErrorKind::InvalidTokenType("array", other.kind_name()).into()),
+		}
+	}
+
+	/// Converts token to tuple, failing with `InvalidTokenType` on any other variant.
+	pub fn into_tuple(self) -> Result<Vec<Token>> {
+		match self {
+			Token::Tuple(tuple) => Ok(tuple),
+			other => Err(ErrorKind::InvalidTokenType("tuple", other.kind_name()).into()),
+		}
+	}
+
+	/// Returns a reference to the address held by the token, if any.
+	pub fn as_address(&self) -> Option<&Address> {
+		match *self {
+			Token::Address(ref address) => Some(address),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the fixed bytes held by the token, if any.
+	pub fn as_fixed_bytes(&self) -> Option<&[u8]> {
+		match *self {
+			Token::FixedBytes(ref bytes) => Some(&bytes[..]),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the bytes held by the token, if any.
+	pub fn as_bytes(&self) -> Option<&[u8]> {
+		match *self {
+			Token::Bytes(ref bytes) => Some(&bytes[..]),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the int held by the token, if any.
+	pub fn as_int(&self) -> Option<&Uint> {
+		match *self {
+			Token::Int(ref int) => Some(int),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the uint held by the token, if any.
+	pub fn as_uint(&self) -> Option<&Uint> {
+		match *self {
+			Token::Uint(ref uint) => Some(uint),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the bool held by the token, if any.
+	pub fn as_bool(&self) -> Option<bool> {
+		match *self {
+			Token::Bool(b) => Some(b),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the string held by the token, if any.
+	pub fn as_string(&self) -> Option<&str> {
+		match *self {
+			Token::String(ref s) => Some(&s[..]),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the fixed array held by the token, if any.
+	pub fn as_fixed_array(&self) -> Option<&[Token]> {
+		match *self {
+			Token::FixedArray(ref arr) => Some(&arr[..]),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the array held by the token, if any.
+	pub fn as_array(&self) -> Option<&[Token]> {
+		match *self {
+			Token::Array(ref arr) => Some(&arr[..]),
+			_ => None,
+		}
+	}
+
+	/// Returns a reference to the tuple held by the token, if any.
+	pub fn as_tuple(&self) -> Option<&[Token]> {
+		match *self {
+			Token::Tuple(ref tuple) => Some(&tuple[..]),
+			_ => None,
+		}
+	}
+
+	/// Returns an iterator over the elements of array, fixed array and tuple tokens,
+	/// `None` for other tokens.
+	pub fn iter(&self) -> Option<slice::Iter<'_, Token>> {
+		self.elements().map(|elements| elements.iter())
+	}
+
+	/// Consumes the token, returning an iterator over the elements of array, fixed array
+	/// and tuple tokens, `None` for other tokens.
+	// returns `None` for tokens without elements, so it can't implement `IntoIterator`
+	#[allow(clippy::should_implement_trait)]
+	pub fn into_iter(self) -> Option<::alloc::vec::IntoIter<Token>> {
+		match self {
+			Token::FixedArray(tokens) | Token::Array(tokens) | Token::Tuple(tokens) => Some(tokens.into_iter()),
+			_ => None,
+		}
+	}
+
+	/// Returns the number of elements of array, fixed array and tuple tokens, `None` for other tokens.
+	pub fn len(&self) -> Option<usize> {
+		self.elements().map(|elements| elements.len())
+	}
+
+	/// Returns whether array, fixed array and tuple tokens have no elements, `None` for other tokens.
+	pub fn is_empty(&self) -> Option<bool> {
+		self.elements().map(|elements| elements.is_empty())
+	}
+
+	/// Returns the nested token at `path`, dot separated indices into tuples and arrays,
+	/// e.g. `0.1` for the second element of the first element of the token.
+	///
+	/// Returns `None` if an index is out of bounds or applied to a token without elements.
+	/// The empty path returns the token itself.
+	pub fn path_get(&self, path: &str) -> Option<&Token> {
+		if path.is_empty() {
+			return Some(self);
+		}
+
+		path.split('.').try_fold(self, |token, index| {
+			token.elements()?.get(index.parse::<usize>().ok()?)
+		})
+	}
+
+	fn elements(&self) -> Option<&[Token]> {
+		match *self {
+			Token::FixedArray(ref tokens) | Token::Array(ref tokens) | Token::Tuple(ref tokens) => Some(tokens),
+			_ => None,
+		}
+	}
+
+	/// Name of the token variant, as used in error messages.
+	fn kind_name(&self) -> &'static str {
+		match *self {
+			Token::Address(_) => "address",
+			Token::FixedBytes(_) => "fixed_bytes",
+			Token::Bytes(_) => "bytes",
+			Token::Int(_) => "int",
+			Token::Uint(_) => "uint",
+			Token::Bool(_) => "bool",
+			Token::String(_) => "string",
+			Token::FixedArray(_) => "fixed_array",
+			Token::Array(_) => "array",
+			Token::Tuple(_) => "tuple",
+		}
+	}
+
+	/// Encodes the token as a single standalone ABI value.
+	pub fn encode_abi(&self) -> Bytes {
+		encode(slice::from_ref(self))
+	}
+
+	/// Decodes a single ABI value of the given type.
+	pub fn decode_abi(data: &[u8], param_type: &ParamType) -> Result<Token> {
+		let mut tokens = decode(slice::from_ref(param_type), data)?;
+		Ok(tokens.remove(0))
+	}
+
+	/// Infers the parameter type of the token.
+	///
+	/// The inference is lossy: integers are always reported as 256 bit wide, since the
+	/// original width can't be recovered from the value, and element types of arrays are
+	/// taken from their first element. Empty arrays default to `uint256` elements.
+	pub fn type_of(&self) -> ParamType {
+		match *self {
+			Token::Address(_) => ParamType::Address,
+			Token::FixedBytes(ref bytes) => ParamType::FixedBytes(bytes.len()),
+			Token::Bytes(_) => ParamType::Bytes,
+			Token::Int(_) => ParamType::Int(256),
+			Token::Uint(_) => ParamType::Uint(256),
+			Token::Bool(_) => ParamType::Bool,
+			Token::String(_) => ParamType::String,
+			Token::FixedArray(ref tokens) => ParamType::FixedArray(Box::new(Token::element_type_of(tokens)), tokens.len()),
+			Token::Array(ref tokens) => ParamType::Array(Box::new(Token::element_type_of(tokens))),
+			Token::Tuple(ref tokens) => ParamType::Tuple(tokens.iter().map(|t| Box::new(t.type_of())).collect()),
+		}
+	}
+
+	fn element_type_of(tokens: &[Token]) -> ParamType {
+		tokens.first().map_or(ParamType::Uint(256), Token::type_of)
+	}
+
+	/// Checks that the token structurally matches the given parameter type.
+	///
+	/// Unlike `type_check`, integers must fit in the declared bit width and the
+	/// returned error points at the offending nested token, e.g.
+	/// `token at tuple index 2: expected uint256, got bool`.
+	pub fn validate(&self, param_type: &ParamType) -> Result<()> {
+		self.validate_at(param_type, "")
+	}
+
+	/// Validates all tokens against the given parameter types, see `validate`.
+	pub fn validate_all(tokens: &[Token], param_types: &[ParamType]) -> Result<()> {
+		if tokens.len() != param_types.len() {
+			return Err(ErrorKind::InvalidData.into());
+		}
+
+		for (index, (token, param_type)) in tokens.iter().zip(param_types).enumerate() {
+			token.validate_at(param_type, &format!("param {}", index))?;
+		}
+
+		Ok(())
+	}
+
+	fn validate_at(&self, param_type: &ParamType, path: &str) -> Result<()> {
+		let mismatch = |got: String| -> Error {
+			let location = if path.is_empty() {
+				"token".to_owned()
+			} else {
+				format!("token at {}", path)
+			};
+			ErrorKind::TokenMismatch(location, param_type.to_string(), got).into()
+		};
+		let child_path = |kind: &str, index: usize| {
+			if path.is_empty() {
+				format!("{} index {}", kind, index)
+			} else {
+				format!("{}, {} index {}", path, kind, index)
+			}
+		};
+
+		match (self, param_type) {
+			(Token::Address(_), ParamType::Address) |
+			(Token::Bytes(_), ParamType::Bytes) |
+			(Token::Bool(_), ParamType::Bool) |
+			(Token::String(_), ParamType::String) => Ok(()),
+			(Token::FixedBytes(bytes), ParamType::FixedBytes(size)) => if bytes.len() <= *size {
+				Ok(())
+			} else {
+				Err(mismatch(format!("{} bytes", bytes.len())))
+			},
+			(Token::Uint(value), ParamType::Uint(size)) => if value.bits() <= *size {
+				Ok(())
+			} else {
+				Err(mismatch(format!("out of range value {}", value)))
+			},
+			(Token::Int(value), ParamType::Int(size)) => {
+				// two's complement value fits when its magnitude leaves the sign bit free
+				let magnitude = if value.bit(255) { !*value } else { *value };
+				if magnitude.bits() < *size {
+					Ok(())
+				} else {
+					Err(mismatch(format!("out of range value {}", self)))
+				}
+			},
+			(Token::Array(tokens), ParamType::Array(inner)) => {
+				for (index, token) in tokens.iter().enumerate() {
+					token.validate_at(inner, &child_path("array", index))?;
+				}
+				Ok(())
+			},
+			(Token::FixedArray(tokens), ParamType::FixedArray(inner, size)) => {
+				if tokens.len() != *size {
+					return Err(mismatch(format!("fixed_array of length {}", tokens.len())));
+				}
+				for (index, token) in tokens.iter().enumerate() {
+					token.validate_at(inner, &child_path("array", index))?;
+				}
+				Ok(())
+			},
+			(Token::Tuple(tokens), ParamType::Tuple(params)) => {
+				if tokens.len() != params.len() {
+					return Err(mismatch(format!("tuple of length {}", tokens.len())));
+				}
+				for (index, (token, param)) in tokens.iter().zip(params).enumerate() {
+					token.validate_at(param, &child_path("tuple", index))?;
+				}
+				Ok(())
+			},
+			_ => Err(mismatch(self.kind_name().to_owned())),
+		}
+	}
+
+	/// Converts the token to a token of the given type, widening it when needed, e.g. to fit
+	/// values parsed from JSON into the declared params.
+	///
+	/// Unsigned integers convert to signed integers and non-negative signed integers to unsigned
+	/// integers, fixed bytes are right padded with zeros to the declared size. Arrays and tuples
+	/// are converted element by element. Fails if the value doesn't fit in the type, e.g. when
+	/// narrowing integers or fixed bytes, or if the token doesn't match the type at all.
+	pub fn coerce_to(&self, param_type: &ParamType) -> Result<Token> {
+		let coerced = match (self, param_type) {
+			(Token::Uint(value), ParamType::Int(_)) if !value.bit(255) => Token::Int(*value),
+			(Token::Int(value), ParamType::Uint(_)) if !value.bit(255) => Token::Uint(*value),
+			(Token::FixedBytes(bytes), ParamType::FixedBytes(size)) if bytes.len() <= *size => {
+				let mut bytes = bytes.clone();
+				bytes.resize(*size, 0);
+				Token::FixedBytes(bytes)
+			},
+			(Token::Array(tokens), ParamType::Array(inner)) => {
+				return tokens.iter().map(|token| token.coerce_to(inner)).collect::<Result<_>>().map(Token::Array);
+			},
+			(Token::FixedArray(tokens), ParamType::FixedArray(inner, size)) if tokens.len() == *size => {
+				return tokens.iter().map(|token| token.coerce_to(inner)).collect::<Result<_>>().map(Token::FixedArray);
+			},
+			(Token::Tuple(tokens), ParamType::Tuple(params)) if tokens.len() == params.len() => {
+				return tokens.iter().zip(params)
+					.map(|(token, param)| token.coerce_to(param))
+					.collect::<Result<_>>()
+					.map(Token::Tuple);
+			},
+			_ => self.clone(),
+		};
+
+		coerced.validate(param_type)?;
+		Ok(coerced)
+	}
+
 	/// Check if all the types of the tokens match the given parameter types.
 	pub fn types_check(tokens: &[Token], param_types: &[ParamType]) -> bool {
 		param_types.len() == tokens.len() && {
@@ -201,7 +692,7 @@ impl Token {
 
 #[cfg(test)]
 mod tests {
-	use {Token, ParamType};
+	use {Token, ParamType, Uint, Error, ErrorKind};
 
 	#[test]
 	fn test_type_check() {
@@ -232,5 +723,343 @@ mod tests {
 		assert_not_type_check(vec![Token::FixedArray(vec![Token::Bool(false), Token::Bool(true)])], vec![ParamType::FixedArray(Box::new(ParamType::Bool), 3)]);
 		assert_not_type_check(vec![Token::FixedArray(vec![Token::Bool(false), Token::Uint(0.into())])], vec![ParamType::FixedArray(Box::new(ParamType::Bool), 2)]);
 		assert_not_type_check(vec![Token::FixedArray(vec![Token::Bool(false), Token::Bool(true)])], vec![ParamType::FixedArray(Box::new(ParamType::Address), 2)]);
+
+		assert_type_check(vec![Token::Tuple(vec![Token::Bool(false), Token::Uint(0.into())])], vec![ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Uint(256))])]);
+		assert_not_type_check(vec![Token::Tuple(vec![Token::Bool(false), Token::Uint(0.into())])], vec![ParamType::Tuple(tuple_components![Box::new(ParamType::Uint(256)), Box::new(ParamType::Bool)])]);
+		assert_not_type_check(vec![Token::Tuple(vec![Token::Bool(false)])], vec![ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Bool)])]);
+	}
+
+	#[test]
+	fn test_into_accessors() {
+		assert_eq!(Token::Address([1u8; 20].into()).into_address().unwrap(), [1u8; 20].into());
+		assert_eq!(Token::FixedBytes(vec![1, 2]).into_fixed_bytes().unwrap(), vec![1, 2]);
+		assert_eq!(Token::Bytes(vec![3]).into_bytes().unwrap(), vec![3]);
+		assert_eq!(Token::Int(5.into()).into_int().unwrap(), 5.into());
+		assert_eq!(Token::Uint(7.into()).into_uint().unwrap(), 7.into());
+		assert_eq!(Token::Bool(true).into_bool().unwrap(), true);
+		assert_eq!(Token::String("abc".into()).into_string().unwrap(), "abc");
+		assert_eq!(Token::FixedArray(vec![Token::Bool(true)]).into_fixed_array().unwrap(), vec![Token::Bool(true)]);
+		assert_eq!(Token::Array(vec![Token::Bool(false)]).into_array().unwrap(), vec![Token::Bool(false)]);
+		assert_eq!(Token::Tuple(vec![Token::Uint(1.into())]).into_tuple().unwrap(), vec![Token::Uint(1.into())]);
+	}
+
+	#[test]
+	fn test_into_accessor_wrong_type() {
+		match Token::Bool(true).into_uint() {
+			Err(Error(ErrorKind::InvalidTokenType("uint", "bool"), _)) => {},
+			other => panic!("unexpected result: {:?}", other),
+		}
+		assert!(Token::Int(1.into()).into_uint().is_err());
+		assert!(Token::Array(vec![]).into_fixed_array().is_err());
+		assert!(Token::Bytes(vec![]).into_fixed_bytes().is_err());
+		assert_eq!(
+			format!("{}", Token::String("a".into()).into_tuple().unwrap_err()),
+			"Invalid token type, expected `tuple` but got `string`"
+		);
+	}
+
+	#[test]
+	fn test_as_accessors() {
+		assert_eq!(Token::Address([1u8; 20].into()).as_address(), Some(&[1u8; 20].into()));
+		assert_eq!(Token::FixedBytes(vec![1, 2]).as_fixed_bytes(), Some(&[1u8, 2][..]));
+		assert_eq!(Token::Bytes(vec![3]).as_bytes(), Some(&[3u8][..]));
+		assert_eq!(Token::Int(5.into()).as_int(), Some(&5.into()));
+		assert_eq!(Token::Uint(7.into()).as_uint(), Some(&7.into()));
+		assert_eq!(Token::Bool(true).as_bool(), Some(true));
+		assert_eq!(Token::String("abc".into()).as_string(), Some("abc"));
+		assert_eq!(Token::FixedArray(vec![Token::Bool(true)]).as_fixed_array(), Some(&[Token::Bool(true)][..]));
+		assert_eq!(Token::Array(vec![]).as_array(), Some(&[][..]));
+		assert_eq!(Token::Tuple(vec![Token::Bool(false)]).as_tuple(), Some(&[Token::Bool(false)][..]));
+
+		assert_eq!(Token::Bool(true).as_uint(), None);
+		assert_eq!(Token::Uint(1.into()).as_int(), None);
+		assert_eq!(Token::FixedArray(vec![]).as_array(), None);
+		assert_eq!(Token::Bytes(vec![]).as_string(), None);
+	}
+
+	#[test]
+	fn test_from_impls() {
+		assert_eq!(Token::from(true), Token::Bool(true));
+		assert_eq!(Token::from(42u64), Token::Uint(42.into()));
+		assert_eq!(Token::from(u128::max_value()), Token::Uint(Uint::from(u128::max_value())));
+		assert_eq!(Token::from([7u8; 20]), Token::Address([7u8; 20].into()));
+		assert_eq!(Token::from(vec![1u8, 2, 3]), Token::Bytes(vec![1, 2, 3]));
+		assert_eq!(Token::from("hello".to_owned()), Token::String("hello".into()));
+		assert_eq!(Token::from("hello"), Token::String("hello".into()));
+	}
+
+	#[test]
+	fn test_display() {
+		assert_eq!(format!("{}", Token::Bool(true)), "true");
+		assert_eq!(format!("{}", Token::Uint(100.into())), "100");
+		assert_eq!(format!("{}", Token::Int(100.into())), "100");
+		assert_eq!(format!("{}", Token::Int(Uint::max_value())), "-1");
+		assert_eq!(format!("{}", Token::Int(!Uint::from(1))), "-2");
+		assert_eq!(format!("{}", Token::Address([0x11u8; 20].into())), "0x1111111111111111111111111111111111111111");
+		assert_eq!(format!("{}", Token::Address(hex!("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").into())), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+		assert_eq!(format!("{}", Token::Bytes(vec![0x12, 0x34])), "0x1234");
+		assert_eq!(format!("{}", Token::Bytes(vec![])), "0x");
+		assert_eq!(format!("{}", Token::FixedBytes(vec![0x12, 0x34])), "bytes2(0x1234)");
+		assert_eq!(format!("{}", Token::String("a \"quoted\" value".into())), r#""a \"quoted\" value""#);
+	}
+
+	#[test]
+	fn test_display_nested() {
+		let token = Token::Tuple(vec![
+			Token::Address([0x22u8; 20].into()),
+			Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+			Token::FixedArray(vec![
+				Token::Tuple(vec![Token::Bool(false), Token::String("x".into())]),
+			]),
+			Token::Array(vec![]),
+			Token::Tuple(vec![]),
+		]);
+
+		assert_eq!(
+			format!("{}", token),
+			r#"(0x2222222222222222222222222222222222222222, [1, 2], [(false, "x")], [], ())"#
+		);
+	}
+
+	#[test]
+	fn test_validate() {
+		let param = ParamType::Tuple(tuple_components![
+			Box::new(ParamType::Address),
+			Box::new(ParamType::FixedArray(Box::new(ParamType::Int(8)), 2)),
+			Box::new(ParamType::Array(Box::new(ParamType::Uint(8)))),
+			Box::new(ParamType::FixedBytes(4)),
+		]);
+		let token = Token::Tuple(vec![
+			Token::Address([0u8; 20].into()),
+			Token::FixedArray(vec![Token::Int(127.into()), Token::Int(!Uint::from(127))]),
+			Token::Array(vec![Token::Uint(255.into())]),
+			Token::FixedBytes(vec![1, 2, 3, 4]),
+		]);
+
+		token.validate(&param).unwrap();
+		Token::Uint(Uint::max_value()).validate(&ParamType::Uint(256)).unwrap();
+		Token::Int(Uint::max_value()).validate(&ParamType::Int(8)).unwrap();
+	}
+
+	#[test]
+	fn test_validate_errors() {
+		fn error(token: Token, param: ParamType) -> String {
+			format!("{}", token.validate(&param).unwrap_err())
+		}
+
+		assert_eq!(error(Token::Bool(true), ParamType::Address), "token: expected address, got bool");
+		assert_eq!(error(Token::Uint(256.into()), ParamType::Uint(8)), "token: expected uint8, got out of range value 256");
+		assert_eq!(error(Token::Int(128.into()), ParamType::Int(8)), "token: expected int8, got out of range value 128");
+		assert_eq!(error(Token::Int(!Uint::from(128)), ParamType::Int(8)), "token: expected int8, got out of range value -129");
+		assert_eq!(error(Token::FixedBytes(vec![0; 5]), ParamType::FixedBytes(4)), "token: expected bytes4, got 5 bytes");
+		assert_eq!(
+			error(Token::FixedArray(vec![Token::Bool(true)]), ParamType::FixedArray(Box::new(ParamType::Bool), 2)),
+			"token: expected bool[2], got fixed_array of length 1"
+		);
+		assert_eq!(
+			error(
+				Token::Tuple(vec![Token::Bool(true), Token::Bool(true), Token::Bool(true)]),
+				ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Bool), Box::new(ParamType::Uint(256))])
+			),
+			"token at tuple index 2: expected uint256, got bool"
+		);
+		assert_eq!(
+			error(
+				Token::Array(vec![Token::Tuple(vec![]), Token::Tuple(vec![Token::Uint(1.into())])]),
+				ParamType::Array(Box::new(ParamType::Tuple(tuple_components![])))
+			),
+			"token at array index 1: expected (), got tuple of length 1"
+		);
+	}
+
+	#[test]
+	fn test_validate_all() {
+		let params = [ParamType::Bool, ParamType::Array(Box::new(ParamType::Address))];
+
+		Token::validate_all(&[Token::Bool(true), Token::Array(vec![])], &params).unwrap();
+		assert!(Token::validate_all(&[Token::Bool(true)], &params).is_err());
+		assert_eq!(
+			format!("{}", Token::validate_all(&[Token::Bool(true), Token::Array(vec![Token::Bool(false)])], &params).unwrap_err()),
+			"token at param 1, array index 0: expected address, got bool"
+		);
+	}
+
+	#[test]
+	fn test_type_of() {
+		assert_eq!(Token::Address([0u8; 20].into()).type_of(), ParamType::Address);
+		assert_eq!(Token::FixedBytes(vec![0; 3]).type_of(), ParamType::FixedBytes(3));
+		assert_eq!(Token::Bytes(vec![0; 3]).type_of(), ParamType::Bytes);
+		assert_eq!(Token::Int(1.into()).type_of(), ParamType::Int(256));
+		assert_eq!(Token::Uint(1.into()).type_of(), ParamType::Uint(256));
+		assert_eq!(Token::Bool(true).type_of(), ParamType::Bool);
+		assert_eq!(Token::String("".into()).type_of(), ParamType::String);
+		assert_eq!(
+			Token::FixedArray(vec![Token::Bool(true), Token::Bool(false)]).type_of(),
+			ParamType::FixedArray(Box::new(ParamType::Bool), 2)
+		);
+		assert_eq!(
+			Token::Array(vec![Token::Array(vec![Token::String("a".into())])]).type_of(),
+			ParamType::Array(Box::new(ParamType::Array(Box::new(ParamType::String))))
+		);
+		assert_eq!(
+			Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![])]).type_of(),
+			ParamType::Tuple(tuple_components![Box::new(ParamType::Bool), Box::new(ParamType::Bytes)])
+		);
+	}
+
+	#[test]
+	fn test_type_of_empty() {
+		assert_eq!(Token::Array(vec![]).type_of(), ParamType::Array(Box::new(ParamType::Uint(256))));
+		assert_eq!(Token::FixedArray(vec![]).type_of(), ParamType::FixedArray(Box::new(ParamType::Uint(256)), 0));
+		assert_eq!(Token::Tuple(vec![]).type_of(), ParamType::Tuple(tuple_components![]));
+		assert_eq!(Token::FixedBytes(vec![]).type_of(), ParamType::FixedBytes(0));
+	}
+
+	#[test]
+	fn test_encode_decode_abi_roundtrip() {
+		let tokens = vec![
+			Token::Address([0x11u8; 20].into()),
+			Token::FixedBytes(vec![1, 2, 3]),
+			Token::Bytes(vec![4; 40]),
+			Token::Int(!Uint::from(1)),
+			Token::Uint(Uint::max_value()),
+			Token::Bool(true),
+			Token::String("gavofyork".into()),
+			Token::FixedArray(vec![Token::String("a".into()), Token::String("b".into())]),
+			Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+			Token::Array(vec![]),
+			Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![5])]),
+			Token::Array(vec![Token::Tuple(vec![Token::Address([0x22u8; 20].into()), Token::Bool(true)])]),
+			Token::Tuple(vec![Token::Address([0x22u8; 20].into()), Token::Array(vec![Token::Bool(true)])]),
+		];
+
+		for token in tokens {
+			assert_eq!(Token::decode_abi(&token.encode_abi(), &token.type_of()).unwrap(), token);
+		}
+	}
+
+	#[test]
+	fn test_encode_abi() {
+		assert_eq!(Token::Bool(true).encode_abi(), ::encode(&[Token::Bool(true)]));
+		assert!(Token::decode_abi(&[0u8; 31], &ParamType::Bool).is_err());
+	}
+
+	#[test]
+	fn coerce_to() {
+		let minus_one = Token::Int(!Uint::zero());
+		let max = Uint::max_value();
+
+		assert_eq!(Token::Uint(5.into()).coerce_to(&ParamType::Uint(256)).unwrap(), Token::Uint(5.into()));
+		assert_eq!(Token::Uint(255.into()).coerce_to(&ParamType::Uint(8)).unwrap(), Token::Uint(255.into()));
+		assert!(Token::Uint(256.into()).coerce_to(&ParamType::Uint(8)).is_err());
+		assert_eq!(Token::Uint(max).coerce_to(&ParamType::Uint(256)).unwrap(), Token::Uint(max));
+
+		assert_eq!(Token::Uint(5.into()).coerce_to(&ParamType::Int(256)).unwrap(), Token::Int(5.into()));
+		assert_eq!(Token::Uint(127.into()).coerce_to(&ParamType::Int(8)).unwrap(), Token::Int(127.into()));
+		assert!(Token::Uint(128.into()).coerce_to(&ParamType::Int(8)).is_err());
+		assert!(Token::Uint(max).coerce_to(&ParamType::Int(256)).is_err());
+		assert!(Token::Uint(max >> 1).coerce_to(&ParamType::Int(256)).is_ok());
+		assert!(Token::Uint((max >> 1) + 1).coerce_to(&ParamType::Int(256)).is_err());
+
+		assert_eq!(minus_one.coerce_to(&ParamType::Int(8)).unwrap(), minus_one);
+		let minus_128 = Token::Int(!Uint::from(127));
+		assert_eq!(minus_128.coerce_to(&ParamType::Int(8)).unwrap(), minus_128);
+		assert!(Token::Int(!Uint::from(128)).coerce_to(&ParamType::Int(8)).is_err());
+		assert!(minus_one.coerce_to(&ParamType::Uint(256)).is_err());
+		assert_eq!(Token::Int(200.into()).coerce_to(&ParamType::Uint(8)).unwrap(), Token::Uint(200.into()));
+		assert!(Token::Int(256.into()).coerce_to(&ParamType::Uint(8)).is_err());
+
+		let mut padded = vec![0, 1, 2];
+		padded.resize(32, 0);
+		assert_eq!(Token::FixedBytes(vec![0, 1, 2]).coerce_to(&ParamType::FixedBytes(32)).unwrap(), Token::FixedBytes(padded));
+		assert_eq!(Token::FixedBytes(vec![1, 2]).coerce_to(&ParamType::FixedBytes(2)).unwrap(), Token::FixedBytes(vec![1, 2]));
+		assert!(Token::FixedBytes(vec![0, 1, 2]).coerce_to(&ParamType::FixedBytes(2)).is_err());
+
+		let tuple = Token::Tuple(vec![
+			Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+			Token::FixedArray(vec![Token::FixedBytes(vec![0xff])]),
+		]);
+		let kind: ParamType = "(int16[],bytes2[1])".parse().unwrap();
+		assert_eq!(tuple.coerce_to(&kind).unwrap(), Token::Tuple(vec![
+			Token::Array(vec![Token::Int(1.into()), Token::Int(2.into())]),
+			Token::FixedArray(vec![Token::FixedBytes(vec![0xff, 0])]),
+		]));
+		assert!(tuple.coerce_to(&"(uint8[],bytes1[2])".parse().unwrap()).is_err());
+		assert!(tuple.coerce_to(&"(uint8[])".parse().unwrap()).is_err());
+
+		assert!(Token::Bool(true).coerce_to(&ParamType::Uint(8)).is_err());
+		assert!(Token::Uint(1.into()).coerce_to(&ParamType::Bool).is_err());
+		assert_eq!(Token::String("a".into()).coerce_to(&ParamType::String).unwrap(), Token::String("a".into()));
+		let err = Token::Uint(300.into()).coerce_to(&ParamType::Uint(8)).unwrap_err();
+		assert_eq!(format!("{}", err), "token: expected uint8, got out of range value 300");
+	}
+
+	#[test]
+	fn iter_elements() {
+		let kind: ParamType = "(address,uint256)[]".parse().unwrap();
+		let transfers = Token::Array(vec![
+			Token::Tuple(vec![Token::Address([1u8; 20].into()), Token::Uint(100.into())]),
+			Token::Tuple(vec![Token::Address([2u8; 20].into()), Token::Uint(0.into())]),
+			Token::Tuple(vec![Token::Address([3u8; 20].into()), Token::Uint(7.into())]),
+		]);
+		let decoded = Token::decode_abi(&transfers.encode_abi(), &kind).unwrap();
+
+		assert_eq!(decoded.len(), Some(3));
+		assert_eq!(decoded.is_empty(), Some(false));
+		let amounts: Vec<Uint> = decoded.iter().unwrap()
+			.flat_map(|transfer| transfer.iter().unwrap().skip(1))
+			.filter_map(|amount| amount.as_uint().cloned())
+			.filter(|amount| !amount.is_zero())
+			.collect();
+		assert_eq!(amounts, vec![Uint::from(100), Uint::from(7)]);
+
+		let recipients: Vec<Token> = decoded.into_iter().unwrap()
+			.flat_map(|transfer| transfer.into_iter().unwrap().take(1))
+			.collect();
+		assert_eq!(recipients, vec![
+			Token::Address([1u8; 20].into()),
+			Token::Address([2u8; 20].into()),
+			Token::Address([3u8; 20].into()),
+		]);
+
+		assert_eq!(Token::FixedArray(vec![Token::Bool(true)]).len(), Some(1));
+		assert_eq!(Token::Tuple(vec![]).is_empty(), Some(true));
+		assert_eq!(Token::Array(vec![]).iter().unwrap().count(), 0);
+		assert!(Token::Bytes(vec![1, 2]).iter().is_none());
+		assert!(Token::String("ab".into()).into_iter().is_none());
+		assert_eq!(Token::Bytes(vec![1, 2]).len(), None);
+		assert_eq!(Token::Uint(0.into()).is_empty(), None);
+	}
+
+	#[test]
+	fn path_get() {
+		let kind: ParamType = "((address,uint256),(bytes,bool[]),((string,int8)[2]))".parse().unwrap();
+		let token = Token::Tuple(vec![
+			Token::Tuple(vec![Token::Address([1u8; 20].into()), Token::Uint(5.into())]),
+			Token::Tuple(vec![Token::Bytes(vec![1, 2]), Token::Array(vec![Token::Bool(true), Token::Bool(false)])]),
+			Token::Tuple(vec![Token::FixedArray(vec![
+				Token::Tuple(vec![Token::String("a".into()), Token::Int(1.into())]),
+				Token::Tuple(vec![Token::String("b".into()), Token::Int(2.into())]),
+			])]),
+		]);
+		let token = Token::decode_abi(&token.encode_abi(), &kind).unwrap();
+
+		assert_eq!(token.path_get(""), Some(&token));
+		assert_eq!(token.path_get("0.1"), Some(&Token::Uint(5.into())));
+		assert_eq!(token.path_get("1.0"), Some(&Token::Bytes(vec![1, 2])));
+		assert_eq!(token.path_get("1.1.1"), Some(&Token::Bool(false)));
+		assert_eq!(token.path_get("2.0.1.0"), Some(&Token::String("b".into())));
+		assert_eq!(token.path_get("2.0.1").and_then(|t| t.path_get("1")), Some(&Token::Int(2.into())));
+		assert_eq!(token.path_get("0").and_then(Token::len), Some(2));
+
+		assert_eq!(token.path_get("3"), None);
+		assert_eq!(token.path_get("1.1.2"), None);
+		assert_eq!(token.path_get("2.0.2.0"), None);
+		assert_eq!(token.path_get("0.1.0"), None);
+		assert_eq!(token.path_get("1.0.0"), None);
+		assert_eq!(token.path_get("0.x"), None);
+		assert_eq!(token.path_get("0..1"), None);
+		assert_eq!(token.path_get("0."), None);
+		assert_eq!(token.path_get("-1"), None);
 	}
 }
@@ -0,0 +1,310 @@
+//! Tuple param, a component of a tuple type.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error as SerdeError;
+use param_type::{Reader, Writer};
+use {ParamType, ErrorKind, Result};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Tuple param, describing a component of a tuple type in the ABI json.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TupleParam {
+	/// Param name, if the component is named.
+	pub name: Option<String>,
+	/// Param type.
+	pub kind: ParamType,
+	/// Solidity type of the param, e.g. `struct Pool.Key` or `enum State`, if known.
+	pub internal_type: Option<String>,
+	/// Components of the param type if it is a tuple or an array of tuples, keeping their names
+	/// and internal types. Empty for other types and if no component has a name or internal type.
+	pub components: Vec<TupleParam>,
+}
+
+impl TupleParam {
+	/// Unnamed component of type `kind`.
+	fn unnamed(kind: &ParamType) -> Self {
+		TupleParam { name: None, kind: kind.clone(), internal_type: None, components: vec![] }
+	}
+
+	/// Returns true if the component tells nothing more than its type.
+	fn is_unnamed(&self) -> bool {
+		self.name.is_none() && self.internal_type.is_none() && self.components.is_empty()
+	}
+}
+
+/// Returns `components`, or nothing if none of them has a name or internal type, so that they
+/// don't have to be listed for params built from a `ParamType` alone.
+pub fn named_components(components: Vec<TupleParam>) -> Vec<TupleParam> {
+	if components.iter().all(TupleParam::is_unnamed) {
+		vec![]
+	} else {
+		components
+	}
+}
+
+/// Returns the description of tuple components of types `kinds`, using `components` where they
+/// have the same type, e.g. unless `kind` of a param was changed after it was parsed.
+fn describe_components(kinds: &[Box<ParamType>], components: &[TupleParam]) -> Vec<TupleParam> {
+	kinds.iter()
+		.enumerate()
+		.map(|(i, kind)| match components.get(i) {
+			Some(component) if component.kind == **kind => component.clone(),
+			_ => TupleParam::unnamed(kind),
+		})
+		.collect()
+}
+
+/// Writes `param_type` in the human readable form, like `Writer::write`, but with the names of
+/// tuple components taken from `components`, e.g. `(address token, uint256)[]`.
+pub fn write_named(param_type: &ParamType, components: &[TupleParam]) -> String {
+	match *param_type {
+		ParamType::Array(ref inner) => format!("{}[]", write_named(inner, components)),
+		ParamType::FixedArray(ref inner, len) => format!("{}[{}]", write_named(inner, components), len),
+		ParamType::Tuple(ref kinds) if !components.is_empty() => {
+			let components = describe_components(kinds, components).into_iter()
+				.map(|c| match c.name {
+					Some(ref name) if !name.is_empty() => format!("{} {}", write_named(&c.kind, &c.components), name),
+					_ => write_named(&c.kind, &c.components),
+				})
+				.collect::<Vec<String>>()
+				.join(", ");
+			format!("({})", components)
+		},
+		_ => Writer::write(param_type),
+	}
+}
+
+/// Param type as found in the ABI json. Tuple types are written as `tuple`, followed by
+/// the array suffixes, e.g. `tuple[2][]`, and their component types are listed in `components`.
+#[derive(Serialize, Deserialize)]
+pub struct AbiType {
+	#[serde(rename = "type")]
+	kind: String,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	components: Vec<TupleParam>,
+}
+
+impl AbiType {
+	/// Describes `param_type` in the ABI json format, with the names and internal types of tuple
+	/// components taken from `components`.
+	pub fn new(param_type: &ParamType, components: &[TupleParam]) -> Self {
+		let mut kind = param_type;
+		let mut suffix = String::new();
+		loop {
+			match *kind {
+				ParamType::Array(ref inner) => {
+					suffix = format!("[]{}", suffix);
+					kind = inner;
+				},
+				ParamType::FixedArray(ref inner, len) => {
+					suffix = format!("[{}]{}", len, suffix);
+					kind = inner;
+				},
+				ParamType::Tuple(ref kinds) => return AbiType {
+					kind: format!("tuple{}", suffix),
+					components: describe_components(kinds, components),
+				},
+				_ => return AbiType {
+					kind: Writer::write(param_type),
+					components: vec![],
+				},
+			}
+		}
+	}
+
+	/// Converts to param type, building tuple types from the components, which are returned too.
+	pub fn into_parts(self) -> Result<(ParamType, Vec<TupleParam>)> {
+		let AbiType { kind, components } = self;
+		if !kind.starts_with("tuple") {
+			return Ok((Reader::read(&kind)?, vec![]));
+		}
+
+		let kinds = components.iter().map(|c| Box::new(c.kind.clone())).collect();
+		let mut param_type = ParamType::Tuple(kinds);
+		let mut suffix = &kind["tuple".len()..];
+		while !suffix.is_empty() {
+			let end = match suffix.find(']') {
+				Some(end) if suffix.starts_with('[') => end,
+				_ => return Err(ErrorKind::InvalidName(kind.clone()).into()),
+			};
+
+			param_type = match &suffix[1..end] {
+				"" => ParamType::Array(Box::new(param_type)),
+				len => {
+					let len = len.parse().map_err(|_| ErrorKind::InvalidName(kind.clone()))?;
+					ParamType::FixedArray(Box::new(param_type), len)
+				},
+			};
+			suffix = &suffix[end + 1..];
+		}
+
+		Ok((param_type, named_components(components)))
+	}
+
+	/// Deserialization helper, converts to param type and its components with the error type of
+	/// the deserializer.
+	pub fn deserialize_parts<E: SerdeError>(self) -> ::core::result::Result<(ParamType, Vec<TupleParam>), E> {
+		self.into_parts().map_err(|e| E::custom(format!("{:?}", e).as_str()))
+	}
+}
+
+/// Tuple param as found in the ABI json.
+#[derive(Serialize, Deserialize)]
+struct AbiTupleParam {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	name: Option<String>,
+	#[serde(flatten)]
+	kind: AbiType,
+	#[serde(rename = "internalType", default, skip_serializing_if = "Option::is_none")]
+	internal_type: Option<String>,
+}
+
+impl<'a> Deserialize<'a> for TupleParam {
+	fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error> where D: Deserializer<'a> {
+		let abi = AbiTupleParam::deserialize(deserializer)?;
+		let (kind, components) = abi.kind.deserialize_parts()?;
+		Ok(TupleParam {
+			name: abi.name,
+			kind,
+			internal_type: abi.internal_type,
+			components,
+		})
+	}
+}
+
+impl Serialize for TupleParam {
+	fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> where S: Serializer {
+		AbiTupleParam {
+			name: self.name.clone(),
+			kind: AbiType::new(&self.kind, &self.components),
+			internal_type: self.internal_type.clone(),
+		}.serialize(serializer)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use {ParamType, TupleParam};
+
+	#[test]
+	fn tuple_param_serialization() {
+		let s = r#"{
+			"name": "order",
+			"type": "tuple[2][]",
+			"components": [
+				{ "name": "maker", "type": "address" },
+				{ "type": "uint256[]" }
+			]
+		}"#;
+
+		let deserialized: TupleParam = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, TupleParam {
+			name: Some("order".to_owned()),
+			kind: "(address,uint256[])[2][]".parse().unwrap(),
+			internal_type: None,
+			components: vec![
+				TupleParam { name: Some("maker".to_owned()), kind: ParamType::Address, internal_type: None, components: vec![] },
+				TupleParam { name: None, kind: "uint256[]".parse().unwrap(), internal_type: None, components: vec![] },
+			],
+		});
+
+		let serialized = serde_json::to_value(&deserialized).unwrap();
+		assert_eq!(serialized, serde_json::from_str::<serde_json::Value>(s).unwrap());
+		assert_eq!(serde_json::from_value::<TupleParam>(serialized).unwrap(), deserialized);
+
+		// without components, tuple components are unnamed
+		let param = TupleParam {
+			name: Some("order".to_owned()),
+			kind: ParamType::Array(Box::new(ParamType::FixedArray(Box::new(
+				ParamType::Tuple(tuple_components![Box::new(ParamType::Address), Box::new(ParamType::Array(Box::new(ParamType::Uint(256))))])
+			), 2))),
+			internal_type: None,
+			components: vec![],
+		};
+		let serialized = serde_json::to_value(&param).unwrap();
+		let expected: serde_json::Value = serde_json::from_str(r#"{
+			"name": "order",
+			"type": "tuple[2][]",
+			"components": [{ "type": "address" }, { "type": "uint256[]" }]
+		}"#).unwrap();
+		assert_eq!(serialized, expected);
+		assert_eq!(serde_json::from_value::<TupleParam>(serialized).unwrap(), param);
+	}
+
+	#[test]
+	fn nested_tuple_components() {
+		let s = r#"{
+			"type": "tuple",
+			"components": [
+				{
+					"name": "inner",
+					"type": "tuple",
+					"components": [
+						{ "name": "owner", "type": "address" },
+						{ "name": "flags", "type": "tuple[]", "components": [
+							{ "type": "bool" },
+							{ "type": "uint256" }
+						] }
+					]
+				},
+				{ "name": "data", "type": "bytes" }
+			]
+		}"#;
+
+		let deserialized: TupleParam = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.kind, "((address,(bool,uint256)[]),bytes)".parse().unwrap());
+		let inner = &deserialized.components[0];
+		assert_eq!(inner.name, Some("inner".to_owned()));
+		assert_eq!(inner.components[1].name, Some("flags".to_owned()));
+		// `flags` components are unnamed
+		assert!(inner.components[1].components.is_empty());
+
+		let serialized = serde_json::to_value(&deserialized).unwrap();
+		assert_eq!(serialized, serde_json::from_str::<serde_json::Value>(s).unwrap());
+		assert_eq!(serde_json::from_value::<TupleParam>(serialized).unwrap(), deserialized);
+	}
+
+	#[test]
+	fn tuple_param_component_names_and_internal_types() {
+		let s = r#"{"name":"positions","type":"tuple[]","components":[{"name":"token","type":"address","internalType":"contract IERC20"},{"name":"state","type":"uint8","internalType":"enum Vault.State"}],"internalType":"struct Vault.Position[]"}"#;
+
+		let deserialized: TupleParam = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, TupleParam {
+			name: Some("positions".to_owned()),
+			kind: "(address,uint8)[]".parse().unwrap(),
+			internal_type: Some("struct Vault.Position[]".to_owned()),
+			components: vec![
+				TupleParam {
+					name: Some("token".to_owned()),
+					kind: ParamType::Address,
+					internal_type: Some("contract IERC20".to_owned()),
+					components: vec![],
+				},
+				TupleParam {
+					name: Some("state".to_owned()),
+					kind: ParamType::Uint(8),
+					internal_type: Some("enum Vault.State".to_owned()),
+					components: vec![],
+				},
+			],
+		});
+		assert_eq!(serde_json::to_string(&deserialized).unwrap(), s);
+
+		// components not matching the type are replaced by unnamed ones
+		let mut changed = deserialized.clone();
+		changed.kind = "(address,uint256)[]".parse().unwrap();
+		assert_eq!(
+			serde_json::to_string(&changed).unwrap(),
+			r#"{"name":"positions","type":"tuple[]","components":[{"name":"token","type":"address","internalType":"contract IERC20"},{"type":"uint256"}],"internalType":"struct Vault.Position[]"}"#
+		);
+	}
+
+	#[test]
+	fn invalid_tuple_suffix() {
+		assert!(serde_json::from_str::<TupleParam>(r#"{ "type": "tuple[" }"#).is_err());
+		assert!(serde_json::from_str::<TupleParam>(r#"{ "type": "tuple[x]" }"#).is_err());
+		assert!(serde_json::from_str::<TupleParam>(r#"{ "type": "tuplex" }"#).is_err());
+	}
+}
@@ -0,0 +1,97 @@
+//! Mapping of ABI types to Rust types, for generating bindings.
+
+use ParamType;
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Maps ABI types to the Rust types of generated bindings, see `Function::generate_rust_signature_with`.
+///
+/// Closures returning `Some` type for the ABI types they map, and `None` for the types mapped
+/// by `default_rust_type`, are mappers too.
+pub trait TypeMapper {
+	/// Returns the Rust type of values of `kind`.
+	fn rust_type(&self, kind: &ParamType) -> String;
+}
+
+/// Maps `address` to `[u8; 20]`, integers to `U256`, `bool` to `bool`, `bytes` to `Vec<u8>`,
+/// `bytesN` to `[u8; N]`, `string` to `String`, `T[]` to `Vec<T>`, `T[k]` to `[T; k]` and
+/// tuples to Rust tuples.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultTypeMapper;
+
+impl TypeMapper for DefaultTypeMapper {
+	fn rust_type(&self, kind: &ParamType) -> String {
+		default_rust_type(self, kind)
+	}
+}
+
+impl<F> TypeMapper for F where F: Fn(&ParamType) -> Option<String> {
+	fn rust_type(&self, kind: &ParamType) -> String {
+		self(kind).unwrap_or_else(|| default_rust_type(self, kind))
+	}
+}
+
+/// Maps `kind` like `DefaultTypeMapper`, mapping the components of arrays and tuples with `mapper`.
+///
+/// Mappers overriding only some types fall back to it for the other types.
+pub fn default_rust_type<M: TypeMapper + ?Sized>(mapper: &M, kind: &ParamType) -> String {
+	match *kind {
+		ParamType::Address => "[u8; 20]".to_owned(),
+		ParamType::Bytes => "Vec<u8>".to_owned(),
+		ParamType::FixedBytes(size) => format!("[u8; {}]", size),
+		ParamType::Int(_) | ParamType::Uint(_) => "U256".to_owned(),
+		ParamType::Bool => "bool".to_owned(),
+		ParamType::String => "String".to_owned(),
+		ParamType::Array(ref kind) => format!("Vec<{}>", mapper.rust_type(kind)),
+		ParamType::FixedArray(ref kind, size) => format!("[{}; {}]", mapper.rust_type(kind), size),
+		ParamType::Tuple(ref kinds) => match kinds.len() {
+			1 => format!("({},)", mapper.rust_type(&kinds[0])),
+			_ => {
+				let kinds: Vec<_> = kinds.iter().map(|kind| mapper.rust_type(kind)).collect();
+				format!("({})", kinds.join(", "))
+			},
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {DefaultTypeMapper, ParamType, TypeMapper};
+
+	#[test]
+	fn test_default_type_mapper() {
+		let rust_type = |kind: &str| DefaultTypeMapper.rust_type(&kind.parse().unwrap());
+
+		assert_eq!(rust_type("address"), "[u8; 20]");
+		assert_eq!(rust_type("bytes"), "Vec<u8>");
+		assert_eq!(rust_type("bytes4"), "[u8; 4]");
+		assert_eq!(rust_type("int8"), "U256");
+		assert_eq!(rust_type("uint256"), "U256");
+		assert_eq!(rust_type("bool"), "bool");
+		assert_eq!(rust_type("string"), "String");
+		assert_eq!(rust_type("string[2]"), "[String; 2]");
+		assert_eq!(rust_type("uint256[][3]"), "[Vec<U256>; 3]");
+		assert_eq!(rust_type("()"), "()");
+		assert_eq!(rust_type("(bool)"), "(bool,)");
+		assert_eq!(rust_type("(address,uint24,bytes)[]"), "Vec<([u8; 20], U256, Vec<u8>)>");
+	}
+
+	#[test]
+	fn test_closure_type_mapper() {
+		let mapper = |kind: &ParamType| match *kind {
+			ParamType::Uint(size) if size <= 64 => Some(format!("u{}", size)),
+			ParamType::Int(size) if size <= 64 => Some(format!("i{}", size)),
+			ParamType::Address => Some("Address".to_owned()),
+			_ => None,
+		};
+		let rust_type = |kind: &str| mapper.rust_type(&kind.parse().unwrap());
+
+		assert_eq!(rust_type("int64"), "i64");
+		assert_eq!(rust_type("uint256"), "U256");
+		assert_eq!(rust_type("address"), "Address");
+		// components of arrays and tuples are mapped by the closure too
+		assert_eq!(rust_type("uint8[]"), "Vec<u8>");
+		assert_eq!(rust_type("(address,int16)[2]"), "[(Address, i16); 2]");
+		assert_eq!(rust_type("(bytes,uint128)"), "(Vec<u8>, U256)");
+	}
+}
@@ -0,0 +1,165 @@
+//! Utilities for ABI values given by users, e.g. in JSON configs or command line arguments.
+
+use hex::{FromHex, ToHex};
+use tiny_keccak::keccak256;
+use {Result, Uint};
+#[cfg(not(feature = "std"))]
+use no_std_prelude::*;
+
+/// Parses an unsigned 256 bit integer, given in decimal or as `0x` prefixed hex.
+///
+/// Fails on invalid characters and values not fitting in 256 bits.
+pub fn parse_uint(s: &str) -> Result<Uint> {
+	match s.strip_prefix("0x") {
+		Some(hex) if !hex.is_empty() && hex.len() <= 64 => {
+			let padded = format!("{:0>64}", hex);
+			let bytes: Vec<u8> = match padded.from_hex() {
+				Ok(bytes) => bytes,
+				Err(_) => bail!("Invalid hex integer `{}`", s),
+			};
+			Ok(Uint::from(&bytes[..]))
+		},
+		Some(_) => bail!("Invalid hex integer `{}`", s),
+		None if s.is_empty() => bail!("Invalid integer ``"),
+		None => match Uint::from_dec_str(s) {
+			Ok(value) => Ok(value),
+			Err(_) if s.bytes().all(|b| b.is_ascii_digit()) => bail!("Integer `{}` does not fit in 256 bits", s),
+			Err(_) => bail!("Invalid integer `{}`", s),
+		},
+	}
+}
+
+/// Parses a signed 256 bit integer, given in decimal or as `0x` prefixed hex, optionally
+/// preceded by `-`, e.g. `-1000` or `-0x3e8`.
+///
+/// Returns the 256 bit two's complement of the value, as held by `Token::Int`. Fails on
+/// invalid characters and values outside of `-2^255..2^255`.
+pub fn parse_int(s: &str) -> Result<Uint> {
+	let min = Uint::one() << 255;
+	match s.strip_prefix('-') {
+		Some(magnitude) => {
+			let magnitude = parse_uint(magnitude)?;
+			if magnitude > min {
+				bail!("Integer `{}` does not fit in 256 bits", s);
+			}
+			Ok((!magnitude).overflowing_add(Uint::one()).0)
+		},
+		None => {
+			let value = parse_uint(s)?;
+			if value >= min {
+				bail!("Integer `{}` does not fit in 256 bits", s);
+			}
+			Ok(value)
+		},
+	}
+}
+
+/// Formats the address as `0x` prefixed hex with the EIP-55 mixed case checksum: letters
+/// are uppercase where the matching nibble of the hash of the lowercase hex is at least 8.
+pub fn to_checksum_address(address: &[u8; 20]) -> String {
+	let hex = address.to_hex::<String>();
+	let hash = keccak256(hex.as_bytes());
+	let checksummed: String = hex.chars()
+		.enumerate()
+		.map(|(i, c)| {
+			let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0f };
+			if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+		})
+		.collect();
+	format!("0x{}", checksummed)
+}
+
+/// Parses a `0x` prefixed hex address, validating its EIP-55 checksum.
+///
+/// All lowercase and all uppercase addresses carry no checksum and are accepted as they are.
+pub fn from_checksum_address(s: &str) -> Result<[u8; 20]> {
+	let hex = match s.strip_prefix("0x") {
+		Some(hex) if hex.len() == 40 => hex,
+		_ => bail!("Invalid address `{}`", s),
+	};
+	let bytes: Vec<u8> = hex.from_hex()?;
+	let mut address = [0u8; 20];
+	address.copy_from_slice(&bytes);
+
+	let has_lowercase = hex.chars().any(|c| c.is_ascii_lowercase());
+	let has_uppercase = hex.chars().any(|c| c.is_ascii_uppercase());
+	if has_lowercase && has_uppercase && to_checksum_address(&address) != s {
+		bail!("Invalid checksum of address `{}`", s);
+	}
+	Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_uint, parse_int, to_checksum_address, from_checksum_address};
+	use Uint;
+
+	#[test]
+	fn test_parse_uint() {
+		let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+		assert_eq!(parse_uint("0").unwrap(), Uint::zero());
+		assert_eq!(parse_uint("1000").unwrap(), Uint::from(1000));
+		assert_eq!(parse_uint("0x3e8").unwrap(), Uint::from(1000));
+		assert_eq!(parse_uint("0x00003E8").unwrap(), Uint::from(1000));
+		assert_eq!(parse_uint(max).unwrap(), Uint::max_value());
+		assert_eq!(parse_uint(&format!("0x{}", "f".repeat(64))).unwrap(), Uint::max_value());
+
+		assert_eq!(
+			format!("{}", parse_uint("115792089237316195423570985008687907853269984665640564039457584007913129639936").unwrap_err()),
+			"Integer `115792089237316195423570985008687907853269984665640564039457584007913129639936` does not fit in 256 bits"
+		);
+		assert!(parse_uint(&format!("0x1{}", "0".repeat(64))).is_err());
+		assert!(parse_uint("").is_err());
+		assert!(parse_uint("0x").is_err());
+		assert!(parse_uint("-1").is_err());
+		assert!(parse_uint("1e18").is_err());
+		assert!(parse_uint("0x3g8").is_err());
+		assert!(parse_uint(" 1").is_err());
+	}
+
+	#[test]
+	fn test_parse_int() {
+		let min = Uint::one() << 255;
+		assert_eq!(parse_int("0").unwrap(), Uint::zero());
+		assert_eq!(parse_int("-0").unwrap(), Uint::zero());
+		assert_eq!(parse_int("1").unwrap(), Uint::one());
+		assert_eq!(parse_int("-1").unwrap(), Uint::max_value());
+		assert_eq!(parse_int("-0x1").unwrap(), Uint::max_value());
+		assert_eq!(parse_int("-1000").unwrap(), parse_int("-0x3e8").unwrap());
+		assert_eq!(parse_int("57896044618658097711785492504343953926634992332820282019728792003956564819967").unwrap(), min - 1);
+		assert_eq!(parse_int("-57896044618658097711785492504343953926634992332820282019728792003956564819968").unwrap(), min);
+
+		assert!(parse_int("57896044618658097711785492504343953926634992332820282019728792003956564819968").is_err());
+		assert!(parse_int("-57896044618658097711785492504343953926634992332820282019728792003956564819969").is_err());
+		assert!(parse_int(&format!("0x8{}", "0".repeat(63))).is_err());
+		assert!(parse_int("--1").is_err());
+		assert!(parse_int("-").is_err());
+		assert!(parse_int("+1").is_err());
+	}
+
+	#[test]
+	fn test_checksum_address() {
+		// test vectors of EIP-55
+		for address in &[
+			"0x52908400098527886E0F7030069857D2E4169EE7",
+			"0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+			"0xde709f2102306220921060314715629080e2fb77",
+			"0x27b1fdb04752bbc536007a920d24acb045561c26",
+			"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+			"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+			"0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+			"0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+		] {
+			let parsed = from_checksum_address(address).unwrap();
+			assert_eq!(to_checksum_address(&parsed), *address);
+			assert_eq!(from_checksum_address(&address.to_lowercase()).unwrap(), parsed);
+			assert_eq!(from_checksum_address(&address.to_uppercase().replace("0X", "0x")).unwrap(), parsed);
+		}
+
+		assert!(from_checksum_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").is_err());
+		assert!(from_checksum_address("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+		assert!(from_checksum_address("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+		assert!(from_checksum_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA").is_err());
+		assert!(from_checksum_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg").is_err());
+	}
+}
@@ -0,0 +1,66 @@
+//! Uses the library from a `no_std` crate.
+//!
+//! Run with `cargo test --no-default-features --test no_std` to check the `no_std` build.
+
+#![no_std]
+
+extern crate alloc;
+#[macro_use]
+extern crate ethabi;
+
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use ethabi::{decode, encode, Function, Param, ParamType, StateMutability, Token};
+
+#[test]
+fn encode_decode() {
+	let types = [
+		ParamType::Address,
+		ParamType::Array(Box::new(ParamType::String)),
+		ParamType::Tuple(tuple_components![Box::new(ParamType::Uint(256)), Box::new(ParamType::Bytes)]),
+	];
+	let tokens = vec![
+		Token::Address([0x11u8; 20].into()),
+		Token::Array(vec![Token::String("no".into()), Token::String("std".into())]),
+		Token::Tuple(vec![Token::Uint(7.into()), Token::Bytes(vec![1, 2, 3])]),
+	];
+
+	let encoded = encode(&tokens);
+	assert_eq!(decode(&types, &encoded).unwrap(), tokens);
+}
+
+#[test]
+fn encode_function_call() {
+	let function = Function {
+		name: "transfer".into(),
+		inputs: vec![
+			Param { name: "to".into(), kind: ParamType::Address, internal_type: None, components: vec![] },
+			Param { name: "amount".into(), kind: ParamType::Uint(256), internal_type: None, components: vec![] },
+		],
+		outputs: vec![],
+		state_mutability: StateMutability::NonPayable,
+	};
+	let tokens = [Token::Address([0x22u8; 20].into()), Token::Uint(1000.into())];
+
+	let encoded = function.encode_input(&tokens).unwrap();
+	assert_eq!(encoded[..4], [0xa9, 0x05, 0x9c, 0xbb]);
+	assert_eq!(function.decode_input(&encoded).unwrap(), tokens.iter().cloned().collect::<Vec<_>>());
+}
+
+#[test]
+fn errors_display() {
+	let err = decode(&[ParamType::Bool], &[0u8; 31]).unwrap_err();
+	assert_eq!(err.to_string(), "Invalid data at byte offset 0: data length is not a multiple of 32");
+}
+
+#[test]
+fn errors_chain() {
+	let err = decode(&[ParamType::Bytes], &[0xffu8; 32]).unwrap_err();
+	let messages: Vec<_> = err.iter().map(|err| err.to_string()).collect();
+	assert_eq!(messages, vec![
+		"Cannot decode bytes".to_string(),
+		"Invalid data at byte offset 0: value does not fit in 32 bits".to_string(),
+	]);
+}
@@ -0,0 +1,38 @@
+//! Encode-decode round-trip property tests.
+//!
+//! Run with `cargo test --features proptest`.
+//!
+//! Known limitations:
+//! - only the standard encoding is covered, `encode_packed` is ambiguous and can't be decoded;
+//! - indexed event params of dynamic types are hashed into topics, so they can't round-trip either.
+
+#![cfg(feature = "proptest")]
+
+extern crate ethabi;
+#[macro_use]
+extern crate proptest;
+
+use ethabi::proptest_strategies::param_type_and_token;
+use ethabi::{decode, encode, encoded_size, ParamType, Token};
+use proptest::prelude::*;
+
+fn types_and_tokens() -> impl Strategy<Value = (Vec<ParamType>, Vec<Token>)> {
+	prop::collection::vec(param_type_and_token(3), 0..=4)
+		.prop_map(|pairs| pairs.into_iter().unzip())
+}
+
+proptest! {
+	#[test]
+	fn decode_inverts_encode((types, tokens) in types_and_tokens()) {
+		let encoded = encode(&tokens);
+		prop_assert_eq!(encoded.len(), encoded_size(&tokens));
+		prop_assert_eq!(decode(&types, &encoded).unwrap(), tokens);
+	}
+
+	#[test]
+	fn encode_inverts_decode((types, tokens) in types_and_tokens()) {
+		let encoded = encode(&tokens);
+		let decoded = decode(&types, &encoded).unwrap();
+		prop_assert_eq!(encode(&decoded), encoded);
+	}
+}
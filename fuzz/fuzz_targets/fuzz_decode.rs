@@ -0,0 +1,31 @@
+//! Decodes arbitrary data as a few fixed lists of types, decoding must never panic.
+
+#![no_main]
+
+use ethabi::{decode, tuple_components, ParamType};
+use libfuzzer_sys::fuzz_target;
+
+fn types() -> Vec<Vec<ParamType>> {
+	let address_array = ParamType::Array(Box::new(ParamType::Address));
+	let dynamic_tuple = ParamType::Tuple(tuple_components![
+		Box::new(ParamType::Uint(256)),
+		Box::new(ParamType::String),
+		Box::new(ParamType::Array(Box::new(ParamType::Bytes))),
+	]);
+
+	vec![
+		vec![ParamType::Address, ParamType::Uint(256), ParamType::Bool],
+		vec![ParamType::Int(8), ParamType::FixedBytes(5), ParamType::FixedBytes(32)],
+		vec![ParamType::Bytes, ParamType::String],
+		vec![address_array.clone(), ParamType::FixedArray(Box::new(ParamType::String), 2)],
+		vec![ParamType::Array(Box::new(address_array.clone())), ParamType::FixedArray(Box::new(address_array), 3)],
+		vec![dynamic_tuple.clone(), ParamType::Array(Box::new(dynamic_tuple.clone()))],
+		vec![ParamType::Tuple(tuple_components![Box::new(dynamic_tuple), Box::new(ParamType::Tuple(tuple_components![]))])],
+	]
+}
+
+fuzz_target!(|data: &[u8]| {
+	for types in types() {
+		let _ = decode(&types, data);
+	}
+});
@@ -0,0 +1,14 @@
+//! Parses arbitrary strings as param types, parsing must never panic.
+//!
+//! Successfully parsed types must also survive a round-trip through their canonical name.
+
+#![no_main]
+
+use ethabi::param_type::Reader;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|name: &str| {
+	if let Ok(kind) = Reader::read(name) {
+		assert_eq!(Reader::read(&kind.to_string()).unwrap(), kind);
+	}
+});
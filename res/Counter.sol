@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT
+pragma solidity >=0.7.0 <0.9.0;
+
+contract Counter {
+	uint256 public count;
+
+	event Incremented(uint256 count);
+
+	function increment() public {
+		count += 1;
+		emit Incremented(count);
+	}
+}
+
+contract Ownable {
+	address public owner;
+
+	event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+
+	constructor() {
+		owner = msg.sender;
+	}
+
+	function transferOwnership(address newOwner) public {
+		require(msg.sender == owner);
+		emit OwnershipTransferred(owner, newOwner);
+		owner = newOwner;
+	}
+}
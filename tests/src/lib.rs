@@ -20,6 +20,8 @@ use_contract!(validators, "../res/Validators.abi");
 use_contract!(operations, "../res/Operations.abi");
 use_contract!(urlhint, "../res/urlhint.abi");
 use_contract!(test_rust_keywords, "../res/test_rust_keywords.abi");
+use_contract!(tuple, "../res/tuple.abi");
+use_contract!(overloads, "../res/overloads.abi");
 
 #[cfg(test)]
 mod tests {
@@ -105,6 +107,42 @@ mod tests {
 		assert_eq!(expected_string, encoded_from_string.to_hex::<String>())
 	}
 
+	#[test]
+	fn test_encoding_and_decoding_tuples() {
+		use tuple::functions;
+
+		let maker: Address = [0x11u8; 20].into();
+		let encoded = functions::place_order::encode_input((maker, Uint::from(5)), true);
+		let expected = hex!("
+			2cdb1e0c
+			0000000000000000000000001111111111111111111111111111111111111111
+			0000000000000000000000000000000000000000000000000000000000000005
+			0000000000000000000000000000000000000000000000000000000000000001
+		").to_vec();
+		assert_eq!(expected, encoded);
+
+		let output = hex!("
+			0000000000000000000000001111111111111111111111111111111111111111
+			0000000000000000000000000000000000000000000000000000000000000005
+			0000000000000000000000000000000000000000000000000000000000000001
+		");
+		let decoded_output = functions::get_order::decode_output(&output).unwrap();
+		assert_eq!((maker, (Uint::from(5), true)), decoded_output);
+	}
+
+	#[test]
+	fn test_encoding_overloaded_functions() {
+		use overloads::functions;
+
+		let to = [0x11u8; 20];
+		let encoded = functions::transfer::encode_input(to, 1);
+		let encoded_with_data = functions::transfer_1::encode_input(to, 1, vec![0x12]);
+
+		assert_eq!(encoded[..4], hex!("a9059cbb"));
+		assert_eq!(encoded_with_data[..4], hex!("be45fd62"));
+		assert_eq!(encoded[4..], encoded_with_data[4..68]);
+	}
+
 	#[test]
 	fn encoding_input_works() {
 		use eip20;
@@ -1,8 +1,12 @@
 #!/bin/bash
 
+set -eo pipefail
+
 cd res
 
 for sol in *.sol; do
+	# Counter.sol is compiled into the solc output fixtures below
+	[ "$sol" = "Counter.sol" ] && continue
 	solc --abi -o . --overwrite "$sol"
 done
 
@@ -12,3 +16,17 @@ for abi in *.abi; do
 done
 
 rm tmp
+
+# solc before 0.8 encodes the abi in combined json output as a string
+SOLC_0_7=${SOLC_0_7:-solc-0.7.6}
+SOLC_0_8=${SOLC_0_8:-solc}
+
+$SOLC_0_7 --combined-json abi Counter.sol > Counter.combined.json
+$SOLC_0_8 --combined-json abi Counter.sol > Counter.combined-0.8.json
+$SOLC_0_8 --standard-json --allow-paths . > Counter.standard.json <<JSON
+{
+	"language": "Solidity",
+	"sources": { "Counter.sol": { "urls": ["Counter.sol"] } },
+	"settings": { "outputSelection": { "*": { "*": ["abi"] } } }
+}
+JSON